@@ -1,53 +1,248 @@
+use crate::miner::MinerError;
 use anyhow::{Context, Result};
-use kaspa_addresses::Address;
+use futures_util::stream::{self, StreamExt};
+use kaspa_addresses::{Address, AddressError, Prefix, Version};
+use kaspa_consensus_core::block::Block;
+use kaspa_consensus_core::header::Header;
 use kaspa_grpc_client::GrpcClient;
+use kaspa_hashes::Hash;
 use kaspa_rpc_core::{
     api::rpc::RpcApi, notify::mode::NotificationMode, GetBlockTemplateRequest, RpcRawBlock,
     SubmitBlockRequest, SubmitBlockResponse,
 };
+use serde::Serialize;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, warn};
 
+/// Structured result of validating a Kaspa address
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidatedAddress {
+    pub is_valid: bool,
+    /// Network prefix, e.g. `kaspa` or `kaspatest`. Set even if the rest fails to decode.
+    pub network: String,
+    /// Script type and payload size, e.g. `Schnorr P2PK (32-byte payload)`
+    pub address_type: String,
+    pub error: Option<String>,
+}
+
+/// Validate a Kaspa address and extract its network prefix, version and payload length
+pub fn validate_address(address: &str) -> ValidatedAddress {
+    let mut info = ValidatedAddress {
+        is_valid: false,
+        network: String::new(),
+        address_type: String::new(),
+        error: None,
+    };
+
+    let address = address.trim();
+    if address.is_empty() {
+        info.error = Some("Address is empty".to_string());
+        return info;
+    }
+
+    // Check the prefix separately so an unknown network is reported even if
+    // the rest of the address would fail to decode
+    match address.split_once(':') {
+        Some((prefix, _)) => match Prefix::try_from(prefix) {
+            Ok(prefix) => info.network = prefix.to_string(),
+            Err(_) => {
+                info.error = Some(format!("Unknown network prefix \"{}\"", prefix));
+                return info;
+            }
+        },
+        None => {
+            info.error = Some("Missing network prefix (e.g. kaspatest:)".to_string());
+            return info;
+        }
+    }
+
+    match Address::try_from(address) {
+        Ok(parsed) => {
+            info.is_valid = true;
+            info.network = parsed.prefix.to_string();
+            let kind = match parsed.version {
+                Version::PubKey => "Schnorr P2PK",
+                Version::PubKeyECDSA => "ECDSA P2PK",
+                Version::ScriptHash => "P2SH",
+            };
+            info.address_type = format!("{} ({}-byte payload)", kind, parsed.payload.len());
+        }
+        Err(AddressError::BadChecksum) => info.error = Some("Wrong checksum".to_string()),
+        Err(AddressError::BadPayload) => info.error = Some("Wrong length".to_string()),
+        Err(e) => info.error = Some(e.to_string()),
+    }
+
+    info
+}
+
+/// A peer connected to the node
+#[derive(Debug, Clone, Serialize)]
+pub struct PeerInfo {
+    pub id: String,
+    pub address: String,
+    pub is_outbound: bool,
+    pub time_connected_secs: u64,
+}
+
+/// What the node reports about itself, shown in the Node Info panel
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeInfo {
+    pub server_version: String,
+    pub is_synced: bool,
+    pub virtual_daa_score: u64,
+    pub network_name: String,
+    pub p2p_id: String,
+    pub mempool_size: u64,
+    /// Connection attempts it took to reach the node
+    pub connection_attempts: u32,
+}
+
+/// Network-wide figures shown in the Network panel
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkInfo {
+    /// Estimated over the last `NETWORK_HASHRATE_WINDOW` blocks
+    pub network_hashrate_hps: f64,
+    pub difficulty: f64,
+    pub block_count: u64,
+    pub header_count: u64,
+    pub virtual_daa_score: u64,
+}
+
+/// Blocks the node averages over when estimating the network hashrate
+pub const NETWORK_HASHRATE_WINDOW: u32 = 1000;
+
+/// Largest gRPC message accepted from the node unless configured otherwise.
+/// Block templates on large DAGs can exceed the old 500 kB limit.
+pub const DEFAULT_GRPC_MAX_MESSAGE_BYTES: usize = 1024 * 1024;
+
+/// How `KaspaApi::new_with_failover` connects and retries
+#[derive(Debug, Clone)]
+pub struct ApiConnectionConfig {
+    /// Give up after this many attempts; `None` retries forever (the GUI default)
+    pub max_attempts: Option<u32>,
+    /// Largest gRPC message accepted from the node; templates above it fail to decode
+    pub grpc_max_message_bytes: usize,
+}
+
+impl Default for ApiConnectionConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: None,
+            grpc_max_message_bytes: DEFAULT_GRPC_MAX_MESSAGE_BYTES,
+        }
+    }
+}
+
+/// How often `wait_for_sync` asks the node whether it is synced
+const SYNC_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Why `KaspaApi::wait_for_sync_with_timeout` gave up
+#[derive(Debug, thiserror::Error)]
+pub enum SyncError {
+    #[error("node did not finish syncing within {}s", .0.as_secs())]
+    Timeout(Duration),
+    #[error("failed to check sync status: {0}")]
+    RpcError(anyhow::Error),
+}
+
+/// Add the grpc:// prefix if not present
+fn grpc_url(address: &str) -> String {
+    if address.starts_with("grpc://") {
+        address.to_string()
+    } else {
+        format!("grpc://{}", address)
+    }
+}
+
+async fn connect_client(
+    grpc_address: &str,
+    max_message_bytes: usize,
+) -> kaspa_grpc_client::error::Result<GrpcClient> {
+    GrpcClient::connect_with_args(
+        NotificationMode::Direct,
+        grpc_address.to_string(),
+        None,
+        true,
+        None,
+        false,
+        Some(max_message_bytes),
+        Default::default(),
+    )
+    .await
+}
+
 /// Simplified Kaspa API client for standalone miner
 pub struct KaspaApi {
     client: Arc<GrpcClient>,
+    address: String,
+    /// Attempts the connection took, counting every address tried
+    attempt: AtomicU32,
 }
 
 impl KaspaApi {
     /// Create a new Kaspa API client
     pub async fn new(address: String) -> Result<Arc<Self>> {
-        // Add grpc:// prefix if not present
-        let grpc_address = if address.starts_with("grpc://") {
-            address.clone()
-        } else {
-            format!("grpc://{}", address)
-        };
+        Self::new_with_failover(vec![address], &ApiConnectionConfig::default()).await
+    }
 
-        debug!("Connecting to Kaspa node at {}", grpc_address);
+    /// Connect to the first reachable node in `addresses`, tried in order. After
+    /// each full pass over the list the backoff doubles (up to 5s) and the list
+    /// is tried again from the start. Fails with
+    /// `MinerError::MaxReconnectionAttemptsExceeded` once `config.max_attempts` is used up.
+    pub async fn new_with_failover(
+        addresses: Vec<String>,
+        config: &ApiConnectionConfig,
+    ) -> Result<Arc<Self>> {
+        let addresses: Vec<String> = addresses
+            .into_iter()
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect();
+        if addresses.is_empty() {
+            return Err(anyhow::anyhow!("No node address given"));
+        }
 
+        debug!(
+            "Max gRPC message size: {} bytes",
+            config.grpc_max_message_bytes
+        );
         let mut attempt = 0;
         let mut backoff_ms = 250u64;
 
-        let client = loop {
+        let (client, address) = loop {
+            let address = &addresses[attempt % addresses.len()];
             attempt += 1;
-            let connect_fut = GrpcClient::connect_with_args(
-                NotificationMode::Direct,
-                grpc_address.clone(),
-                None,
-                true,
-                None,
-                false,
-                Some(500_000),
-                Default::default(),
-            );
-
-            match connect_fut.await {
-                Ok(client) => break Arc::new(client),
+            let grpc_address = grpc_url(address);
+            debug!("Connecting to Kaspa node at {}", grpc_address);
+            match connect_client(&grpc_address, config.grpc_max_message_bytes).await {
+                Ok(client) => break (Arc::new(client), address.clone()),
+                Err(e)
+                    if config
+                        .max_attempts
+                        .is_some_and(|max| attempt >= max as usize) =>
+                {
+                    warn!(
+                        "Failed to connect to kaspa node {} (attempt {}): {}, giving up",
+                        address, attempt, e
+                    );
+                    return Err(MinerError::MaxReconnectionAttemptsExceeded {
+                        attempts: attempt as u32,
+                    }
+                    .into());
+                }
+                Err(e) if attempt % addresses.len() != 0 => {
+                    warn!(
+                        "Failed to connect to kaspa node {} (attempt {}): {}, trying next address",
+                        address, attempt, e
+                    );
+                }
                 Err(e) => {
                     warn!(
-                        "Failed to connect to kaspa node (attempt {}): {}, retrying in {:.2}s",
+                        "Failed to connect to kaspa node {} (attempt {}): {}, retrying in {:.2}s",
+                        address,
                         attempt,
                         e,
                         Duration::from_millis(backoff_ms).as_secs_f64()
@@ -62,9 +257,89 @@ impl KaspaApi {
         // Start the client
         client.start(None).await;
 
-        debug!("Connected to Kaspa node successfully");
+        debug!("Connected to Kaspa node {} successfully", address);
+
+        Ok(Arc::new(Self {
+            client,
+            address,
+            attempt: AtomicU32::new(attempt as u32),
+        }))
+    }
+
+    /// Make a single connection attempt, failing if it takes longer than `timeout`.
+    /// Unlike `new`, errors are returned instead of retried.
+    pub async fn new_with_timeout(address: String, timeout: Duration) -> Result<Arc<Self>> {
+        let grpc_address = grpc_url(&address);
+        let connect = connect_client(&grpc_address, DEFAULT_GRPC_MAX_MESSAGE_BYTES);
+        let client = tokio::time::timeout(timeout, connect)
+            .await
+            .map_err(anyhow::Error::from)?
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let client = Arc::new(client);
+        client.start(None).await;
+        Ok(Arc::new(Self {
+            client,
+            address,
+            attempt: AtomicU32::new(1),
+        }))
+    }
+
+    /// Round trip to the node, returning how long it took
+    pub async fn health_check(&self) -> Result<Duration> {
+        let started = std::time::Instant::now();
+        self.client
+            .get_info()
+            .await
+            .context("Failed to get node info")?;
+        Ok(started.elapsed())
+    }
+
+    /// Close the connection. Used for short-lived clients such as connection tests.
+    pub async fn disconnect(&self) {
+        if let Err(e) = self.client.disconnect().await {
+            debug!("Error disconnecting from {}: {}", self.address, e);
+        }
+    }
+
+    /// Node address this client is connected to
+    pub fn active_address(&self) -> &str {
+        &self.address
+    }
+
+    /// Connection attempts it took to reach the node
+    pub fn connection_attempts(&self) -> u32 {
+        self.attempt.load(Ordering::Relaxed)
+    }
 
-        Ok(Arc::new(Self { client }))
+    /// Like `wait_for_sync`, but gives up after `timeout`. Fails with `RpcError`
+    /// instead of `Timeout` if the node couldn't be asked at the last check.
+    pub async fn wait_for_sync_with_timeout(&self, timeout: Duration) -> Result<(), SyncError> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            let last_error = match tokio::time::timeout(remaining, self.client.get_info()).await {
+                Ok(Ok(info)) if info.is_synced => {
+                    debug!("Node is synced");
+                    return Ok(());
+                }
+                Ok(Ok(_)) => {
+                    debug!("Node not synced yet, waiting...");
+                    None
+                }
+                Ok(Err(e)) => {
+                    warn!("Error checking sync status: {}", e);
+                    Some(anyhow::Error::from(e))
+                }
+                Err(_) => None,
+            };
+            if tokio::time::Instant::now() + SYNC_POLL_INTERVAL > deadline {
+                return Err(match last_error {
+                    Some(e) => SyncError::RpcError(e),
+                    None => SyncError::Timeout(timeout),
+                });
+            }
+            sleep(SYNC_POLL_INTERVAL).await;
+        }
     }
 
     /// Wait for node to sync
@@ -82,14 +357,36 @@ impl KaspaApi {
                     warn!("Error checking sync status: {}", e);
                 }
             }
-            sleep(Duration::from_secs(2)).await;
+            sleep(SYNC_POLL_INTERVAL).await;
         }
     }
 
+    /// Blocks per second, from how far the virtual DAA score moves over `window`.
+    /// GetInfo has no DAA score, so this reads the DAG info like `get_node_info`.
+    pub async fn measure_bps(&self, window: Duration) -> Result<f64> {
+        let start = self
+            .client
+            .get_block_dag_info()
+            .await
+            .context("Failed to get block DAG info")?;
+        let started = std::time::Instant::now();
+        sleep(window).await;
+        let end = self
+            .client
+            .get_block_dag_info()
+            .await
+            .context("Failed to get block DAG info")?;
+        let blocks = end
+            .virtual_daa_score
+            .saturating_sub(start.virtual_daa_score);
+        Ok(blocks as f64 / started.elapsed().as_secs_f64())
+    }
+
     /// Get block template for mining (with retry logic matching rkstratum_cpu_miner.rs)
     pub async fn get_block_template_rpc(
         &self,
         mining_address: &str,
+        extra_data: &[u8],
     ) -> Result<(kaspa_consensus_core::block::Block, RpcRawBlock)> {
         // Retry up to 3 times if we get "Odd number of digits" error
         // This error can occur if the block template has malformed hash fields
@@ -107,7 +404,7 @@ impl KaspaApi {
                 .client
                 .get_block_template_call(
                     None,
-                    GetBlockTemplateRequest::new(address, b"internal".to_vec()),
+                    GetBlockTemplateRequest::new(address, extra_data.to_vec()),
                 )
                 .await
             {
@@ -164,6 +461,83 @@ impl KaspaApi {
         ))
     }
 
+    /// Version, sync state and network of the node
+    pub async fn get_node_info(&self) -> Result<NodeInfo> {
+        let info = self
+            .client
+            .get_info()
+            .await
+            .context("Failed to get node info")?;
+        // GetInfo doesn't say which network the node is on; the DAG info does
+        let dag = self
+            .client
+            .get_block_dag_info()
+            .await
+            .context("Failed to get block DAG info")?;
+        Ok(NodeInfo {
+            server_version: info.server_version,
+            is_synced: info.is_synced,
+            virtual_daa_score: dag.virtual_daa_score,
+            network_name: dag.network.to_string(),
+            p2p_id: info.p2p_id,
+            mempool_size: info.mempool_size,
+            connection_attempts: self.connection_attempts(),
+        })
+    }
+
+    /// Hashrate, difficulty and block counts of the network the node is on
+    pub async fn get_network_info(&self) -> Result<NetworkInfo> {
+        let dag = self
+            .client
+            .get_block_dag_info()
+            .await
+            .context("Failed to get block DAG info")?;
+        let network_hashrate_hps = self
+            .client
+            .estimate_network_hashes_per_second(NETWORK_HASHRATE_WINDOW, None)
+            .await
+            .context("Failed to estimate network hashrate")?;
+        Ok(NetworkInfo {
+            network_hashrate_hps: network_hashrate_hps as f64,
+            difficulty: dag.difficulty,
+            block_count: dag.block_count,
+            header_count: dag.header_count,
+            virtual_daa_score: dag.virtual_daa_score,
+        })
+    }
+
+    /// Address prefix of the network the node is on, or `None` if it can't be
+    /// asked. Like `get_node_info`, this reads the network from the DAG info.
+    pub async fn network_prefix(&self) -> Option<Prefix> {
+        match self.client.get_block_dag_info().await {
+            Ok(dag) => Some(Prefix::from(dag.network.network_type())),
+            Err(e) => {
+                warn!("Failed to get node network: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Peers currently connected to the node
+    pub async fn get_peers(&self) -> Result<Vec<PeerInfo>> {
+        let response = self
+            .client
+            .get_connected_peer_info()
+            .await
+            .context("Failed to get connected peer info")?;
+        Ok(response
+            .peer_info
+            .into_iter()
+            .map(|peer| PeerInfo {
+                id: peer.id.to_string(),
+                address: peer.address.to_string(),
+                is_outbound: peer.is_outbound,
+                // The node reports connection time in milliseconds
+                time_connected_secs: peer.time_connected / 1000,
+            })
+            .collect())
+    }
+
     /// Submit a mined block
     pub async fn submit_rpc_block(&self, rpc_block: RpcRawBlock) -> Result<SubmitBlockResponse> {
         let request = SubmitBlockRequest::new(rpc_block, false);
@@ -172,4 +546,63 @@ impl KaspaApi {
             .await
             .context("Failed to submit block")
     }
+
+    /// Submit an all-zero block (nonce 0) to check that the submit path reaches the
+    /// node, independently of mining. The node is expected to reject it; only a
+    /// failed RPC call is returned as an error.
+    pub async fn test_submit_dummy_block(&self) -> Result<String> {
+        let header = Header::from_precomputed_hash(Hash::default(), vec![]);
+        let block = Block::new(header, vec![]);
+        let response = self.submit_rpc_block(RpcRawBlock::from(&block)).await?;
+        debug!("Test submit report: {:?}", response.report);
+        Ok(if response.report.is_success() {
+            "Node accepted test submit (unexpected)".to_string()
+        } else {
+            "Node rejected test submit as expected".to_string()
+        })
+    }
+
+    /// Submit several mined blocks, keeping up to `concurrency` requests in flight.
+    /// Results are returned in the same order as `blocks`. A failed call is retried
+    /// up to `retries` times, waiting `retry_backoff` and doubling it after each try.
+    /// Timeouts are not retried: the node may still be processing the block.
+    /// Each result comes with the time spent on it, retries included.
+    pub async fn submit_batch(
+        &self,
+        blocks: Vec<RpcRawBlock>,
+        concurrency: usize,
+        timeout: Duration,
+        retries: u32,
+        retry_backoff: Duration,
+    ) -> Vec<(Result<SubmitBlockResponse>, Duration)> {
+        stream::iter(blocks)
+            .map(|block| async move {
+                let started = std::time::Instant::now();
+                let mut attempt = 0;
+                let mut backoff = retry_backoff;
+                loop {
+                    // A timed-out submit surfaces as a `tokio::time::error::Elapsed` error
+                    let result =
+                        tokio::time::timeout(timeout, self.submit_rpc_block(block.clone()))
+                            .await
+                            .map_err(anyhow::Error::from)
+                            .and_then(|result| result);
+                    match result {
+                        Err(e) if attempt < retries && !e.is::<tokio::time::error::Elapsed>() => {
+                            attempt += 1;
+                            warn!(
+                                "Submit block failed (retry {}/{} in {:?}): {:#}",
+                                attempt, retries, backoff, e
+                            );
+                            sleep(backoff).await;
+                            backoff = backoff.saturating_mul(2);
+                        }
+                        result => return (result, started.elapsed()),
+                    }
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
 }
@@ -0,0 +1,85 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// File name of the address book inside the app data directory
+pub const ADDRESS_BOOK_FILE_NAME: &str = "address_book.json";
+
+/// Which field an address book entry fills in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressType {
+    Node,
+    Mining,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressBookEntry {
+    pub label: String,
+    pub address: String,
+    pub address_type: AddressType,
+}
+
+/// Saved node and mining addresses, so switching wallets or nodes doesn't
+/// mean pasting them in again. Labels are unique per `AddressType`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AddressBook {
+    pub entries: Vec<AddressBookEntry>,
+}
+
+impl AddressBook {
+    /// An empty book if nothing was saved yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let text =
+            serde_json::to_string_pretty(self).context("Failed to serialize address book")?;
+        std::fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Add an entry, replacing the address of an existing one with the same
+    /// label and type
+    pub fn add(&mut self, entry: AddressBookEntry) -> Result<()> {
+        let label = entry.label.trim();
+        let address = entry.address.trim();
+        if label.is_empty() {
+            return Err(anyhow::anyhow!("Label must not be empty"));
+        }
+        if address.is_empty() {
+            return Err(anyhow::anyhow!("Address must not be empty"));
+        }
+        let entry = AddressBookEntry {
+            label: label.to_string(),
+            address: address.to_string(),
+            address_type: entry.address_type,
+        };
+        match self
+            .entries
+            .iter_mut()
+            .find(|e| e.address_type == entry.address_type && e.label == entry.label)
+        {
+            Some(existing) => existing.address = entry.address,
+            None => self.entries.push(entry),
+        }
+        Ok(())
+    }
+
+    /// Remove the entry with this label and type. Returns false if there was none.
+    pub fn remove(&mut self, label: &str, address_type: AddressType) -> bool {
+        let before = self.entries.len();
+        self.entries
+            .retain(|e| !(e.address_type == address_type && e.label == label));
+        self.entries.len() != before
+    }
+}
@@ -0,0 +1,226 @@
+pub mod address_book;
+pub mod profiles;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// File name of the saved profile inside the app data directory
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Current on-disk format of `MinerProfile`
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// User-settable miner settings, persisted between runs. Missing fields fall
+/// back to their defaults so older files keep loading.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MinerProfile {
+    /// Missing in files written before the field existed, which are schema 0
+    #[serde(default)]
+    pub schema_version: u32,
+    /// Node addresses in failover order
+    pub node_addresses: Vec<String>,
+    pub mining_address: String,
+    pub threads: usize,
+    pub throttle_ms: Option<u64>,
+}
+
+impl Default for MinerProfile {
+    fn default() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            node_addresses: vec!["127.0.0.1:16210".to_string()],
+            mining_address: String::new(),
+            threads: 1,
+            throttle_ms: None,
+        }
+    }
+}
+
+impl MinerProfile {
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::load_with(path, migrate)
+    }
+
+    /// `load` with the migration step passed in, so a failing one can be tested
+    fn load_with(path: &Path, migrate: impl FnOnce(Self, u32) -> Result<Self>) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let profile: Self =
+            toml::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))?;
+        let from_version = profile.schema_version;
+        if from_version > SCHEMA_VERSION {
+            return Err(anyhow::anyhow!(
+                "{} was written by a newer version (schema {}, supported {})",
+                path.display(),
+                from_version,
+                SCHEMA_VERSION
+            ));
+        }
+        if from_version == SCHEMA_VERSION {
+            return Ok(profile);
+        }
+
+        match migrate(profile, from_version) {
+            Ok(migrated) => {
+                migrated.save(path)?;
+                tracing::info!(
+                    "Migrated {} from schema {} to {}",
+                    path.display(),
+                    from_version,
+                    SCHEMA_VERSION
+                );
+                Ok(migrated)
+            }
+            Err(e) => {
+                // Keep the original around: the next save overwrites it
+                let backup = backup_path(path);
+                std::fs::copy(path, &backup)
+                    .with_context(|| format!("Failed to back up {}", path.display()))?;
+                Err(e.context(format!(
+                    "Failed to migrate {} (original saved as {})",
+                    path.display(),
+                    backup.display()
+                )))
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let text = toml::to_string_pretty(self).context("Failed to serialize profile")?;
+        std::fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Upgrade a profile read with schema `from_version` to `SCHEMA_VERSION`, one
+/// version at a time
+pub fn migrate(old: MinerProfile, from_version: u32) -> Result<MinerProfile> {
+    let mut profile = old;
+    for version in from_version..SCHEMA_VERSION {
+        profile = match version {
+            0 => migrate_v0_to_v1(profile),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "No migration from config schema {}",
+                    version
+                ))
+            }
+        };
+    }
+    Ok(profile)
+}
+
+/// Schema 0 is any file written before `schema_version` existed. It has the same
+/// fields as schema 1, so only the version changes.
+fn migrate_v0_to_v1(mut profile: MinerProfile) -> MinerProfile {
+    profile.schema_version = 1;
+    profile
+}
+
+/// `config.toml` -> `config.toml.bak`
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A file written before `schema_version` existed
+    const V0_CONFIG: &str = r#"
+node_addresses = ["10.0.0.2:16210"]
+mining_address = "kaspatest:qz0000000000000000000000000000000000000000000000000000000000"
+threads = 4
+throttle_ms = 10
+"#;
+
+    fn temp_config(name: &str, contents: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("rkstratum-config-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CONFIG_FILE_NAME);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn missing_schema_version_is_v0() {
+        let profile: MinerProfile = toml::from_str(V0_CONFIG).unwrap();
+        assert_eq!(profile.schema_version, 0);
+        assert_eq!(profile.threads, 4);
+    }
+
+    #[test]
+    fn migrate_v0_to_v1_keeps_settings() {
+        let old: MinerProfile = toml::from_str(V0_CONFIG).unwrap();
+        let migrated = migrate(old.clone(), 0).unwrap();
+        assert_eq!(migrated.schema_version, 1);
+        assert_eq!(
+            migrated,
+            MinerProfile {
+                schema_version: 1,
+                ..old
+            }
+        );
+    }
+
+    #[test]
+    fn migrate_from_current_version_is_a_no_op() {
+        let profile = MinerProfile::default();
+        assert_eq!(migrate(profile.clone(), SCHEMA_VERSION).unwrap(), profile);
+    }
+
+    #[test]
+    fn load_rewrites_migrated_file() {
+        let path = temp_config("migrate", V0_CONFIG);
+        let profile = MinerProfile::load(&path).unwrap();
+        assert_eq!(profile.schema_version, SCHEMA_VERSION);
+        assert_eq!(profile.node_addresses, vec!["10.0.0.2:16210".to_string()]);
+
+        let saved: MinerProfile = toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved, profile);
+        assert!(!backup_path(&path).exists());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn failed_migration_backs_up_original() {
+        let path = temp_config("backup", V0_CONFIG);
+        let result = MinerProfile::load_with(&path, |_, from| {
+            Err(anyhow::anyhow!("No migration from config schema {}", from))
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&path)).unwrap(),
+            V0_CONFIG
+        );
+        // The original is left alone, not half-rewritten
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), V0_CONFIG);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn load_rejects_newer_schema() {
+        let path = temp_config(
+            "newer",
+            &format!("schema_version = {}\nthreads = 2\n", SCHEMA_VERSION + 1),
+        );
+        assert!(MinerProfile::load(&path).is_err());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn backup_path_appends_bak() {
+        assert_eq!(
+            backup_path(Path::new("/data/config.toml")),
+            PathBuf::from("/data/config.toml.bak")
+        );
+    }
+}
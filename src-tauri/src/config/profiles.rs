@@ -0,0 +1,88 @@
+use super::MinerProfile;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// File name of the named profiles inside the app data directory
+pub const PROFILES_FILE_NAME: &str = "profiles.json";
+
+/// Miner settings saved under a name, e.g. one per wallet or node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub config: MinerProfile,
+}
+
+/// Every saved profile, in the order they were created
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Profiles {
+    pub profiles: Vec<Profile>,
+}
+
+impl Profiles {
+    /// No profiles if nothing was saved yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let text = serde_json::to_string_pretty(self).context("Failed to serialize profiles")?;
+        std::fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.profiles.iter().map(|p| p.name.clone()).collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Add a profile under a new name
+    pub fn create(&mut self, name: &str, config: MinerProfile) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("Profile name must not be empty"));
+        }
+        if self.get(name).is_some() {
+            return Err(anyhow::anyhow!(
+                "A profile named \"{}\" already exists",
+                name
+            ));
+        }
+        self.profiles.push(Profile {
+            name: name.to_string(),
+            config,
+        });
+        Ok(())
+    }
+
+    /// Replace the settings of an existing profile
+    pub fn update(&mut self, name: &str, config: MinerProfile) -> Result<()> {
+        let profile = self
+            .profiles
+            .iter_mut()
+            .find(|p| p.name == name)
+            .ok_or_else(|| anyhow::anyhow!("No profile named \"{}\"", name))?;
+        profile.config = config;
+        Ok(())
+    }
+
+    pub fn delete(&mut self, name: &str) -> Result<()> {
+        let before = self.profiles.len();
+        self.profiles.retain(|p| p.name != name);
+        if self.profiles.len() == before {
+            return Err(anyhow::anyhow!("No profile named \"{}\"", name));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use crate::api;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, Url};
+
+/// Custom URI scheme registered in `tauri.conf.json`
+pub const SCHEME: &str = "kaspa-miner";
+
+/// Link the app was launched with, held until the frontend is listening
+static STARTUP_LINK: Mutex<Option<String>> = Mutex::new(None);
+
+/// Settings carried by a `kaspa-miner://connect?node=..&address=..&threads=..` link
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DeepLink {
+    pub node: Option<String>,
+    pub address: Option<String>,
+    pub threads: Option<usize>,
+}
+
+pub fn parse(uri: &str) -> Result<DeepLink, String> {
+    let url = Url::parse(uri).map_err(|e| format!("Malformed link {}: {}", uri, e))?;
+    if url.scheme() != SCHEME {
+        return Err(format!("Unsupported link scheme: {}", url.scheme()));
+    }
+    if url.host_str() != Some("connect") {
+        return Err(format!(
+            "Unsupported link action: {}",
+            url.host_str().unwrap_or_default()
+        ));
+    }
+
+    let mut link = DeepLink::default();
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "node" => link.node = Some(value.into_owned()),
+            "address" => {
+                if !api::validate_address(&value).is_valid {
+                    return Err(format!("Invalid mining address in link: {}", value));
+                }
+                link.address = Some(value.into_owned());
+            }
+            "threads" => {
+                let threads = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|t| *t > 0)
+                    .ok_or_else(|| format!("Invalid thread count in link: {}", value))?;
+                link.threads = Some(threads);
+            }
+            other => tracing::warn!("Ignoring unknown deep link parameter: {}", other),
+        }
+    }
+    Ok(link)
+}
+
+/// Bring the window to front and send the parsed link (or the parse error) to the
+/// frontend as a `deep_link` / `deep_link_error` event
+pub fn handle(app: &AppHandle, uri: &str) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    match parse(uri) {
+        Ok(link) => {
+            tracing::info!("Opened deep link: {:?}", link);
+            let _ = app.emit("deep_link", &link);
+        }
+        Err(e) => {
+            tracing::warn!("{}", e);
+            let _ = app.emit("deep_link_error", e);
+        }
+    }
+}
+
+/// Remember a link received before the frontend was loaded
+pub fn set_startup_link(uri: String) {
+    *STARTUP_LINK.lock() = Some(uri);
+}
+
+/// Handle the launch link, if any. Called by the frontend once its listeners are set up.
+pub fn handle_startup_link(app: &AppHandle) {
+    if let Some(uri) = STARTUP_LINK.lock().take() {
+        handle(app, &uri);
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Round trips kept by `ConnectionLatencyTracker`
+pub const LATENCY_SAMPLES: usize = 60;
+
+/// p95 template fetch latency above which the UI warns about a slow node link
+pub const HIGH_LATENCY_THRESHOLD: Duration = Duration::from_millis(200);
+
+/// Round-trip times of the last `LATENCY_SAMPLES` gRPC calls to the node,
+/// failed ones included
+#[derive(Debug, Default, Clone)]
+pub struct ConnectionLatencyTracker {
+    samples: VecDeque<Duration>,
+}
+
+impl ConnectionLatencyTracker {
+    pub fn record(&mut self, latency: Duration) {
+        if self.samples.len() == LATENCY_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+    }
+
+    /// Zero until the first call was recorded
+    pub fn avg_latency(&self) -> Duration {
+        if self.samples.is_empty() {
+            return Duration::ZERO;
+        }
+        self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+    }
+
+    /// Latency 95% of the recorded calls stayed within; zero until the first
+    /// call was recorded
+    pub fn p95_latency(&self) -> Duration {
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (sorted.len() * 95).div_ceil(100);
+        rank.checked_sub(1)
+            .and_then(|i| sorted.get(i).copied())
+            .unwrap_or_default()
+    }
+}
+
+/// Submit queue depth above which the miner warns that blocks are found faster
+/// than the node accepts them
+pub const SUBMIT_QUEUE_WARN_DEPTH: usize = 10;
+
+/// Where the time between finding a block and the node's answer goes, over the
+/// last `LATENCY_SAMPLES` submits
+#[derive(Debug, Default, Clone)]
+pub struct SubmitLatencyTracker {
+    /// From the mining thread queueing the block to the submit task picking it up
+    pub queue_wait: ConnectionLatencyTracker,
+    /// SubmitBlock round trip, retries included
+    pub rpc: ConnectionLatencyTracker,
+}
+
+impl SubmitLatencyTracker {
+    pub fn record(&mut self, queue_wait_time: Duration, rpc_time: Duration) {
+        self.queue_wait.record(queue_wait_time);
+        self.rpc.record(rpc_time);
+    }
+}
@@ -0,0 +1,211 @@
+use chrono::{DateTime, Local, Utc};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+/// One captured log event
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub level: Level,
+    /// The event message followed by its other fields as `key=value`
+    pub message: String,
+}
+
+/// Recent log entries, kept so they can be saved to a file. Oldest entries are
+/// dropped once `MAX_ENTRIES` is reached.
+#[derive(Default)]
+pub struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+}
+
+pub type SharedLogBuffer = Arc<Mutex<LogBuffer>>;
+
+impl LogBuffer {
+    pub const MAX_ENTRIES: usize = 2000;
+
+    pub fn push(&mut self, entry: LogEntry) {
+        while self.entries.len() >= Self::MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Every entry as `[TIMESTAMP] [LEVEL] message`, one per line
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for entry in &self.entries {
+            let _ = writeln!(
+                text,
+                "[{}] [{}] {}",
+                entry.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
+                entry.level,
+                entry.message
+            );
+        }
+        text
+    }
+}
+
+/// Tracing layer that appends every event to a `LogBuffer`
+pub struct LogBufferLayer {
+    buffer: SharedLogBuffer,
+}
+
+impl LogBufferLayer {
+    pub fn new(buffer: SharedLogBuffer) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        self.buffer.lock().push(LogEntry {
+            timestamp: Local::now(),
+            level: *event.metadata().level(),
+            message: visitor.finish(),
+        });
+    }
+}
+
+/// Collects the `message` field and appends the others as `key=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(mut self) -> String {
+        self.message.push_str(&self.fields);
+        self.message
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Daily rolling log file for `--log-file`. `rotate` moves the current file
+/// aside and starts a new one without waiting for the day to end.
+#[derive(Clone)]
+pub struct LogFile {
+    inner: Arc<Mutex<LogFileInner>>,
+}
+
+struct LogFileInner {
+    directory: PathBuf,
+    file_name_prefix: String,
+    appender: RollingFileAppender,
+}
+
+fn daily_appender(directory: &Path, file_name_prefix: &str) -> anyhow::Result<RollingFileAppender> {
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(file_name_prefix)
+        .build(directory)
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to open log file {}: {}",
+                directory.join(file_name_prefix).display(),
+                e
+            )
+        })
+}
+
+impl LogFile {
+    /// Log to `path`, with the date appended to the file name (`miner.log.2024-01-31`)
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let file_name_prefix = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or_else(|| anyhow::anyhow!("{} is not a file path", path.display()))?;
+        let directory = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let appender = daily_appender(&directory, &file_name_prefix)?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(LogFileInner {
+                directory,
+                file_name_prefix,
+                appender,
+            })),
+        })
+    }
+
+    /// Rename today's file with a time suffix (`miner.log.2024-01-31.083015.123`)
+    /// and start writing to a fresh one. Returns the renamed file. On failure the
+    /// current file stays in use.
+    pub fn rotate(&self) -> anyhow::Result<PathBuf> {
+        let inner = &mut *self.inner.lock();
+        let _ = inner.appender.flush();
+        // The appender names its files after the UTC date
+        let now = Utc::now();
+        let current = inner.directory.join(format!(
+            "{}.{}",
+            inner.file_name_prefix,
+            now.format("%Y-%m-%d")
+        ));
+        let rotated = current.with_file_name(format!(
+            "{}.{}",
+            current.file_name().unwrap_or_default().to_string_lossy(),
+            now.format("%H%M%S%.3f")
+        ));
+        if current.exists() {
+            std::fs::rename(&current, &rotated).map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to move {} to {}: {}",
+                    current.display(),
+                    rotated.display(),
+                    e
+                )
+            })?;
+        }
+        inner.appender = daily_appender(&inner.directory, &inner.file_name_prefix)?;
+        Ok(rotated)
+    }
+}
+
+impl std::io::Write for LogFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.lock().appender.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.lock().appender.flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogFile {
+    type Writer = LogFile;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
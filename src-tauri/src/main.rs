@@ -1,13 +1,31 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod api;
+mod config;
+mod deep_link;
+mod latency;
+mod logs;
 mod miner;
+mod session;
+mod stats;
+mod topology;
 
-use api::KaspaApi;
-use miner::{start_cpu_miner, CpuMinerConfig, CpuMinerMetrics};
+use api::{ApiConnectionConfig, KaspaApi, NetworkInfo, NodeInfo, SyncError, ValidatedAddress};
+use config::address_book::{self, AddressBook, AddressBookEntry, AddressType};
+use config::profiles::{self, Profiles};
+use config::MinerProfile;
+use logs::{LogBufferLayer, LogFile, SharedLogBuffer};
+use miner::{
+    start_cpu_miner, BenchmarkResult, CpuMinerConfig, CpuMinerConfigBuilder, CpuMinerMetrics,
+    MinerEvents, ThreadDetail,
+};
+use session::{SessionLog, SessionRecord};
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::time::Duration;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_dialog::DialogExt;
 use tokio::sync::Mutex;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
@@ -19,14 +37,98 @@ use tracing_subscriber::{
 
 struct MinerState {
     api: Arc<Mutex<Option<Arc<KaspaApi>>>>,
-    metrics: Arc<Mutex<Option<Arc<CpuMinerMetrics>>>>,
-    shutdown: Arc<Mutex<Option<tokio::sync::watch::Sender<bool>>>>,
+    /// Running session
+    miner: Arc<Mutex<Option<miner::MinerHandle>>>,
+    /// Metrics of the last stopped session, kept so its stats can still be exported
+    /// and its accepted blocks opened in the explorer
+    finished_metrics: Arc<Mutex<Option<Arc<CpuMinerMetrics>>>>,
+    // Destination for `--metrics-fd` JSON lines, and the task currently streaming to it
+    metrics_fd: Option<std::fs::File>,
+    metrics_stream: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    sessions: SessionLog,
+    /// Addresses passed to the last successful `connect_node`, saved with the profile
+    node_addresses: Arc<Mutex<Vec<String>>>,
+    /// Blocks per second measured by `connect_node`, for the default poll interval
+    node_bps: Arc<Mutex<Option<f64>>>,
+    /// Recent log entries for `save_logs`
+    logs: SharedLogBuffer,
+    /// `--log-file` destination, rotated by `rotate_logs`
+    log_file: Option<LogFile>,
+    /// Hashes tried by every session since the app started
+    lifetime_hashes: Arc<AtomicU64>,
+    /// Runtime the miner's template, submit and watchdog tasks run on (see `start_miner_runtime`)
+    miner_runtime: tokio::runtime::Handle,
 }
 
+/// Interval between JSON lines written to `--metrics-fd`
+const METRICS_FD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Block explorer used for "View in Explorer" links
+const KASPA_EXPLORER_URL: &str = "https://explorer.kaspa.org";
+
+/// How long `connect_node` waits for the node to sync unless told otherwise
+const DEFAULT_SYNC_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long `connect_node` watches the DAA score to estimate the network's BPS
+const BPS_SAMPLE_WINDOW: Duration = Duration::from_secs(2);
+
+/// How long `disconnect_and_stop` waits for mining threads to exit
+const THREAD_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Worker threads of the miner runtime unless `KASPA_MINER_ASYNC_THREADS` says otherwise
+const DEFAULT_MINER_ASYNC_THREADS: usize = 2;
+
 // Global app handle for log emission (set during setup)
 use std::sync::OnceLock;
 static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
 
+/// The miner's async tasks get their own runtime so template fetches and submits
+/// don't queue behind Tauri's IPC and window work. `KASPA_MINER_RUNTIME_FLAVOR` picks
+/// `multi_thread` (the default, with `KASPA_MINER_ASYNC_THREADS` workers) or
+/// `current_thread`. The runtime is driven by its own thread until the app exits.
+fn start_miner_runtime() -> tokio::runtime::Handle {
+    let runtime = miner_runtime_builder()
+        .thread_name("kaspa-miner-async")
+        .enable_all()
+        .build()
+        .expect("failed to build the miner runtime");
+    let handle = runtime.handle().clone();
+    std::thread::Builder::new()
+        .name("kaspa-miner-runtime".to_string())
+        .spawn(move || runtime.block_on(std::future::pending::<()>()))
+        .expect("failed to start the miner runtime thread");
+    handle
+}
+
+// Runs before tracing is set up, so problems go to stderr
+fn miner_runtime_builder() -> tokio::runtime::Builder {
+    let flavor = std::env::var("KASPA_MINER_RUNTIME_FLAVOR").unwrap_or_default();
+    match flavor.trim() {
+        "current_thread" => return tokio::runtime::Builder::new_current_thread(),
+        "" | "multi_thread" => {}
+        other => eprintln!(
+            "Ignoring KASPA_MINER_RUNTIME_FLAVOR={}: expected multi_thread or current_thread",
+            other
+        ),
+    }
+    let worker_threads = match std::env::var("KASPA_MINER_ASYNC_THREADS") {
+        Ok(value) => match value.trim().parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                eprintln!(
+                    "Ignoring KASPA_MINER_ASYNC_THREADS={}: expected a positive number",
+                    value
+                );
+                DEFAULT_MINER_ASYNC_THREADS
+            }
+        },
+        Err(_) => DEFAULT_MINER_ASYNC_THREADS,
+    };
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.worker_threads(worker_threads);
+    builder
+}
+
 // Custom time formatter for logs
 struct LocalTimer;
 
@@ -37,27 +139,118 @@ impl FormatTime for LocalTimer {
     }
 }
 
+/// Formatting for log lines shown in the frontend log panel. ANSI colors must be
+/// disabled explicitly: the fmt layer decides based on the process terminal, not on
+/// `writer`, so escape codes would otherwise leak into the panel as raw `\x1b[` sequences.
+fn frontend_log_layer<S, W>(writer: W) -> impl Layer<S>
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt::layer()
+        .with_timer(LocalTimer)
+        .with_ansi(false)
+        .with_writer(writer)
+}
+
+/// `address` may be a comma-separated list of fallback nodes, tried in order.
+/// Gives up if the node isn't synced after `sync_timeout_secs` (default 60).
 #[tauri::command]
-async fn connect_node(address: String, state: State<'_, MinerState>) -> Result<String, String> {
-    let api = KaspaApi::new(address.clone())
+async fn connect_node(
+    address: String,
+    sync_timeout_secs: Option<u64>,
+    state: State<'_, MinerState>,
+) -> Result<String, String> {
+    let addresses: Vec<String> = address
+        .split(',')
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect();
+    // The GUI keeps retrying until the user gives up
+    let api = KaspaApi::new_with_failover(addresses.clone(), &ApiConnectionConfig::default())
         .await
         .map_err(|e| format!("Failed to connect: {}", e))?;
 
-    api.wait_for_sync()
-        .await
-        .map_err(|e| format!("Failed to sync: {}", e))?;
+    let sync_timeout = sync_timeout_secs.map_or(DEFAULT_SYNC_TIMEOUT, Duration::from_secs);
+    if let Err(e) = api.wait_for_sync_with_timeout(sync_timeout).await {
+        api.disconnect().await;
+        return Err(match e {
+            SyncError::Timeout(timeout) => format!(
+                "The node is still syncing after {}s. Wait for it to finish syncing, or check that it is configured for the right network.",
+                timeout.as_secs()
+            ),
+            SyncError::RpcError(e) => format!("Failed to sync: {:#}", e),
+        });
+    }
+
+    // Picks the template poll interval when the user didn't set one
+    let bps = match api.measure_bps(BPS_SAMPLE_WINDOW).await {
+        Ok(bps) => {
+            tracing::info!("Network produces {:.1} blocks per second", bps);
+            Some(bps)
+        }
+        Err(e) => {
+            tracing::warn!("Failed to measure network BPS: {:#}", e);
+            None
+        }
+    };
 
+    let message = format!("Connected to {} and synced", api.active_address());
     *state.api.lock().await = Some(api);
-    Ok("Connected and synced".to_string())
+    *state.node_bps.lock().await = bps;
+    *state.node_addresses.lock().await = addresses;
+    Ok(message)
+}
+
+/// Time allowed for the connection test's connect attempt and health check each
+const TEST_CONNECTION_STEP_TIMEOUT: Duration = Duration::from_secs(2);
+/// Overall limit; the test always reports back within this
+const TEST_CONNECTION_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Check that a node is reachable over a temporary connection, without touching
+/// the miner's connection
+#[tauri::command]
+async fn test_connection(address: String) -> Result<String, String> {
+    let address = address.trim().to_string();
+    let test = async {
+        let api = KaspaApi::new_with_timeout(address, TEST_CONNECTION_STEP_TIMEOUT).await?;
+        let latency = tokio::time::timeout(TEST_CONNECTION_STEP_TIMEOUT, api.health_check()).await;
+        api.disconnect().await;
+        latency.map_err(anyhow::Error::from)?
+    };
+    let result = tokio::time::timeout(TEST_CONNECTION_TIMEOUT, test)
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|r| r);
+    match result {
+        Ok(latency) => Ok(format!("✓ Node reachable ({}ms)", latency.as_millis())),
+        Err(e) if e.is::<tokio::time::error::Elapsed>() => {
+            Err("✗ Timeout (node unreachable or wrong port)".to_string())
+        }
+        Err(e) if e.to_string().to_lowercase().contains("refused") => {
+            Err("✗ Connection refused".to_string())
+        }
+        Err(e) => Err(format!("✗ {}", e)),
+    }
 }
 
 #[tauri::command]
 async fn start_mining(
     mining_address: String,
+    // Paid in turn after `mining_address`, one accepted block each
+    extra_mining_addresses: Option<Vec<String>>,
     threads: usize,
     throttle_ms: Option<u64>,
+    target_hashrate: Option<f64>,
+    stress_test_secs: Option<u64>,
+    extra_data: Option<String>,
+    poll_interval_ms: Option<u64>,
+    // Logical CPU per thread, from `detect_cpus`; `None` lets the OS schedule them
+    cpu_affinity: Option<Vec<usize>>,
+    app: AppHandle,
     state: State<'_, MinerState>,
 ) -> Result<String, String> {
+    let extra_data = parse_extra_data(extra_data)?;
     let api = {
         let api_guard = state.api.lock().await;
         api_guard
@@ -66,38 +259,288 @@ async fn start_mining(
             .clone()
     };
 
-    let config = CpuMinerConfig {
+    let node = api.active_address().to_string();
+    let mut builder = miner_config(threads, throttle_ms, target_hashrate)
+        .mining_addresses(
+            std::iter::once(mining_address.clone())
+                .chain(extra_mining_addresses.unwrap_or_default())
+                .collect(),
+        )
+        .hashes_tried_counter(Arc::clone(&state.lifetime_hashes));
+    if let Some(extra_data) = extra_data {
+        builder = builder.extra_data(extra_data);
+    }
+    if let Some(cpus) = cpu_affinity {
+        builder = builder.cpu_affinity(cpus);
+    }
+    builder = builder.poll_interval(match poll_interval_ms {
+        Some(ms) => Duration::from_millis(ms),
+        None => miner::default_poll_interval(*state.node_bps.lock().await),
+    });
+    // The frontend only passes this after the user confirmed the risks
+    if let Some(secs) = stress_test_secs {
+        builder = builder.thermal_stress_test(Duration::from_secs(secs));
+    }
+    // `start_cpu_miner` checks every address against the node's network
+    let config = builder.build().map_err(|e| format!("{:#}", e))?;
+
+    let (
+        handle,
+        MinerEvents {
+            mut errors,
+            mut blocks_found,
+            mut connection_restored,
+        },
+    ) = start_cpu_miner(api, config, state.miner_runtime.clone())
+        .await
+        .map_err(|e| format!("Failed to start miner: {}", e))?;
+    let metrics = Arc::clone(handle.metrics());
+
+    // Forward miner errors and found blocks to the frontend; ends when the miner tasks exit
+    let error_app = app.clone();
+    tokio::spawn(async move {
+        while let Some(error) = errors.recv().await {
+            let _ = error_app.emit(error.event_name(), error.payload());
+        }
+    });
+    let block_app = app.clone();
+    tokio::spawn(async move {
+        while let Some(event) = blocks_found.recv().await {
+            let _ = block_app.emit("block_found", &event);
+        }
+    });
+    let restored_app = app.clone();
+    tokio::spawn(async move {
+        while let Some(address) = connection_restored.recv().await {
+            let _ = restored_app.emit("connection_restored", address);
+        }
+    });
+
+    if let Some(fd) = state.metrics_fd.as_ref() {
+        match fd.try_clone() {
+            Ok(fd) => {
+                let handle = metrics.stream_to_fd(fd, METRICS_FD_INTERVAL);
+                if let Some(previous) = state.metrics_stream.lock().await.replace(handle) {
+                    previous.abort();
+                }
+            }
+            Err(e) => tracing::warn!("Failed to clone metrics fd: {}", e),
+        }
+    }
+
+    *state.miner.lock().await = Some(handle);
+    state
+        .sessions
+        .start(node, mining_address.clone(), threads.max(1), throttle_ms);
+
+    // Remember the settings that worked for the next launch
+    let profile = MinerProfile {
+        node_addresses: state.node_addresses.lock().await.clone(),
         mining_address,
         threads: threads.max(1),
-        throttle: throttle_ms.map(Duration::from_millis),
-        // Optimization: Use 50ms poll interval for high BPS networks like TN12 (10 BPS)
-        // This ensures we get new work quickly when blocks are found
-        template_poll_interval: Duration::from_millis(50),
+        throttle_ms,
+        ..Default::default()
     };
+    match config_path(&app) {
+        Ok(path) => {
+            if let Err(e) = profile.save(&path) {
+                tracing::warn!("Failed to save config: {:#}", e);
+            }
+        }
+        Err(e) => tracing::warn!("{}", e),
+    }
+
+    Ok("Mining started".to_string())
+}
 
-    let (metrics, shutdown) = start_cpu_miner(api, config)
+/// Config builder with the user's thread and throttle choices
+fn miner_config(
+    threads: usize,
+    throttle_ms: Option<u64>,
+    target_hashrate: Option<f64>,
+) -> CpuMinerConfigBuilder {
+    let mut builder = CpuMinerConfig::builder().threads(threads);
+    if let Some(ms) = throttle_ms {
+        builder = builder.throttle(Duration::from_millis(ms));
+    }
+    if let Some(hps) = target_hashrate {
+        builder = builder.target_hashrate(hps);
+    }
+    builder
+}
+
+/// Coinbase extra data typed in the UI; blank means the miner's default
+fn parse_extra_data(text: Option<String>) -> Result<Option<Vec<u8>>, String> {
+    let Some(text) = text.filter(|t| !t.trim().is_empty()) else {
+        return Ok(None);
+    };
+    if text.len() > miner::MAX_USER_EXTRA_DATA_LEN {
+        return Err(format!(
+            "Extra data is {} bytes; at most {} are allowed",
+            text.len(),
+            miner::MAX_USER_EXTRA_DATA_LEN
+        ));
+    }
+    Ok(Some(text.into_bytes()))
+}
+
+/// Measure local hashrate on a fake template; needs no node connection
+#[tauri::command]
+async fn benchmark(
+    threads: usize,
+    throttle_ms: Option<u64>,
+    target_hashrate: Option<f64>,
+    duration_secs: u64,
+) -> Result<BenchmarkResult, String> {
+    let config = miner_config(threads, throttle_ms, target_hashrate)
+        .build_benchmark()
+        .map_err(|e| format!("{:#}", e))?;
+    let duration = Duration::from_secs(duration_secs.max(1));
+    tokio::task::spawn_blocking(move || miner::benchmark_cpu_miner(config, duration))
         .await
-        .map_err(|e| format!("Failed to start miner: {}", e))?;
+        .map_err(|e| format!("Benchmark failed: {}", e))
+}
 
-    *state.metrics.lock().await = Some(metrics);
-    *state.shutdown.lock().await = Some(shutdown);
+/// Logical CPUs, so the thread slider can show which CPUs the threads map to
+#[tauri::command]
+fn detect_cpus() -> Vec<topology::CpuId> {
+    topology::detect_cpus()
+}
 
-    Ok("Mining started".to_string())
+fn config_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(config::CONFIG_FILE_NAME))
+        .map_err(|e| format!("Failed to locate app data directory: {}", e))
+}
+
+/// Settings saved by the last successful `start_mining`, if any
+#[tauri::command]
+fn load_config(app: AppHandle) -> Result<Option<MinerProfile>, String> {
+    let path = config_path(&app)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    MinerProfile::load(&path)
+        .map(Some)
+        .map_err(|e| format!("Failed to load config: {:#}", e))
+}
+
+fn load_profiles(app: &AppHandle) -> Result<(std::path::PathBuf, Profiles), String> {
+    let path = app
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join(profiles::PROFILES_FILE_NAME))
+        .map_err(|e| format!("Failed to locate app data directory: {}", e))?;
+    let profiles =
+        Profiles::load(&path).map_err(|e| format!("Failed to load profiles: {:#}", e))?;
+    Ok((path, profiles))
+}
+
+fn save_profiles(path: &std::path::Path, profiles: &Profiles) -> Result<(), String> {
+    profiles
+        .save(path)
+        .map_err(|e| format!("Failed to save profiles: {:#}", e))
+}
+
+/// New profile holding the settings of the last `start_mining`, or the
+/// defaults if there are none
+#[tauri::command]
+fn create_profile(app: AppHandle, name: String) -> Result<(), String> {
+    let config = load_config(app.clone())?.unwrap_or_default();
+    let (path, mut profiles) = load_profiles(&app)?;
+    profiles.create(&name, config).map_err(|e| e.to_string())?;
+    save_profiles(&path, &profiles)
+}
+
+#[tauri::command]
+fn delete_profile(app: AppHandle, name: String) -> Result<(), String> {
+    let (path, mut profiles) = load_profiles(&app)?;
+    profiles.delete(&name).map_err(|e| e.to_string())?;
+    save_profiles(&path, &profiles)
+}
+
+/// Profile names, in the order they were created
+#[tauri::command]
+fn list_profiles(app: AppHandle) -> Result<Vec<String>, String> {
+    load_profiles(&app).map(|(_, profiles)| profiles.names())
+}
+
+#[tauri::command]
+fn load_profile(app: AppHandle, name: String) -> Result<MinerProfile, String> {
+    let (_, profiles) = load_profiles(&app)?;
+    profiles
+        .get(&name)
+        .map(|profile| profile.config.clone())
+        .ok_or_else(|| format!("No profile named \"{}\"", name))
+}
+
+#[tauri::command]
+fn update_profile(app: AppHandle, name: String, config: MinerProfile) -> Result<(), String> {
+    let (path, mut profiles) = load_profiles(&app)?;
+    profiles.update(&name, config).map_err(|e| e.to_string())?;
+    save_profiles(&path, &profiles)
+}
+
+fn address_book_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(address_book::ADDRESS_BOOK_FILE_NAME))
+        .map_err(|e| format!("Failed to locate app data directory: {}", e))
+}
+
+fn load_address_book(app: &AppHandle) -> Result<(std::path::PathBuf, AddressBook), String> {
+    let path = address_book_path(app)?;
+    let book =
+        AddressBook::load(&path).map_err(|e| format!("Failed to load address book: {:#}", e))?;
+    Ok((path, book))
+}
+
+/// Save an address under `label`, replacing one with the same label and type.
+/// Returns the updated entries.
+#[tauri::command]
+fn add_address_book_entry(
+    app: AppHandle,
+    label: String,
+    address: String,
+    address_type: AddressType,
+) -> Result<Vec<AddressBookEntry>, String> {
+    let (path, mut book) = load_address_book(&app)?;
+    book.add(AddressBookEntry {
+        label,
+        address,
+        address_type,
+    })
+    .map_err(|e| e.to_string())?;
+    book.save(&path)
+        .map_err(|e| format!("Failed to save address book: {:#}", e))?;
+    Ok(book.entries)
+}
+
+#[tauri::command]
+fn remove_address_book_entry(
+    app: AppHandle,
+    label: String,
+    address_type: AddressType,
+) -> Result<Vec<AddressBookEntry>, String> {
+    let (path, mut book) = load_address_book(&app)?;
+    if !book.remove(&label, address_type) {
+        return Err(format!("No saved address named \"{}\"", label));
+    }
+    book.save(&path)
+        .map_err(|e| format!("Failed to save address book: {:#}", e))?;
+    Ok(book.entries)
+}
+
+#[tauri::command]
+fn list_address_book_entries(app: AppHandle) -> Result<Vec<AddressBookEntry>, String> {
+    load_address_book(&app).map(|(_, book)| book.entries)
 }
 
 #[tauri::command]
 async fn disconnect_node(state: State<'_, MinerState>) -> Result<String, String> {
     // Stop mining first if running
-    {
-        let shutdown = {
-            let mut shutdown_guard = state.shutdown.lock().await;
-            shutdown_guard.take()
-        };
-        if let Some(shutdown) = shutdown {
-            let _ = shutdown.send(true);
-            *state.metrics.lock().await = None;
-        }
-    }
+    stop_session(&state).await;
 
     // Clear API connection
     *state.api.lock().await = None;
@@ -106,36 +549,384 @@ async fn disconnect_node(state: State<'_, MinerState>) -> Result<String, String>
 
 #[tauri::command]
 async fn stop_mining(state: State<'_, MinerState>) -> Result<String, String> {
-    let shutdown = {
-        let mut shutdown_guard = state.shutdown.lock().await;
-        shutdown_guard.take()
+    match stop_session(&state).await {
+        Some(true) => Ok("Mining stopped".to_string()),
+        Some(false) => Ok("Mining stopped (threads still shutting down)".to_string()),
+        None => Err("Miner not running".to_string()),
+    }
+}
+
+/// Park the mining threads and template poller without ending the session
+#[tauri::command]
+async fn pause_mining(state: State<'_, MinerState>) -> Result<String, String> {
+    set_paused(&state, true).await?;
+    Ok("Mining paused".to_string())
+}
+
+#[tauri::command]
+async fn resume_mining(state: State<'_, MinerState>) -> Result<String, String> {
+    set_paused(&state, false).await?;
+    Ok("Mining resumed".to_string())
+}
+
+/// Add or remove mining threads without restarting the session
+#[tauri::command]
+async fn set_thread_count(threads: usize, state: State<'_, MinerState>) -> Result<String, String> {
+    let miner = state.miner.lock().await;
+    let miner = miner
+        .as_ref()
+        .ok_or_else(|| "Miner not running".to_string())?;
+    miner::set_thread_count(miner, threads).map_err(|e| format!("{:#}", e))?;
+    Ok(format!("Mining with {} thread(s)", threads))
+}
+
+async fn set_paused(state: &MinerState, paused: bool) -> Result<(), String> {
+    let miner = state.miner.lock().await;
+    let miner = miner
+        .as_ref()
+        .ok_or_else(|| "Miner not running".to_string())?;
+    miner.set_paused(paused).map_err(|e| format!("{:#}", e))
+}
+
+/// Stop the running session and record it. `None` if nothing was mining, otherwise
+/// whether the miner shut down within `THREAD_SHUTDOWN_TIMEOUT`.
+async fn stop_session(state: &MinerState) -> Option<bool> {
+    if let Some(stream) = state.metrics_stream.lock().await.take() {
+        stream.abort();
+    }
+    let handle = state.miner.lock().await.take()?;
+    let metrics = Arc::clone(handle.metrics());
+    let stopped = tokio::time::timeout(THREAD_SHUTDOWN_TIMEOUT, handle.stop())
+        .await
+        .is_ok();
+    finish_session(state, metrics).await;
+    Some(stopped)
+}
+
+/// Stop mining, wait for the mining threads to exit, then drop the node connection.
+/// Returns false if the threads did not exit within `THREAD_SHUTDOWN_TIMEOUT`.
+async fn shutdown_miner_and_disconnect(state: &MinerState) -> bool {
+    // Hold the connection so no command can start mining on it mid-teardown
+    let mut api_guard = state.api.lock().await;
+    let threads_exited = stop_session(state).await.unwrap_or(true);
+    *api_guard = None;
+    threads_exited
+}
+
+/// Record a stopped miner's session and keep its metrics for `export_stats`
+async fn finish_session(state: &MinerState, metrics: Arc<CpuMinerMetrics>) {
+    state.sessions.finish(&metrics);
+    *state.finished_metrics.lock().await = Some(metrics);
+}
+
+/// Ask where to save and write the current (or last stopped) session's stats as CSV.
+/// Returns the chosen path, or `None` if the dialog was cancelled.
+#[tauri::command]
+async fn export_stats(
+    app: AppHandle,
+    state: State<'_, MinerState>,
+) -> Result<Option<String>, String> {
+    let running = state
+        .miner
+        .lock()
+        .await
+        .as_ref()
+        .map(|miner| Arc::clone(miner.metrics()));
+    let metrics = match running {
+        Some(metrics) => metrics,
+        None => state
+            .finished_metrics
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| "No mining session to export".to_string())?,
     };
 
-    if let Some(shutdown) = shutdown {
-        let _ = shutdown.send(true);
-        *state.metrics.lock().await = None;
-        Ok("Mining stopped".to_string())
+    let default_name = format!(
+        "kaspa-miner-stats-{}.csv",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let Some(path) = app
+        .dialog()
+        .file()
+        .add_filter("CSV", &["csv"])
+        .set_file_name(default_name)
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    let path = path.into_path().map_err(|e| e.to_string())?;
+
+    let session = metrics.session.lock().clone();
+    stats::export_session_csv(&path, &metrics, &session).map_err(|e| format!("{:#}", e))?;
+    Ok(Some(path.display().to_string()))
+}
+
+/// Save the buffered log entries to a file the user picks. Emits `log_saved`
+/// with the absolute path once written.
+#[tauri::command]
+async fn save_logs(app: AppHandle, state: State<'_, MinerState>) -> Result<Option<String>, String> {
+    use tokio::io::AsyncWriteExt;
+
+    let default_name = format!(
+        "kaspa-miner-{}.log",
+        chrono::Local::now().format("%Y-%m-%d")
+    );
+    let Some(path) = app
+        .dialog()
+        .file()
+        .add_filter("Log", &["log"])
+        .set_file_name(default_name)
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    let path = path.into_path().map_err(|e| e.to_string())?;
+
+    // Format under the lock so entries appended meanwhile can't interleave
+    // with the snapshot; the file itself is written after releasing it
+    let text = state.logs.lock().to_text();
+    let mut file = tokio::fs::File::create(&path)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    file.write_all(text.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    let path = tokio::fs::canonicalize(&path).await.unwrap_or(path);
+    let path = path.display().to_string();
+    let _ = app.emit("log_saved", &path);
+    Ok(Some(path))
+}
+
+#[tauri::command]
+async fn disconnect_and_stop(state: State<'_, MinerState>) -> Result<String, String> {
+    if shutdown_miner_and_disconnect(&state).await {
+        Ok("Mining stopped and disconnected".to_string())
     } else {
-        Err("Miner not running".to_string())
+        tracing::warn!(
+            "Mining threads did not exit within {:?}",
+            THREAD_SHUTDOWN_TIMEOUT
+        );
+        Ok("Disconnected (mining threads still shutting down)".to_string())
     }
 }
 
 #[tauri::command]
 async fn get_metrics(state: State<'_, MinerState>) -> Result<serde_json::Value, String> {
-    let metrics_guard = state.metrics.lock().await;
-    if let Some(metrics) = metrics_guard.as_ref() {
-        Ok(serde_json::json!({
-            "hashes_tried": metrics.hashes_tried.load(std::sync::atomic::Ordering::Relaxed),
-            "blocks_submitted": metrics.blocks_submitted.load(std::sync::atomic::Ordering::Relaxed),
-            "blocks_accepted": metrics.blocks_accepted.load(std::sync::atomic::Ordering::Relaxed),
-        }))
+    let miner = state.miner.lock().await;
+    if let Some(miner) = miner.as_ref() {
+        let mut metrics = miner.metrics().to_json();
+        metrics["current_mining_address"] = miner.current_mining_address().into();
+        Ok(metrics)
     } else {
         Err("Miner not running".to_string())
     }
 }
 
+#[tauri::command]
+async fn get_peer_count(state: State<'_, MinerState>) -> Result<u64, String> {
+    let api = state
+        .api
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| "Not connected to node".to_string())?;
+    api.get_peers()
+        .await
+        .map(|peers| peers.len() as u64)
+        .map_err(|e| format!("Failed to get peers: {}", e))
+}
+
+#[tauri::command]
+async fn get_node_info(state: State<'_, MinerState>) -> Result<NodeInfo, String> {
+    let api = state
+        .api
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| "Not connected to node".to_string())?;
+    api.get_node_info().await.map_err(|e| format!("{:#}", e))
+}
+
+#[tauri::command]
+async fn get_network_info(state: State<'_, MinerState>) -> Result<NetworkInfo, String> {
+    let api = state
+        .api
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| "Not connected to node".to_string())?;
+    api.get_network_info().await.map_err(|e| format!("{:#}", e))
+}
+
+#[tauri::command]
+async fn test_submit_dummy_block(state: State<'_, MinerState>) -> Result<String, String> {
+    let api = state
+        .api
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| "Not connected to node".to_string())?;
+    Ok(api
+        .test_submit_dummy_block()
+        .await
+        .unwrap_or_else(|e| format!("Network error: {}", e)))
+}
+
+#[tauri::command]
+async fn get_thread_details(state: State<'_, MinerState>) -> Result<Vec<ThreadDetail>, String> {
+    let miner = state.miner.lock().await;
+    miner
+        .as_ref()
+        .map(|miner| miner.metrics().thread_details())
+        .ok_or_else(|| "Miner not running".to_string())
+}
+
+#[tauri::command]
+fn list_sessions(state: State<'_, MinerState>) -> Vec<SessionRecord> {
+    state.sessions.list()
+}
+
+#[tauri::command]
+fn clear_sessions(state: State<'_, MinerState>) {
+    state.sessions.clear();
+}
+
+#[tauri::command]
+fn validate_address(address: String) -> Result<ValidatedAddress, String> {
+    Ok(api::validate_address(&address))
+}
+
+/// Value of `--name VALUE` or `--name=VALUE` on the command line
+fn arg_value(name: &str) -> Option<String> {
+    let prefix = format!("{}=", name);
+    let mut args = std::env::args().skip(1);
+    loop {
+        let arg = args.next()?;
+        if arg == name {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix(&prefix) {
+            return Some(value.to_string());
+        }
+    }
+}
+
+/// Parse `--metrics-fd N` from the command line and take ownership of that descriptor
+fn metrics_fd_from_args() -> Option<std::fs::File> {
+    let fd = arg_value("--metrics-fd")?;
+
+    let Ok(fd) = fd.parse::<i32>() else {
+        eprintln!("Invalid --metrics-fd value: {}", fd);
+        return None;
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::FromRawFd;
+        // SAFETY: the descriptor is handed to us by the parent process (e.g. `3>/metrics.pipe`)
+        // and is not used anywhere else in this process
+        Some(unsafe { std::fs::File::from_raw_fd(fd) })
+    }
+    #[cfg(not(unix))]
+    {
+        eprintln!("--metrics-fd {} is only supported on Unix platforms", fd);
+        None
+    }
+}
+
+/// Open the daily log file from `--log-file` or `KASPA_MINER_LOG_FILE`, if either is set
+fn log_file_from_args() -> Option<LogFile> {
+    let path = arg_value("--log-file")
+        .or_else(|| std::env::var("KASPA_MINER_LOG_FILE").ok())
+        .filter(|path| !path.trim().is_empty())?;
+    match LogFile::open(std::path::Path::new(path.trim())) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            eprintln!("{:#}", e);
+            None
+        }
+    }
+}
+
+/// Move the current `--log-file` file aside and start a new one
+#[tauri::command]
+fn rotate_logs(state: State<'_, MinerState>) -> Result<String, String> {
+    let log_file = state
+        .log_file
+        .as_ref()
+        .ok_or_else(|| "No log file configured (start with --log-file)".to_string())?;
+    let rotated = log_file.rotate().map_err(|e| format!("{:#}", e))?;
+    tracing::info!("Log file rotated, previous file: {}", rotated.display());
+    Ok(format!("Log file rotated to {}", rotated.display()))
+}
+
+#[tauri::command]
+async fn open_block_in_explorer(nonce: u64, state: State<'_, MinerState>) -> Result<(), String> {
+    let running = state
+        .miner
+        .lock()
+        .await
+        .as_ref()
+        .and_then(|miner| miner.metrics().accepted_block_hash(nonce));
+    // Block History keeps showing a stopped session's blocks, so look there too
+    let hash = match running {
+        Some(hash) => hash,
+        None => state
+            .finished_metrics
+            .lock()
+            .await
+            .as_ref()
+            .and_then(|metrics| metrics.accepted_block_hash(nonce))
+            .ok_or_else(|| format!("No accepted block with nonce {}", nonce))?,
+    };
+    tauri_plugin_opener::open_url(
+        format!("{}/blocks/{}", KASPA_EXPLORER_URL, hash),
+        None::<&str>,
+    )
+    .map_err(|e| format!("Failed to open explorer: {}", e))
+}
+
+#[tauri::command]
+fn open_address_in_explorer(address: String) -> Result<(), String> {
+    let address = address.trim();
+    if !api::validate_address(address).is_valid {
+        return Err(format!("Invalid address: {}", address));
+    }
+    tauri_plugin_opener::open_url(
+        format!("{}/addresses/{}", KASPA_EXPLORER_URL, address),
+        None::<&str>,
+    )
+    .map_err(|e| format!("Failed to open explorer: {}", e))
+}
+
+#[tauri::command]
+fn take_startup_deep_link(app: AppHandle) {
+    deep_link::handle_startup_link(&app);
+}
+
 fn main() {
+    let metrics_fd = metrics_fd_from_args();
+    let log_buffer = SharedLogBuffer::default();
+    let log_buffer_layer = LogBufferLayer::new(Arc::clone(&log_buffer));
+    let log_file = log_file_from_args();
+    let log_file_writer = log_file.clone();
+    let miner_runtime = start_miner_runtime();
+
     tauri::Builder::default()
+        // Must be registered first. A second launch (e.g. from a clicked link) exits
+        // and forwards its URL to this instance through the deep-link plugin.
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
@@ -145,15 +936,41 @@ fn main() {
             let app_handle = app.handle().clone();
             APP_HANDLE.set(app_handle.clone()).ok();
 
+            // Installed bundles register the scheme; dev builds must do it at runtime
+            #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+            app.deep_link().register_all()?;
+            if let Some(url) = app
+                .deep_link()
+                .get_current()?
+                .and_then(|urls| urls.into_iter().next())
+            {
+                deep_link::set_startup_link(url.to_string());
+            }
+            let deep_link_handle = app_handle.clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    deep_link::handle(&deep_link_handle, url.as_str());
+                }
+            });
+
             // Initialize tracing with custom layer that emits Tauri events
             let filter =
                 EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
 
             // Create a custom layer that emits to Tauri events
-            let tauri_layer = tracing_subscriber::fmt::layer()
-                .with_timer(LocalTimer)
-                .with_writer(TauriLogWriter)
-                .with_filter(filter.clone());
+            let tauri_layer = frontend_log_layer(TauriLogWriter).with_filter(filter.clone());
+
+            // Kept in memory for Save Logs
+            let buffer_layer = log_buffer_layer.with_filter(filter.clone());
+
+            // Full timestamps and targets for server deployments with --log-file
+            let file_layer = log_file_writer.map(|writer| {
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_target(true)
+                    .with_writer(writer)
+                    .with_filter(filter.clone())
+            });
 
             // Also log to stdout for debugging
             let stdout_layer = tracing_subscriber::fmt::layer()
@@ -162,22 +979,77 @@ fn main() {
 
             tracing_subscriber::registry()
                 .with(tauri_layer)
+                .with(buffer_layer)
+                .with(file_layer)
                 .with(stdout_layer)
                 .init();
 
             Ok(())
         })
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                // Clean up the miner before the window goes away, then close it ourselves
+                api.prevent_close();
+                let window = window.clone();
+                tauri::async_runtime::spawn(async move {
+                    let state = window.state::<MinerState>();
+                    if !shutdown_miner_and_disconnect(&state).await {
+                        tracing::warn!("Mining threads did not exit before window close");
+                    }
+                    let _ = window.destroy();
+                });
+            }
+        })
         .manage(MinerState {
             api: Arc::new(Mutex::new(None)),
-            metrics: Arc::new(Mutex::new(None)),
-            shutdown: Arc::new(Mutex::new(None)),
+            miner: Arc::new(Mutex::new(None)),
+            finished_metrics: Arc::new(Mutex::new(None)),
+            metrics_fd,
+            metrics_stream: Arc::new(Mutex::new(None)),
+            sessions: SessionLog::default(),
+            node_addresses: Arc::new(Mutex::new(Vec::new())),
+            node_bps: Arc::new(Mutex::new(None)),
+            logs: log_buffer,
+            log_file,
+            lifetime_hashes: Arc::new(AtomicU64::new(0)),
+            miner_runtime,
         })
         .invoke_handler(tauri::generate_handler![
             connect_node,
+            test_connection,
             start_mining,
             stop_mining,
+            pause_mining,
+            resume_mining,
+            set_thread_count,
             get_metrics,
-            disconnect_node
+            get_thread_details,
+            get_peer_count,
+            get_node_info,
+            get_network_info,
+            test_submit_dummy_block,
+            disconnect_node,
+            disconnect_and_stop,
+            validate_address,
+            open_block_in_explorer,
+            open_address_in_explorer,
+            list_sessions,
+            clear_sessions,
+            take_startup_deep_link,
+            load_config,
+            create_profile,
+            delete_profile,
+            list_profiles,
+            load_profile,
+            update_profile,
+            add_address_book_entry,
+            remove_address_book_entry,
+            list_address_book_entries,
+            detect_cpus,
+            benchmark,
+            export_stats,
+            save_logs,
+            rotate_logs
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -211,3 +1083,47 @@ impl std::io::Write for TauriLogWriter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collects everything the fmt layer writes
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<parking_lot::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for CapturedLogs {
+        type Writer = CapturedLogs;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn frontend_log_layer_writes_no_ansi_escapes() {
+        let captured = CapturedLogs::default();
+        let subscriber = tracing_subscriber::registry().with(frontend_log_layer(captured.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(nonce = 42, "[Miner] Block rejected by node");
+        });
+
+        let output = String::from_utf8(captured.0.lock().clone()).unwrap();
+        assert!(
+            output.contains("[Miner] Block rejected by node"),
+            "{output}"
+        );
+        assert!(!output.contains("\x1b["), "{output:?}");
+    }
+}
@@ -1,12 +1,30 @@
 use crate::api::KaspaApi;
+use crate::latency::{
+    ConnectionLatencyTracker, SubmitLatencyTracker, HIGH_LATENCY_THRESHOLD, SUBMIT_QUEUE_WARN_DEPTH,
+};
+use crate::topology;
+use arc_swap::ArcSwap;
+use kaspa_addresses::Address;
 use kaspa_consensus_core::block::Block;
+use kaspa_consensus_core::header::Header;
+use kaspa_hashes::Hash;
 use kaspa_pow::State as PowState;
-use kaspa_rpc_core::RpcRawBlock;
+use kaspa_rpc_core::{RpcRawBlock, SubmitBlockRejectReason, SubmitBlockReport};
 use parking_lot::{Condvar, Mutex};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use sysinfo::Components;
+use tokio::runtime::Handle;
 use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tracing::field::Empty;
+use tracing::{Instrument, Level};
 
 // Performance optimizations inspired by kaspanet/cpuminer:
 // 1. Batch hash counting: Update atomic counter every BATCH_SIZE hashes instead of every hash
@@ -14,363 +32,2915 @@ use tokio::sync::{mpsc, watch};
 // 3. Optimized hot path: Minimize branches and checks in the inner mining loop
 // 4. Better nonce distribution: Use thread count as step size for optimal coverage
 // 5. Throttle optimization: Apply throttle less frequently to reduce overhead
+//
+// Not applicable: a partial-hash pre-filter before `check_pow`. kHeavyHash ends with
+// a full cSHAKE256 (Keccak-f[1600]) over the matrix product, and every output byte
+// depends on the whole permutation, so the leading bytes can't be computed any
+// cheaper than the full hash. `kaspa_pow::State` also only exposes the 256-bit
+// target, not a leading-zero count; `check_pow` is already just hash + compare.
+
+/// How each mining thread walks the nonce space
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NonceStrategy {
+    /// Thread `i` tries `i, i + N, i + 2N, ...` for `N` threads
+    #[default]
+    Strided,
+    /// The u64 space is split into `N` equal ranges, walked one by one
+    Contiguous,
+    /// Each thread starts at a random nonce from the OS RNG and walks by one
+    Random,
+}
+
+impl NonceStrategy {
+    /// Starting nonce and step for thread `thread_idx` of `threads`
+    fn start_and_step(self, thread_idx: usize, threads: usize) -> (u64, u64) {
+        match self {
+            Self::Strided => (thread_idx as u64, threads as u64),
+            Self::Contiguous => ((u64::MAX / threads as u64) * thread_idx as u64, 1),
+            Self::Random => (OsRng.next_u64(), 1),
+        }
+    }
+}
+
+/// How mining threads slow themselves down
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ThrottleMode {
+    /// Mine at full speed
+    #[default]
+    None,
+    /// Sleep this long every `THROTTLE_BATCH` hashes
+    Fixed(Duration),
+    /// Keep each thread within ±5% of this many hashes per second by adjusting its sleep
+    TargetHashrate(f64),
+}
+
+/// Hashes between throttle sleeps
+const THROTTLE_BATCH: u64 = 128;
+/// Allowed deviation from the target in `ThrottleMode::TargetHashrate`
+const TARGET_HASHRATE_TOLERANCE: f64 = 0.05;
+
+/// Per-thread throttle state, applied once every `THROTTLE_BATCH` hashes
+struct ThreadThrottle {
+    mode: ThrottleMode,
+    /// Current sleep per batch; only adjusted in `TargetHashrate` mode
+    sleep: Duration,
+    /// End of the last sleep, i.e. the start of the batch being hashed
+    batch_started: Instant,
+}
+
+impl ThreadThrottle {
+    fn new(mode: ThrottleMode) -> Self {
+        let sleep = match mode {
+            ThrottleMode::Fixed(d) => d,
+            ThrottleMode::None | ThrottleMode::TargetHashrate(_) => Duration::ZERO,
+        };
+        Self {
+            mode,
+            sleep,
+            batch_started: Instant::now(),
+        }
+    }
+
+    fn on_batch(&mut self) {
+        if let ThrottleMode::TargetHashrate(target) = self.mode {
+            let work = self.batch_started.elapsed();
+            let actual = THROTTLE_BATCH as f64 / (work + self.sleep).as_secs_f64();
+            if (actual - target).abs() > target * TARGET_HASHRATE_TOLERANCE {
+                // Sleep whatever is left of the batch's time budget at the target rate
+                let budget = THROTTLE_BATCH as f64 / target;
+                self.sleep = Duration::from_secs_f64((budget - work.as_secs_f64()).max(0.0));
+            }
+        }
+        if !self.sleep.is_zero() {
+            std::thread::sleep(self.sleep);
+        }
+        self.batch_started = Instant::now();
+    }
+
+    /// Restart batch timing, e.g. after the thread waited for work or skipped a batch
+    fn reset(&mut self) {
+        self.batch_started = Instant::now();
+    }
+}
 
 #[derive(Clone)]
 pub struct CpuMinerConfig {
+    /// First of `mining_addresses`
     pub mining_address: String,
+    /// Block rewards go to these in turn, moving to the next after every
+    /// accepted block, so earnings aren't all linked to one address
+    pub mining_addresses: Vec<String>,
     pub threads: usize,
-    pub throttle: Option<Duration>,
+    pub throttle_mode: ThrottleMode,
     pub template_poll_interval: Duration,
+    /// Reuse the last fetched template for this long before polling the node again.
+    /// Useful on high-latency connections where most polls return identical templates.
+    pub block_template_cache_duration: Option<Duration>,
+    /// Treat the template poller as stalled if a thread sees no new work for this long
+    pub work_timeout: Duration,
+    /// On a stalled poller, let a mining thread fetch a template itself (last resort)
+    pub enable_thread_emergency_fetch: bool,
+    /// Give up on a SubmitBlock call after this long so a slow node can't stall the
+    /// submit queue
+    pub submit_block_timeout: Duration,
+    pub nonce_strategy: NonceStrategy,
+    /// Log a hashrate summary this often (independent of the 1s metrics sampling)
+    pub emit_hashrate_log_interval: Option<Duration>,
+    /// How often `hashes_tried` is sampled for the hashrate figures
+    pub hashrate_sample_interval: Duration,
+    /// Smoothing factor of the live hashrate, in (0, 1]
+    pub hashrate_ema_alpha: f64,
+    /// Coinbase extra data (pool name, version string, ...); `None` uses
+    /// `DEFAULT_EXTRA_DATA`. At most `MAX_USER_EXTRA_DATA_LEN` bytes is accepted from the UI.
+    pub extra_data: Option<Vec<u8>>,
+    /// Append 4 random bytes to the coinbase extra data, new for each session, so
+    /// identically configured miners don't produce identical coinbase payloads
+    pub randomize_extra_data: bool,
+    /// Pin mining threads to the first hardware thread of each physical core,
+    /// skipping HyperThreading siblings. Threads beyond the number of physical
+    /// cores wrap around to the start of the list. Linux only.
+    pub disable_hyper_threading: bool,
+    /// Pin thread `i` to logical CPU `cpu_affinity[i % len]` (see `topology::detect_cpus`).
+    /// Takes precedence over `disable_hyper_threading`.
+    pub cpu_affinity: Option<Vec<usize>>,
+    /// Mine unthrottled for this long at start, recording peak hashrate and CPU
+    /// temperature, then fall back to `throttle_mode`. Only set after the user confirmed.
+    pub thermal_stress_test_duration: Option<Duration>,
+    /// Reconnect to the node after this many template fetches fail in a row
+    pub max_consecutive_failures: u32,
+    /// Retry a failed SubmitBlock call this many times before dropping the block
+    pub submit_retries: u32,
+    /// Wait before the first submit retry, doubled for each further retry
+    pub submit_retry_backoff: Duration,
+    /// Capacity of the queue between the mining threads and the submit task. Blocks
+    /// found while it is full are dropped rather than stalling the threads.
+    pub submit_queue_depth: usize,
+    /// Counter to add this session's hashes to, kept across sessions for a
+    /// lifetime total. `None` counts from zero.
+    pub hashes_tried_counter: Option<Arc<AtomicU64>>,
+    /// Configs are only created through `CpuMinerConfigBuilder`, which validates them
+    _validated: (),
+}
+
+impl CpuMinerConfig {
+    pub fn builder() -> CpuMinerConfigBuilder {
+        CpuMinerConfigBuilder::default()
+    }
+
+    /// Log the full effective configuration, for sharing in performance reports
+    pub fn log_effective(&self) {
+        tracing::info!(
+            address = %redact_address(&self.mining_address),
+            threads = self.threads,
+            throttle = ?self.throttle_mode,
+            poll_interval = ?self.template_poll_interval,
+            template_cache = %format_optional_duration(self.block_template_cache_duration),
+            work_timeout = ?self.work_timeout,
+            emergency_fetch = self.enable_thread_emergency_fetch,
+            submit_timeout = ?self.submit_block_timeout,
+            nonce_strategy = ?self.nonce_strategy,
+            extra_data = %to_hex(self.extra_data.as_deref().unwrap_or(DEFAULT_EXTRA_DATA)),
+            randomize_extra_data = self.randomize_extra_data,
+            disable_hyper_threading = self.disable_hyper_threading,
+            cpu_affinity = ?self.cpu_affinity,
+            thermal_stress_test = %format_optional_duration(self.thermal_stress_test_duration),
+            hashrate_log_interval = %format_optional_duration(self.emit_hashrate_log_interval),
+            hashrate_sample_interval = ?self.hashrate_sample_interval,
+            hashrate_ema_alpha = self.hashrate_ema_alpha,
+            max_consecutive_failures = self.max_consecutive_failures,
+            submit_retries = self.submit_retries,
+            submit_retry_backoff = ?self.submit_retry_backoff,
+            submit_queue_depth = self.submit_queue_depth,
+            "[Miner] Effective config"
+        );
+    }
+
+    /// CPUs to pin threads to (thread `i` uses entry `i % len`), or `None` to let the OS schedule
+    fn pinned_cpus(&self) -> Option<Arc<[usize]>> {
+        if let Some(cpus) = self.cpu_affinity.as_ref().filter(|cpus| !cpus.is_empty()) {
+            tracing::info!("[Miner] Pinning threads to CPUs: {:?}", cpus);
+            Some(cpus.as_slice().into())
+        } else if self.disable_hyper_threading {
+            let primaries = topology::primary_logical_cpus();
+            match &primaries {
+                Some(cpus) => tracing::info!(
+                    "[Miner] Pinning threads to primary hardware threads: {:?}",
+                    cpus
+                ),
+                None => tracing::warn!(
+                    "[Miner] disable_hyper_threading is set but the CPU topology could not be read"
+                ),
+            }
+            primaries.map(Into::into)
+        } else {
+            None
+        }
+    }
+}
+
+/// Builds a `CpuMinerConfig`. Settings that aren't set keep the app defaults.
+pub struct CpuMinerConfigBuilder {
+    mining_addresses: Vec<String>,
+    threads: usize,
+    throttle: Option<Duration>,
+    target_hashrate: Option<f64>,
+    poll_interval: Duration,
+    extra_data: Option<Vec<u8>>,
+    thermal_stress_test: Option<Duration>,
+    cpu_affinity: Option<Vec<usize>>,
+    nonce_strategy: NonceStrategy,
+    hashes_tried_counter: Option<Arc<AtomicU64>>,
+}
+
+impl Default for CpuMinerConfigBuilder {
+    fn default() -> Self {
+        Self {
+            mining_addresses: Vec::new(),
+            threads: 1,
+            throttle: None,
+            target_hashrate: None,
+            poll_interval: default_poll_interval(None),
+            extra_data: None,
+            thermal_stress_test: None,
+            cpu_affinity: None,
+            nonce_strategy: NonceStrategy::default(),
+            hashes_tried_counter: None,
+        }
+    }
+}
+
+impl CpuMinerConfigBuilder {
+    pub fn mining_address(mut self, address: impl Into<String>) -> Self {
+        self.mining_addresses = vec![address.into()];
+        self
+    }
+
+    /// Rotate through several addresses, see `CpuMinerConfig::mining_addresses`
+    pub fn mining_addresses(mut self, addresses: Vec<String>) -> Self {
+        self.mining_addresses = addresses;
+        self
+    }
+
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Sleep this long every `THROTTLE_BATCH` hashes
+    pub fn throttle(mut self, throttle: Duration) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Hold each thread near this many hashes per second; replaces `throttle`
+    pub fn target_hashrate(mut self, hashes_per_second: f64) -> Self {
+        self.target_hashrate = Some(hashes_per_second);
+        self
+    }
+
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn extra_data(mut self, extra_data: Vec<u8>) -> Self {
+        self.extra_data = Some(extra_data);
+        self
+    }
+
+    /// See `CpuMinerConfig::thermal_stress_test_duration`
+    pub fn thermal_stress_test(mut self, duration: Duration) -> Self {
+        self.thermal_stress_test = Some(duration);
+        self
+    }
+
+    /// See `CpuMinerConfig::cpu_affinity`
+    pub fn cpu_affinity(mut self, cpus: Vec<usize>) -> Self {
+        self.cpu_affinity = Some(cpus);
+        self
+    }
+
+    pub fn nonce_strategy(mut self, strategy: NonceStrategy) -> Self {
+        self.nonce_strategy = strategy;
+        self
+    }
+
+    /// See `CpuMinerConfig::hashes_tried_counter`
+    pub fn hashes_tried_counter(mut self, counter: Arc<AtomicU64>) -> Self {
+        self.hashes_tried_counter = Some(counter);
+        self
+    }
+
+    pub fn build(self) -> anyhow::Result<CpuMinerConfig> {
+        if self.mining_addresses.iter().all(|a| a.trim().is_empty()) {
+            return Err(anyhow::anyhow!("Mining address is required"));
+        }
+        self.build_benchmark()
+    }
+
+    /// Like `build`, but without a mining address, which a benchmark never uses
+    pub fn build_benchmark(self) -> anyhow::Result<CpuMinerConfig> {
+        if self.threads == 0 {
+            return Err(anyhow::anyhow!("At least one mining thread is required"));
+        }
+        if self.poll_interval.is_zero() {
+            return Err(anyhow::anyhow!("Template poll interval must be positive"));
+        }
+        let throttle_mode = match (self.target_hashrate, self.throttle) {
+            (Some(hps), _) if !hps.is_finite() || hps < 0.0 => {
+                return Err(anyhow::anyhow!("Target hashrate must be a positive number"));
+            }
+            // A hashrate target replaces the fixed sleep
+            (Some(hps), _) if hps > 0.0 => ThrottleMode::TargetHashrate(hps),
+            (_, Some(throttle)) => ThrottleMode::Fixed(throttle),
+            _ => ThrottleMode::None,
+        };
+        if self
+            .extra_data
+            .as_ref()
+            .is_some_and(|data| data.len() > MAX_USER_EXTRA_DATA_LEN)
+        {
+            return Err(anyhow::anyhow!(
+                "Extra data is longer than {} bytes",
+                MAX_USER_EXTRA_DATA_LEN
+            ));
+        }
+        let cpu_affinity = self.cpu_affinity.filter(|cpus| !cpus.is_empty());
+        if let Some(cpus) = &cpu_affinity {
+            let available = topology::detect_cpus();
+            if let Some(cpu) = cpus
+                .iter()
+                .find(|&&cpu| !available.iter().any(|c| c.id == cpu))
+            {
+                return Err(anyhow::anyhow!(
+                    "CPU {} is not available to pin a thread to",
+                    cpu
+                ));
+            }
+        }
+        let mining_addresses: Vec<String> = self
+            .mining_addresses
+            .iter()
+            .map(|address| address.trim())
+            .filter(|address| !address.is_empty())
+            .map(str::to_string)
+            .collect();
+        Ok(CpuMinerConfig {
+            mining_address: mining_addresses.first().cloned().unwrap_or_default(),
+            mining_addresses,
+            threads: self.threads,
+            throttle_mode,
+            template_poll_interval: self.poll_interval,
+            block_template_cache_duration: None,
+            work_timeout: Duration::from_secs(5),
+            enable_thread_emergency_fetch: false,
+            submit_block_timeout: Duration::from_secs(5),
+            nonce_strategy: self.nonce_strategy,
+            extra_data: self.extra_data,
+            randomize_extra_data: false,
+            disable_hyper_threading: false,
+            cpu_affinity,
+            thermal_stress_test_duration: self.thermal_stress_test,
+            emit_hashrate_log_interval: Some(Duration::from_secs(60)),
+            hashrate_sample_interval: HASHRATE_SAMPLE_INTERVAL,
+            hashrate_ema_alpha: HASHRATE_EMA_ALPHA,
+            max_consecutive_failures: MAX_CONSECUTIVE_FAILURES,
+            submit_retries: SUBMIT_RETRIES,
+            submit_retry_backoff: SUBMIT_RETRY_BACKOFF,
+            submit_queue_depth: SUBMIT_QUEUE_DEPTH,
+            hashes_tried_counter: self.hashes_tried_counter,
+            _validated: (),
+        })
+    }
+}
+
+/// Keep the first 6 characters of an address so logs can be shared safely
+fn redact_address(address: &str) -> String {
+    let prefix: String = address.chars().take(6).collect();
+    format!("{}...", prefix)
+}
+
+fn format_optional_duration(d: Option<Duration>) -> String {
+    d.map(|d| format!("{:?}", d))
+        .unwrap_or_else(|| "<default>".to_string())
 }
 
 pub struct CpuMinerMetrics {
+    /// Hashes across every session sharing this counter (see
+    /// `CpuMinerConfig::hashes_tried_counter`)
     pub hashes_tried: Arc<AtomicU64>,
+    /// `hashes_tried` when this session started
+    pub session_start_hashes: u64,
+    /// Every submit attempt, whatever the outcome
     pub blocks_submitted: Arc<AtomicU64>,
     pub blocks_accepted: Arc<AtomicU64>,
+    /// Blocks the node answered with a rejection report
+    pub blocks_rejected: Arc<AtomicU64>,
+    /// Breakdown of `blocks_rejected` (see `RejectKind`). Rejections because the node
+    /// was syncing or overloaded are only counted in the total.
+    pub blocks_rejected_stale: Arc<AtomicU64>,
+    pub blocks_rejected_duplicate: Arc<AtomicU64>,
+    pub blocks_rejected_invalid: Arc<AtomicU64>,
+    /// `Debug` form of the most recent rejection report
+    pub last_rejection_reason: Arc<Mutex<Option<String>>>,
+    /// SubmitBlock calls abandoned after `submit_block_timeout`. They are not retried,
+    /// so each one is also counted in `blocks_dropped`.
+    pub submit_timeouts: Arc<AtomicU64>,
+    /// Blocks lost because SubmitBlock timed out, or kept failing after
+    /// `submit_retries` retries
+    pub blocks_dropped: Arc<AtomicU64>,
+    /// Blocks lost while stopping: found after the submit task exited, or
+    /// failed to submit once shutdown had started
+    pub blocks_dropped_on_shutdown: Arc<AtomicU64>,
+    /// Blocks lost because the submit queue was full when they were found
+    pub blocks_dropped_backpressure: Arc<AtomicU64>,
+    /// Published templates by where they came from (see `WorkSource`)
+    pub templates_from_poll: Arc<AtomicU64>,
+    pub templates_from_subscription: Arc<AtomicU64>,
+    pub templates_from_emergency_fetch: Arc<AtomicU64>,
+    pub template_stats: TemplateStats,
+    /// Times the connection watchdog replaced the node client
+    pub node_reconnects: Arc<AtomicU64>,
+    /// Set once `max_consecutive_failures` template fetches failed in a row, cleared
+    /// by the next successful fetch
+    pub connection_lost: Arc<AtomicBool>,
+    /// Number of mining threads that have not exited yet
+    pub active_threads: Arc<AtomicUsize>,
+    /// Configured thread count; changed by `set_thread_count`
+    pub thread_count: Arc<AtomicUsize>,
+    /// Times any thread wrapped around its whole nonce space
+    pub nonce_cycles: Arc<AtomicU64>,
+    /// Rolling 1-minute hashrate in micro-MH/s (MH/s * 1_000_000)
+    pub hashrate_mhs_micro: Arc<AtomicU64>,
+    /// Smoothed live hashrate in H/s (see `HashrateSampler::current_hps`)
+    pub hashrate_ema_hps: Arc<AtomicU64>,
+    /// Highest 1-minute hashrate seen this session, in micro-MH/s
+    pub peak_hashrate_mhs_micro: Arc<AtomicU64>,
+    /// Most recent blocks accepted by the node, newest last
+    pub accepted_blocks: Arc<Mutex<VecDeque<AcceptedBlock>>>,
+    /// Uptime and block timing; starts when the metrics are created with the miner
+    pub session: Arc<Mutex<SessionStats>>,
+    /// Round trips of `get_block_template` calls, polled and emergency fetches alike
+    pub template_fetch_latency: Arc<Mutex<ConnectionLatencyTracker>>,
+    /// Queue wait and SubmitBlock time of found blocks
+    pub submit_latency: Arc<Mutex<SubmitLatencyTracker>>,
+    /// Found blocks waiting in the submit channel
+    pub submit_queue_depth: Arc<AtomicUsize>,
+    /// One entry per hashrate sample, oldest first; at most `STATS_SNAPSHOT_HISTORY`
+    pub snapshots: Arc<Mutex<VecDeque<StatsSnapshot>>>,
+    /// Constant labels attached to every exported metric (node, address, version)
+    pub labels: Arc<HashMap<String, String>>,
+    /// Per-thread diagnostics, indexed by thread. Updated every `CHECK_WORK_INTERVAL`
+    /// hashes, so values lag the thread slightly.
+    pub thread_last_active: Arc<Vec<AtomicU64>>,
+    pub thread_nonces: Arc<Vec<AtomicU64>>,
+    pub thread_hashes_this_work: Arc<Vec<AtomicU64>>,
+    pub thread_total_hashes: Arc<Vec<AtomicU64>>,
+    /// Set once the thread has walked its whole nonce space and started over
+    pub thread_nonces_exhausted: Arc<Vec<AtomicBool>>,
+}
+
+/// Counters of the template poller
+#[derive(Default)]
+pub struct TemplateStats {
+    /// Polled templates identical to the one being mined, so not published
+    pub duplicate_templates_skipped: Arc<AtomicU64>,
+    /// Templates not published because their DAA score was below the current one
+    pub stale_templates_rejected: Arc<AtomicU64>,
+}
+
+/// Snapshot of one mining thread, for diagnosing stalled threads
+#[derive(Clone, Debug, Serialize)]
+pub struct ThreadDetail {
+    pub idx: usize,
+    pub current_nonce: u64,
+    pub hashes_this_work: u64,
+    pub total_hashes: u64,
+    pub last_active_ms_ago: u64,
+    pub nonces_exhausted: bool,
+}
+
+/// Coinbase extra data sent with every template request unless configured
+pub const DEFAULT_EXTRA_DATA: &[u8] = b"rkstratum-cpu-miner";
+/// Longest custom extra data the UIs accept
+pub const MAX_USER_EXTRA_DATA_LEN: usize = 64;
+/// Room left for extra data in the 204-byte coinbase payload after the 19 bytes of
+/// fixed fields and a standard (up to 35-byte) script public key
+const MAX_EXTRA_DATA_LEN: usize = 150;
+const RANDOM_EXTRA_DATA_LEN: usize = 4;
+
+/// Extra data for this session, optionally with a random suffix
+fn session_extra_data(base: &[u8], randomize: bool) -> Vec<u8> {
+    let mut extra_data = base.to_vec();
+    if randomize {
+        extra_data.truncate(MAX_EXTRA_DATA_LEN - RANDOM_EXTRA_DATA_LEN);
+        let mut suffix = [0u8; RANDOM_EXTRA_DATA_LEN];
+        OsRng.fill_bytes(&mut suffix);
+        extra_data.extend_from_slice(&suffix);
+    }
+    extra_data.truncate(MAX_EXTRA_DATA_LEN);
+    extra_data
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn micro_to_mhs(mhs_micro: u64) -> f64 {
+    mhs_micro as f64 / 1_000_000.0
+}
+
+/// Default `CpuMinerConfig::hashrate_sample_interval`
+pub const HASHRATE_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+/// Default `CpuMinerConfig::hashrate_ema_alpha`
+pub const HASHRATE_EMA_ALPHA: f64 = 0.3;
+/// Template poll interval for a network producing `bps` blocks per second.
+/// High BPS networks like TN12 (10 BPS) need new work quickly, so anything
+/// above 5 BPS (or unknown) polls every 50ms; slower networks poll twice per
+/// block, which is 500ms on mainnet (1 BPS).
+pub fn default_poll_interval(bps: Option<f64>) -> Duration {
+    match bps {
+        Some(bps) if bps > 0.0 && bps <= 5.0 => Duration::from_secs_f64(0.5 / bps.max(1.0)),
+        _ => Duration::from_millis(50),
+    }
+}
+
+/// Default `CpuMinerConfig::max_consecutive_failures`
+pub const MAX_CONSECUTIVE_FAILURES: u32 = 5;
+/// Default `CpuMinerConfig::submit_retries`
+pub const SUBMIT_RETRIES: u32 = 3;
+/// Default `CpuMinerConfig::submit_retry_backoff`
+pub const SUBMIT_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+/// Default `CpuMinerConfig::submit_queue_depth`
+pub const SUBMIT_QUEUE_DEPTH: usize = 32;
+/// Span of the rolling average hashrate
+const HASHRATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Turns the ever-increasing `hashes_tried` counter into hashes per second. Call
+/// `sample` once per interval; the live rate is an exponential moving average of
+/// the per-interval rates and the window average spans the last minute.
+pub struct HashrateSampler {
+    hashes: Arc<AtomicU64>,
+    samples: VecDeque<(Instant, u64)>,
+    capacity: usize,
+    alpha: f64,
+    ema_hps: Option<f64>,
+}
+
+impl HashrateSampler {
+    /// `alpha` in (0, 1] weights the newest interval; higher reacts faster
+    pub fn new(hashes: Arc<AtomicU64>, interval: Duration, alpha: f64) -> Self {
+        let interval = interval.max(Duration::from_millis(1));
+        let capacity = (HASHRATE_WINDOW.as_secs_f64() / interval.as_secs_f64()).ceil() as usize;
+        Self {
+            hashes,
+            samples: VecDeque::with_capacity(capacity + 1),
+            capacity: capacity.max(1),
+            alpha: alpha.clamp(f64::EPSILON, 1.0),
+            ema_hps: None,
+        }
+    }
+
+    pub fn sample(&mut self) {
+        let now = (Instant::now(), self.hashes.load(Ordering::Relaxed));
+        if let Some(&(prev_at, prev)) = self.samples.back() {
+            let secs = now.0.duration_since(prev_at).as_secs_f64();
+            if secs > 0.0 {
+                let hps = now.1.saturating_sub(prev) as f64 / secs;
+                self.ema_hps = Some(match self.ema_hps {
+                    Some(ema) => self.alpha * hps + (1.0 - self.alpha) * ema,
+                    None => hps,
+                });
+            }
+        }
+        self.samples.push_back(now);
+        if self.samples.len() > self.capacity + 1 {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Smoothed live hashrate in H/s
+    pub fn current_hps(&self) -> f64 {
+        self.ema_hps.unwrap_or(0.0)
+    }
+
+    /// Average hashrate over the sampled window (up to one minute) in H/s
+    pub fn window_avg_hps(&self) -> f64 {
+        let (Some(&(first_at, first)), Some(&(last_at, last))) =
+            (self.samples.front(), self.samples.back())
+        else {
+            return 0.0;
+        };
+        let secs = last_at.duration_since(first_at).as_secs_f64();
+        if secs > 0.0 {
+            last.saturating_sub(first) as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Counters at one `HashrateSampler` sample, for exporting the session
+#[derive(Debug, Clone)]
+pub struct StatsSnapshot {
+    pub at: Instant,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub hashes_tried: u64,
+    pub blocks_submitted: u64,
+    pub blocks_accepted: u64,
+    /// Smoothed live hashrate at this sample
+    pub hashrate_hps: f64,
+}
+
+/// Samples kept in `CpuMinerMetrics::snapshots` (an hour at the default interval)
+const STATS_SNAPSHOT_HISTORY: usize = 3600;
+
+/// Maximum number of entries kept in `CpuMinerMetrics::accepted_blocks`
+const ACCEPTED_BLOCKS_HISTORY: usize = 50;
+
+/// Block times kept by `SessionStats` for the average interval
+const SESSION_BLOCK_HISTORY: usize = 100;
+
+/// Timing of the current mining session
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    pub start_time: Instant,
+    pub last_block_found: Option<Instant>,
+    /// When accepted blocks were found, oldest first; at most `SESSION_BLOCK_HISTORY`
+    pub block_found_times: Vec<Instant>,
+    blocks_found: usize,
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self {
+            start_time: Instant::now(),
+            last_block_found: None,
+            block_found_times: Vec::new(),
+            blocks_found: 0,
+        }
+    }
+
+    pub fn record_block_found(&mut self) {
+        let now = Instant::now();
+        self.last_block_found = Some(now);
+        self.blocks_found += 1;
+        if self.block_found_times.len() == SESSION_BLOCK_HISTORY {
+            self.block_found_times.remove(0);
+        }
+        self.block_found_times.push(now);
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.start_time.elapsed()
+    }
+
+    /// Mean time between the recorded blocks; needs at least two
+    pub fn avg_block_interval(&self) -> Option<Duration> {
+        let intervals = self
+            .block_found_times
+            .len()
+            .checked_sub(1)
+            .filter(|n| *n > 0)?;
+        let span = *self.block_found_times.last()? - *self.block_found_times.first()?;
+        Some(span / intervals as u32)
+    }
+
+    /// Includes blocks that already rotated out of `block_found_times`
+    pub fn blocks_found_this_session(&self) -> usize {
+        self.blocks_found
+    }
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A block found by this miner and accepted by the node
+#[derive(Clone, Debug, Serialize)]
+pub struct AcceptedBlock {
+    pub nonce: u64,
+    pub hash: String,
+    pub timestamp: String,
 }
 
 impl Default for CpuMinerMetrics {
     fn default() -> Self {
         Self {
             hashes_tried: Arc::new(AtomicU64::new(0)),
+            session_start_hashes: 0,
             blocks_submitted: Arc::new(AtomicU64::new(0)),
             blocks_accepted: Arc::new(AtomicU64::new(0)),
+            blocks_rejected: Arc::new(AtomicU64::new(0)),
+            blocks_rejected_stale: Arc::new(AtomicU64::new(0)),
+            blocks_rejected_duplicate: Arc::new(AtomicU64::new(0)),
+            blocks_rejected_invalid: Arc::new(AtomicU64::new(0)),
+            last_rejection_reason: Arc::new(Mutex::new(None)),
+            submit_timeouts: Arc::new(AtomicU64::new(0)),
+            blocks_dropped: Arc::new(AtomicU64::new(0)),
+            blocks_dropped_on_shutdown: Arc::new(AtomicU64::new(0)),
+            blocks_dropped_backpressure: Arc::new(AtomicU64::new(0)),
+            templates_from_poll: Arc::new(AtomicU64::new(0)),
+            templates_from_subscription: Arc::new(AtomicU64::new(0)),
+            templates_from_emergency_fetch: Arc::new(AtomicU64::new(0)),
+            template_stats: TemplateStats::default(),
+            node_reconnects: Arc::new(AtomicU64::new(0)),
+            connection_lost: Arc::new(AtomicBool::new(false)),
+            active_threads: Arc::new(AtomicUsize::new(0)),
+            thread_count: Arc::new(AtomicUsize::new(0)),
+            nonce_cycles: Arc::new(AtomicU64::new(0)),
+            hashrate_mhs_micro: Arc::new(AtomicU64::new(0)),
+            hashrate_ema_hps: Arc::new(AtomicU64::new(0)),
+            peak_hashrate_mhs_micro: Arc::new(AtomicU64::new(0)),
+            accepted_blocks: Arc::new(Mutex::new(VecDeque::new())),
+            session: Arc::new(Mutex::new(SessionStats::new())),
+            template_fetch_latency: Arc::new(Mutex::new(ConnectionLatencyTracker::default())),
+            submit_latency: Arc::new(Mutex::new(SubmitLatencyTracker::default())),
+            submit_queue_depth: Arc::new(AtomicUsize::new(0)),
+            snapshots: Arc::new(Mutex::new(VecDeque::new())),
+            labels: Arc::new(HashMap::new()),
+            thread_last_active: Arc::new(Vec::new()),
+            thread_nonces: Arc::new(Vec::new()),
+            thread_hashes_this_work: Arc::new(Vec::new()),
+            thread_total_hashes: Arc::new(Vec::new()),
+            thread_nonces_exhausted: Arc::new(Vec::new()),
+        }
+    }
+}
+
+impl CpuMinerMetrics {
+    /// Per-thread slots are allocated for at least one thread per logical CPU, so
+    /// `set_thread_count` can grow the session up to that many threads
+    pub fn new(threads: usize, labels: HashMap<String, String>) -> Self {
+        let slots = threads.max(std::thread::available_parallelism().map_or(1, |n| n.get()));
+        let per_thread = || Arc::new((0..slots).map(|_| AtomicU64::new(0)).collect());
+        Self {
+            labels: Arc::new(labels),
+            thread_count: Arc::new(AtomicUsize::new(threads)),
+            thread_last_active: per_thread(),
+            thread_nonces: per_thread(),
+            thread_hashes_this_work: per_thread(),
+            thread_total_hashes: per_thread(),
+            thread_nonces_exhausted: Arc::new((0..slots).map(|_| AtomicBool::new(false)).collect()),
+            ..Default::default()
+        }
+    }
+
+    pub fn thread_details(&self) -> Vec<ThreadDetail> {
+        let now = unix_millis();
+        let threads = self.thread_count.load(Ordering::Relaxed);
+        (0..threads.min(self.thread_last_active.len()))
+            .map(|idx| ThreadDetail {
+                idx,
+                current_nonce: self.thread_nonces[idx].load(Ordering::Relaxed),
+                hashes_this_work: self.thread_hashes_this_work[idx].load(Ordering::Relaxed),
+                total_hashes: self.thread_total_hashes[idx].load(Ordering::Relaxed),
+                last_active_ms_ago: now
+                    .saturating_sub(self.thread_last_active[idx].load(Ordering::Relaxed)),
+                nonces_exhausted: self.thread_nonces_exhausted[idx].load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    fn record_rejection(&self, kind: RejectKind) {
+        self.blocks_rejected.fetch_add(1, Ordering::Relaxed);
+        let counter = match kind {
+            RejectKind::Stale => &self.blocks_rejected_stale,
+            RejectKind::Duplicate => &self.blocks_rejected_duplicate,
+            RejectKind::Invalid => &self.blocks_rejected_invalid,
+            RejectKind::NodeUnavailable => return,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_template(&self, source: WorkSource) {
+        let counter = match source {
+            WorkSource::Poll => &self.templates_from_poll,
+            WorkSource::Subscription => &self.templates_from_subscription,
+            WorkSource::EmergencyFetch => &self.templates_from_emergency_fetch,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Publish a thread's progress for `thread_details`
+    fn record_thread_activity(&self, idx: usize, nonce: u64, hashes_this_work: u64, total: u64) {
+        self.thread_nonces[idx].store(nonce, Ordering::Relaxed);
+        self.thread_hashes_this_work[idx].store(hashes_this_work, Ordering::Relaxed);
+        self.thread_total_hashes[idx].store(total, Ordering::Relaxed);
+        self.thread_last_active[idx].store(unix_millis(), Ordering::Relaxed);
+    }
+
+    /// Render all counters in Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let labels = self.prometheus_labels();
+        let counters = [
+            ("rkstratum_hashes_tried", &self.hashes_tried),
+            ("rkstratum_blocks_submitted", &self.blocks_submitted),
+            ("rkstratum_blocks_accepted", &self.blocks_accepted),
+            ("rkstratum_blocks_rejected", &self.blocks_rejected),
+            (
+                "rkstratum_blocks_rejected_stale",
+                &self.blocks_rejected_stale,
+            ),
+            (
+                "rkstratum_blocks_rejected_duplicate",
+                &self.blocks_rejected_duplicate,
+            ),
+            (
+                "rkstratum_blocks_rejected_invalid",
+                &self.blocks_rejected_invalid,
+            ),
+            ("rkstratum_submit_timeouts", &self.submit_timeouts),
+            ("rkstratum_node_reconnects", &self.node_reconnects),
+        ];
+        let mut out = String::new();
+        for (name, counter) in counters {
+            out.push_str(&format!("# TYPE {} counter\n", name));
+            out.push_str(&format!(
+                "{}{} {}\n",
+                name,
+                labels,
+                counter.load(Ordering::Relaxed)
+            ));
+        }
+        out
+    }
+
+    /// `{key="value",...}` label set, sorted by key for stable output
+    fn prometheus_labels(&self) -> String {
+        if self.labels.is_empty() {
+            return String::new();
+        }
+        let mut pairs: Vec<_> = self.labels.iter().collect();
+        pairs.sort();
+        let pairs: Vec<String> = pairs
+            .into_iter()
+            .map(|(k, v)| {
+                let v = v
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('\n', "\\n");
+                format!("{}=\"{}\"", k, v)
+            })
+            .collect();
+        format!("{{{}}}", pairs.join(","))
+    }
+
+    /// Hashes tried since this session started
+    pub fn session_hashes_tried(&self) -> u64 {
+        self.hashes_tried
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.session_start_hashes)
+    }
+
+    /// Snapshot of all counters as a JSON object
+    pub fn to_json(&self) -> serde_json::Value {
+        let (uptime, avg_block_interval, last_block_ago) = {
+            let session = self.session.lock();
+            (
+                session.uptime(),
+                session.avg_block_interval(),
+                session.last_block_found.map(|t| t.elapsed()),
+            )
+        };
+        let (latency_avg, latency_p95) = {
+            let latency = self.template_fetch_latency.lock();
+            (latency.avg_latency(), latency.p95_latency())
+        };
+        let (submit_queue_wait_avg, submit_rpc_avg) = {
+            let latency = self.submit_latency.lock();
+            (latency.queue_wait.avg_latency(), latency.rpc.avg_latency())
+        };
+        serde_json::json!({
+            "total_hashes_tried": self.hashes_tried.load(Ordering::Relaxed),
+            "session_hashes_tried": self.session_hashes_tried(),
+            "blocks_submitted": self.blocks_submitted.load(Ordering::Relaxed),
+            "blocks_accepted": self.blocks_accepted.load(Ordering::Relaxed),
+            "blocks_rejected": self.blocks_rejected.load(Ordering::Relaxed),
+            "blocks_rejected_stale": self.blocks_rejected_stale.load(Ordering::Relaxed),
+            "blocks_rejected_duplicate": self.blocks_rejected_duplicate.load(Ordering::Relaxed),
+            "blocks_rejected_invalid": self.blocks_rejected_invalid.load(Ordering::Relaxed),
+            "last_rejection_reason": &*self.last_rejection_reason.lock(),
+            "submit_timeouts": self.submit_timeouts.load(Ordering::Relaxed),
+            "blocks_dropped": self.blocks_dropped.load(Ordering::Relaxed),
+            "blocks_dropped_on_shutdown": self.blocks_dropped_on_shutdown.load(Ordering::Relaxed),
+            "blocks_dropped_backpressure": self.blocks_dropped_backpressure.load(Ordering::Relaxed),
+            "templates_from_poll": self.templates_from_poll.load(Ordering::Relaxed),
+            "templates_from_subscription": self.templates_from_subscription.load(Ordering::Relaxed),
+            "templates_from_emergency_fetch": self.templates_from_emergency_fetch.load(Ordering::Relaxed),
+            "duplicate_templates_skipped": self.template_stats.duplicate_templates_skipped.load(Ordering::Relaxed),
+            "stale_templates_rejected": self.template_stats.stale_templates_rejected.load(Ordering::Relaxed),
+            "node_reconnects": self.node_reconnects.load(Ordering::Relaxed),
+            "connection_lost": self.connection_lost.load(Ordering::Relaxed),
+            "active_threads": self.active_threads.load(Ordering::Relaxed),
+            "nonce_cycles": self.nonce_cycles.load(Ordering::Relaxed),
+            "hashrate_mhs": micro_to_mhs(self.hashrate_mhs_micro.load(Ordering::Relaxed)),
+            "hashrate_hps": self.hashrate_ema_hps.load(Ordering::Relaxed),
+            // micro-MH/s and H/s are the same unit
+            "hashrate_1m_avg_hps": self.hashrate_mhs_micro.load(Ordering::Relaxed),
+            "peak_hashrate_mhs": micro_to_mhs(self.peak_hashrate_mhs_micro.load(Ordering::Relaxed)),
+            "accepted_blocks": &*self.accepted_blocks.lock(),
+            "uptime_secs": uptime.as_secs(),
+            "avg_block_interval_secs": avg_block_interval.map(|d| d.as_secs_f64()),
+            "last_block_ago_secs": last_block_ago.map(|d| d.as_secs()),
+            "template_fetch_latency_ms_avg": latency_avg.as_secs_f64() * 1000.0,
+            "template_fetch_latency_ms_p95": latency_p95.as_secs_f64() * 1000.0,
+            "template_fetch_latency_high": latency_p95 > HIGH_LATENCY_THRESHOLD,
+            "submit_queue_wait_ms_avg": submit_queue_wait_avg.as_secs_f64() * 1000.0,
+            "submit_rpc_ms_avg": submit_rpc_avg.as_secs_f64() * 1000.0,
+            "submit_queue_depth": self.submit_queue_depth.load(Ordering::Relaxed),
+        })
+    }
+
+    /// Hash of the accepted block with the given nonce, if it is still in the history
+    pub fn accepted_block_hash(&self, nonce: u64) -> Option<String> {
+        self.accepted_blocks
+            .lock()
+            .iter()
+            .rev()
+            .find(|b| b.nonce == nonce)
+            .map(|b| b.hash.clone())
+    }
+
+    /// Periodic hashrate summary. The values are also recorded as span fields so
+    /// log aggregators can parse them without matching the message text.
+    fn log_hashrate(&self) {
+        let hashrate_mhs = micro_to_mhs(self.hashrate_mhs_micro.load(Ordering::Relaxed));
+        let accepted = self.blocks_accepted.load(Ordering::Relaxed);
+        let rejected = self.blocks_rejected.load(Ordering::Relaxed);
+        let span = tracing::info_span!("hashrate_report", hashrate_mhs, accepted, rejected);
+        let _entered = span.enter();
+        tracing::info!(
+            "[Miner] Hashrate: {:.3} MH/s (1m avg), Accepted: {}, Rejected: {}",
+            hashrate_mhs,
+            accepted,
+            rejected
+        );
+    }
+
+    /// Store the current hashrate and raise the session peak if it is higher
+    fn record_hashrate(&self, mhs_micro: u64) {
+        self.hashrate_mhs_micro.store(mhs_micro, Ordering::Relaxed);
+        let mut peak = self.peak_hashrate_mhs_micro.load(Ordering::Relaxed);
+        while mhs_micro > peak {
+            match self.peak_hashrate_mhs_micro.compare_exchange_weak(
+                peak,
+                mhs_micro,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(current) => peak = current,
+            }
+        }
+    }
+
+    fn record_accepted_block(&self, nonce: u64, hash: String) {
+        let mut accepted = self.accepted_blocks.lock();
+        accepted.push_back(AcceptedBlock {
+            nonce,
+            hash,
+            timestamp: chrono::Local::now().to_rfc3339(),
+        });
+        while accepted.len() > ACCEPTED_BLOCKS_HISTORY {
+            accepted.pop_front();
+        }
+    }
+
+    fn record_snapshot(&self, hashrate_hps: f64) {
+        let snapshot = StatsSnapshot {
+            at: Instant::now(),
+            timestamp: chrono::Utc::now(),
+            hashes_tried: self.session_hashes_tried(),
+            blocks_submitted: self.blocks_submitted.load(Ordering::Relaxed),
+            blocks_accepted: self.blocks_accepted.load(Ordering::Relaxed),
+            hashrate_hps,
+        };
+        let mut snapshots = self.snapshots.lock();
+        if snapshots.len() == STATS_SNAPSHOT_HISTORY {
+            snapshots.pop_front();
+        }
+        snapshots.push_back(snapshot);
+    }
+
+    /// Spawn a task that writes one JSON line of metrics to `fd` every `interval`.
+    /// Intended for shell-based monitoring pipelines (e.g. telegraf reading a pipe).
+    /// The task runs until aborted or until a write fails.
+    pub fn stream_to_fd(
+        self: &Arc<Self>,
+        mut fd: impl Write + Send + 'static,
+        interval: Duration,
+    ) -> JoinHandle<()> {
+        let metrics = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                let mut line = metrics.to_json();
+                line["timestamp"] = serde_json::json!(chrono::Utc::now().to_rfc3339());
+                if let Err(e) = writeln!(fd, "{}", line).and_then(|_| fd.flush()) {
+                    tracing::warn!("[Miner] Metrics stream closed: {e}");
+                    break;
+                }
+            }
+        })
+    }
+}
+
+/// Decrements `active_threads` when a mining thread exits, whichever path it takes
+struct ActiveThreadGuard(Arc<AtomicUsize>);
+
+impl ActiveThreadGuard {
+    fn new(counter: &Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::AcqRel);
+        Self(Arc::clone(counter))
+    }
+}
+
+impl Drop for ActiveThreadGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+struct Work {
+    id: u64,
+    /// `block.header.daa_score`; a newer template always has a higher score
+    daa_score: u64,
+    block: Block,
+    rpc_block: RpcRawBlock,
+    pow_state: Arc<PowState>,
+    source: WorkSource,
+}
+
+/// Which mechanism produced a template, for debugging the template pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WorkSource {
+    Poll,
+    /// Pushed by a new block template notification. The RPC client used here does
+    /// not expose that subscription yet, so this counter stays at 0 for now.
+    #[allow(dead_code)]
+    Subscription,
+    EmergencyFetch,
+}
+
+/// Why the node rejected a block. The node reports every consensus failure as
+/// `BlockInvalid`, so stale and duplicate blocks are told apart locally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RejectKind {
+    /// Mined on a template more than `STALE_DAA_GAP` behind the tip when the node
+    /// answered; a high count means the template poll interval should be reduced
+    Stale,
+    /// The same block was submitted before
+    Duplicate,
+    /// Rejected on the current template: invalid PoW or a malformed block
+    Invalid,
+    /// The node is syncing (IBD) or its submit route is full
+    NodeUnavailable,
+}
+
+impl RejectKind {
+    fn classify(report: &SubmitBlockReport, duplicate: bool, stale: bool) -> Self {
+        match report {
+            SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) if duplicate => {
+                Self::Duplicate
+            }
+            SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) if stale => {
+                Self::Stale
+            }
+            SubmitBlockReport::Reject(SubmitBlockRejectReason::BlockInvalid) => Self::Invalid,
+            _ => Self::NodeUnavailable,
+        }
+    }
+}
+
+/// How far past a rejected block's DAA score the tip must be before the rejection
+/// is put down to a stale template. The node still accepts blocks a little behind
+/// the tip (they merge into the DAG), so a rejection with a smaller gap points at
+/// a genuinely invalid block.
+const STALE_DAA_GAP: u64 = 100;
+
+/// Number of recent block hashes remembered to detect duplicate submissions
+const SUBMITTED_HASH_HISTORY: usize = 256;
+
+/// Monotonic counter identifying each published work item. Kept distinct from
+/// other u64s (nonces, work ids) so they cannot be compared by accident.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WorkVersion(u64);
+
+impl WorkVersion {
+    fn initial() -> Self {
+        Self(0)
+    }
+
+    fn wrapping_next(self) -> Self {
+        Self(self.0.wrapping_add(1))
+    }
+
+    fn changed_since(self, last: Self) -> bool {
+        self != last
+    }
+}
+
+struct WorkSlot {
+    work: Option<Work>,
+    version: WorkVersion,
+}
+
+/// Current work item shared with the mining threads. Reads go through `ArcSwap`
+/// and never lock; the mutex/condvar pair only serves threads that block waiting
+/// for a new version.
+struct SharedWork {
+    slot: ArcSwap<WorkSlot>,
+    /// Set when the node client was replaced: the next `publish` accepts any DAA
+    /// score, since the new node may lag behind the old one
+    daa_floor_reset: AtomicBool,
+    wait_lock: Mutex<()>,
+    cv: Condvar,
+}
+
+impl SharedWork {
+    fn new() -> Self {
+        Self {
+            slot: ArcSwap::from_pointee(WorkSlot {
+                work: None,
+                version: WorkVersion::initial(),
+            }),
+            daa_floor_reset: AtomicBool::new(false),
+            wait_lock: Mutex::new(()),
+            cv: Condvar::new(),
+        }
+    }
+
+    /// DAA score of the currently published template
+    fn current_daa_score(&self) -> Option<u64> {
+        self.slot.load().work.as_ref().map(|w| w.daa_score)
+    }
+
+    /// True if `header` has the same timestamp and parents as the published template,
+    /// i.e. the node returned the template threads are already mining
+    fn is_current_template(&self, header: &Header) -> bool {
+        self.slot.load().work.as_ref().is_some_and(|w| {
+            w.block.header.timestamp == header.timestamp
+                && w.block.header.parents_by_level == header.parents_by_level
+        })
+    }
+
+    /// Version of the currently published work (lock-free)
+    fn version(&self) -> WorkVersion {
+        self.slot.load().version
+    }
+
+    /// Let the next `publish` go through whatever its DAA score (see `daa_floor_reset`)
+    fn reset_daa_floor(&self) {
+        self.daa_floor_reset.store(true, Ordering::Release);
+    }
+
+    /// Make `work` the current work. Returns false (and publishes nothing) if it has
+    /// a lower DAA score than the current work, e.g. a delayed response from before a
+    /// block was found. An equal score is a refresh of the same tip (new mempool
+    /// transactions) and is still published.
+    fn publish(&self, work: Work, metrics: &CpuMinerMetrics) -> bool {
+        // Held so concurrent publishers don't reuse a version and waiters can't
+        // miss the notification between their version check and `wait`
+        let _guard = self.wait_lock.lock();
+        let current = self.slot.load();
+        let floor_reset = self.daa_floor_reset.swap(false, Ordering::AcqRel);
+        if let Some(current_score) = current.work.as_ref().map(|w| w.daa_score) {
+            if work.daa_score < current_score && !floor_reset {
+                tracing::debug!(
+                    "[Miner] Rejecting stale template (work id: {}, DAA score {} < {})",
+                    work.id,
+                    work.daa_score,
+                    current_score
+                );
+                metrics
+                    .template_stats
+                    .stale_templates_rejected
+                    .fetch_add(1, Ordering::Relaxed);
+                return false;
+            }
+        }
+        tracing::debug!(
+            "[Miner] Publishing template (work id: {}, source: {:?})",
+            work.id,
+            work.source
+        );
+        metrics.record_template(work.source);
+        let version = current.version.wrapping_next();
+        self.slot.store(Arc::new(WorkSlot {
+            work: Some(work),
+            version,
+        }));
+        self.cv.notify_all();
+        true
+    }
+
+    fn wait_for_update(
+        &self,
+        last_seen: WorkVersion,
+        shutdown_flag: &AtomicBool,
+    ) -> (WorkVersion, Option<Work>) {
+        if !self.version().changed_since(last_seen) {
+            let mut guard = self.wait_lock.lock();
+            while !self.version().changed_since(last_seen) && !shutdown_flag.load(Ordering::Acquire)
+            {
+                self.cv.wait(&mut guard);
+            }
+        }
+        self.snapshot(last_seen, shutdown_flag.load(Ordering::Acquire))
+    }
+
+    /// Like `wait_for_update`, but returns `None` if no update arrives within `timeout`.
+    /// Also returns early once `thread_shutdown` is set (see `set_thread_count`).
+    fn wait_for_update_timeout(
+        &self,
+        last_seen: WorkVersion,
+        shutdown_flag: &AtomicBool,
+        thread_shutdown: &AtomicBool,
+        timeout: Duration,
+    ) -> Option<(WorkVersion, Option<Work>)> {
+        let stopping =
+            || shutdown_flag.load(Ordering::Acquire) || thread_shutdown.load(Ordering::Acquire);
+        if !self.version().changed_since(last_seen) {
+            let deadline = Instant::now() + timeout;
+            let mut guard = self.wait_lock.lock();
+            while !self.version().changed_since(last_seen) && !stopping() {
+                if self.cv.wait_until(&mut guard, deadline).timed_out() {
+                    if !self.version().changed_since(last_seen) && !stopping() {
+                        return None;
+                    }
+                    break;
+                }
+            }
+        }
+        Some(self.snapshot(last_seen, stopping()))
+    }
+
+    /// The current work, or `None` if there is no newer version than `last_seen`
+    /// and the caller is `stopping`
+    fn snapshot(&self, last_seen: WorkVersion, stopping: bool) -> (WorkVersion, Option<Work>) {
+        let slot = self.slot.load();
+        if stopping && !slot.version.changed_since(last_seen) {
+            return (last_seen, None);
+        }
+        (
+            slot.version,
+            slot.work.as_ref().map(|w| Work {
+                id: w.id,
+                daa_score: w.daa_score,
+                block: w.block.clone(),
+                rpc_block: w.rpc_block.clone(),
+                pow_state: Arc::clone(&w.pow_state),
+                source: w.source,
+            }),
+        )
+    }
+
+    fn notify_all(&self) {
+        let _guard = self.wait_lock.lock();
+        self.cv.notify_all();
+    }
+}
+
+/// Parks mining threads while the session is paused, keeping the session (and its
+/// counters) alive. Threads check `paused_flag` every `CHECK_WORK_INTERVAL` hashes.
+#[derive(Default)]
+struct PauseGate {
+    paused_flag: AtomicBool,
+    wait_lock: Mutex<()>,
+    paused_condvar: Condvar,
+}
+
+impl PauseGate {
+    fn is_paused(&self) -> bool {
+        self.paused_flag.load(Ordering::Acquire)
+    }
+
+    fn set_paused(&self, paused: bool) {
+        let _guard = self.wait_lock.lock();
+        self.paused_flag.store(paused, Ordering::Release);
+        self.paused_condvar.notify_all();
+    }
+
+    /// Block until resumed, shut down, or this thread is removed (`thread_shutdown`)
+    fn wait_while_paused(&self, shutdown_flag: &AtomicBool, thread_shutdown: &AtomicBool) {
+        let mut guard = self.wait_lock.lock();
+        while self.is_paused()
+            && !shutdown_flag.load(Ordering::Acquire)
+            && !thread_shutdown.load(Ordering::Acquire)
+        {
+            self.paused_condvar.wait(&mut guard);
+        }
+    }
+
+    fn notify_all(&self) {
+        let _guard = self.wait_lock.lock();
+        self.paused_condvar.notify_all();
+    }
+}
+
+/// Template poller state: the work threads are mining (`current`, tracked by its
+/// DAA score) and the template fetched while they mine it (`prefetch`), which is
+/// promoted as soon as the fetch returns.
+#[derive(Default)]
+struct WorkPipeline {
+    current_daa_score: Option<u64>,
+    prefetch: Option<Work>,
+}
+
+impl WorkPipeline {
+    fn stage(&mut self, work: Work) {
+        self.prefetch = Some(work);
+    }
+
+    /// Drop a prefetched template that is older than the current one
+    fn discard_prefetch(&mut self) {
+        self.prefetch = None;
+    }
+
+    /// True when the prefetched template builds on a different virtual state than
+    /// `current`, i.e. the network (or this miner) found a block since
+    fn prefetch_is_new_tip(&self) -> bool {
+        self.prefetch
+            .as_ref()
+            .is_some_and(|w| self.current_daa_score != Some(w.daa_score))
+    }
+
+    /// Publish the prefetched template as the current work, if there is one.
+    /// Returns false if there was none or `SharedWork::publish` rejected it as stale.
+    fn promote(&mut self, shared: &SharedWork, metrics: &CpuMinerMetrics) -> bool {
+        let Some(work) = self.prefetch.take() else {
+            return false;
+        };
+        let daa_score = work.daa_score;
+        let published = shared.publish(work, metrics);
+        if published {
+            self.current_daa_score = Some(daa_score);
+        }
+        published
+    }
+}
+
+/// Last-resort recovery when the template poller appears stalled: fetch and publish
+/// a template directly from a mining thread. Only one thread runs this at a time.
+fn emergency_fetch(
+    runtime: &Handle,
+    kaspa_api: &KaspaApi,
+    mining_address: &str,
+    extra_data: &[u8],
+    work: &SharedWork,
+    metrics: &CpuMinerMetrics,
+    next_id: &AtomicU64,
+    in_progress: &AtomicBool,
+) {
+    if in_progress.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let started = Instant::now();
+    let fetched = runtime.block_on(kaspa_api.get_block_template_rpc(mining_address, extra_data));
+    metrics
+        .template_fetch_latency
+        .lock()
+        .record(started.elapsed());
+    match fetched {
+        Ok((block, rpc_block)) => {
+            let id = next_id.fetch_add(1, Ordering::Relaxed);
+            let header = block.header.clone();
+            let pow_state = Arc::new(PowState::new(&header));
+            let published = work.publish(
+                Work {
+                    id,
+                    daa_score: block.header.daa_score,
+                    block,
+                    rpc_block,
+                    pow_state,
+                    source: WorkSource::EmergencyFetch,
+                },
+                metrics,
+            );
+            if published {
+                tracing::warn!(
+                    "[Miner] Emergency template fetch succeeded (work id: {})",
+                    id
+                );
+            }
+        }
+        Err(e) => {
+            tracing::warn!("[Miner] Emergency template fetch failed: {e}");
+        }
+    }
+    in_progress.store(false, Ordering::Release);
+}
+
+/// Temperature at or above which the stress test result suggests backing off
+const STRESS_TEST_HOT_TEMP_C: f32 = 80.0;
+
+/// Highest temperature reported by any sensor, in °C
+fn max_component_temp(components: &mut Components) -> Option<f32> {
+    components.refresh();
+    components
+        .iter()
+        .map(|c| c.temperature())
+        .filter(|t| t.is_finite() && *t > 0.0)
+        .reduce(f32::max)
+}
+
+/// Keep `active` set for `duration` so threads ignore their throttle, sampling the
+/// hashrate and CPU temperature once per `HASHRATE_SAMPLE_INTERVAL`, then log the result
+async fn run_thermal_stress_test(
+    duration: Duration,
+    metrics: Arc<CpuMinerMetrics>,
+    active: Arc<AtomicBool>,
+    shutdown_flag: Arc<AtomicBool>,
+) {
+    tracing::warn!(
+        "[Miner] Thermal stress test: mining unthrottled for {:?}",
+        duration
+    );
+    let mut components = Components::new_with_refreshed_list();
+    let started = Instant::now();
+    let mut peak_hashes_per_sec = 0.0f64;
+    let mut max_temp: Option<f32> = None;
+    let mut last = (Instant::now(), metrics.hashes_tried.load(Ordering::Relaxed));
+    let mut ticker = tokio::time::interval(HASHRATE_SAMPLE_INTERVAL);
+    ticker.tick().await;
+    while started.elapsed() < duration && !shutdown_flag.load(Ordering::Acquire) {
+        ticker.tick().await;
+        let now = (Instant::now(), metrics.hashes_tried.load(Ordering::Relaxed));
+        let secs = now.0.duration_since(last.0).as_secs_f64();
+        if secs > 0.0 {
+            peak_hashes_per_sec =
+                peak_hashes_per_sec.max(now.1.saturating_sub(last.1) as f64 / secs);
+        }
+        last = now;
+        if let Some(temp) = max_component_temp(&mut components) {
+            max_temp = Some(max_temp.map_or(temp, |m| m.max(temp)));
+        }
+    }
+    active.store(false, Ordering::Release);
+
+    let peak_mhs = peak_hashes_per_sec / 1_000_000.0;
+    match max_temp {
+        Some(temp) if temp >= STRESS_TEST_HOT_TEMP_C => tracing::warn!(
+            "[Miner] Thermal stress test: peak {:.1} MH/s, max temp {:.0}°C — consider reducing threads or enabling thermal throttle",
+            peak_mhs,
+            temp
+        ),
+        Some(temp) => tracing::info!(
+            "[Miner] Thermal stress test: peak {:.1} MH/s, max temp {:.0}°C",
+            peak_mhs,
+            temp
+        ),
+        None => tracing::info!(
+            "[Miner] Thermal stress test: peak {:.1} MH/s, no temperature sensors found",
+            peak_mhs
+        ),
+    }
+}
+
+/// How often the connection watchdog checks the failure count
+const WATCHDOG_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Replaces the node client once `max_consecutive_failures` template fetches failed in
+/// a row. A dropped gRPC stream otherwise makes every later request fail the same way.
+struct ConnectionWatchdog {
+    api: Arc<ArcSwap<KaspaApi>>,
+    /// Node the miner was started against
+    address: String,
+    /// Reset by the template poller on every successful fetch
+    consecutive_failures: Arc<AtomicU32>,
+    /// Error of the template poller's most recent failed fetch
+    last_error: Arc<Mutex<String>>,
+    max_consecutive_failures: u32,
+    metrics: Arc<CpuMinerMetrics>,
+    errors: mpsc::Sender<MinerError>,
+}
+
+impl ConnectionWatchdog {
+    async fn run(self, mut shutdown: watch::Receiver<bool>) {
+        let mut ticker = tokio::time::interval(WATCHDOG_CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = shutdown.wait_for(|v| *v) => return,
+            }
+            let failures = self.consecutive_failures.load(Ordering::Acquire);
+            if failures < self.max_consecutive_failures.max(1) {
+                continue;
+            }
+
+            tracing::warn!(
+                "[Miner] {} consecutive template requests failed, reconnecting to {}",
+                failures,
+                self.address
+            );
+            self.metrics.connection_lost.store(true, Ordering::Release);
+            let _ = self
+                .errors
+                .try_send(MinerError::ConnectionLost(ConnectionLostPayload {
+                    address: self.address.clone(),
+                    consecutive_failures: failures,
+                    last_error: self.last_error.lock().clone(),
+                }));
+
+            // `KaspaApi::new` retries with backoff until the node is reachable
+            let api = tokio::select! {
+                api = KaspaApi::new(self.address.clone()) => api,
+                _ = shutdown.wait_for(|v| *v) => return,
+            };
+            match api {
+                Ok(api) => {
+                    self.api.store(api);
+                    self.consecutive_failures.store(0, Ordering::Release);
+                    self.metrics.node_reconnects.fetch_add(1, Ordering::Relaxed);
+                    // `connection_lost` stays set until the poller gets a template through
+                    tracing::warn!("[Miner] Reconnected to {}", self.address);
+                }
+                Err(e) => tracing::warn!("[Miner] Reconnect to {} failed: {}", self.address, e),
+            }
         }
     }
 }
 
-struct Work {
-    id: u64,
-    block: Block,
-    rpc_block: RpcRawBlock,
-    pow_state: Arc<PowState>,
-}
-
-struct WorkSlot {
-    work: Option<Work>,
-    version: u64,
+/// Payload of the `connection_lost` event
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionLostPayload {
+    pub address: String,
+    pub consecutive_failures: u32,
+    pub last_error: String,
 }
 
-struct SharedWork {
-    slot: Mutex<WorkSlot>,
-    cv: Condvar,
+/// Errors raised by `start_cpu_miner`, or reported by its background tasks over the
+/// error channel while mining
+#[derive(Debug, thiserror::Error)]
+pub enum MinerError {
+    #[error("mining address is required")]
+    EmptyMiningAddress,
+    /// `index` counts from 1, in `CpuMinerConfig::mining_addresses` order
+    #[error("mining address {index} ({address}) rejected: {reason}")]
+    InvalidMiningAddress {
+        index: usize,
+        address: String,
+        reason: String,
+    },
+    /// The template couldn't be fetched or converted into a block (there is no
+    /// separate conversion variant). `attempt` counts consecutive failures since
+    /// the last good template.
+    #[error("failed to get block template (attempt {attempt}): {source}")]
+    TemplateFetchFailed { attempt: u8, source: anyhow::Error },
+    #[error("submit block failed: {0}")]
+    SubmitFailed(anyhow::Error),
+    #[error(
+        "lost connection to {} after {} failed template requests, reconnecting: {}",
+        .0.address,
+        .0.consecutive_failures,
+        .0.last_error
+    )]
+    ConnectionLost(ConnectionLostPayload),
+    #[error(
+        "thread {thread} searched its whole nonce space and is starting over; a fresh template would help"
+    )]
+    NonceExhausted { thread: usize },
+    #[error("could not reach the node after {attempts} attempts")]
+    MaxReconnectionAttemptsExceeded { attempts: u32 },
+    /// The miner was stopped while a template request was still waiting on the node
+    #[error("miner stopped while waiting for a block template")]
+    ShutdownWhileWaiting,
 }
 
-impl SharedWork {
-    fn new() -> Self {
-        Self {
-            slot: Mutex::new(WorkSlot {
-                work: None,
-                version: 0,
-            }),
-            cv: Condvar::new(),
+impl MinerError {
+    /// Frontend event emitted for this error
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            MinerError::EmptyMiningAddress | MinerError::InvalidMiningAddress { .. } => {
+                "miner_config_error"
+            }
+            MinerError::TemplateFetchFailed { .. } => "template_fetch_failed",
+            MinerError::SubmitFailed(_) => "submit_failed",
+            MinerError::ConnectionLost(_) => "connection_lost",
+            MinerError::NonceExhausted { .. } => "nonce_exhausted",
+            MinerError::MaxReconnectionAttemptsExceeded { .. } => "connection_failed",
+            MinerError::ShutdownWhileWaiting => "shutdown_while_waiting",
         }
     }
 
-    fn publish(&self, work: Work) {
-        let mut slot = self.slot.lock();
-        slot.version = slot.version.wrapping_add(1);
-        slot.work = Some(work);
-        self.cv.notify_all();
-    }
-
-    fn wait_for_update(&self, last_seen: u64, shutdown_flag: &AtomicBool) -> (u64, Option<Work>) {
-        let mut slot = self.slot.lock();
-        while slot.version == last_seen && !shutdown_flag.load(Ordering::Acquire) {
-            self.cv.wait(&mut slot);
+    /// Frontend event payload: structured for `connection_lost`, the message otherwise
+    pub fn payload(&self) -> serde_json::Value {
+        match self {
+            MinerError::ConnectionLost(payload) => serde_json::json!(payload),
+            _ => serde_json::Value::String(self.to_string()),
         }
-        if shutdown_flag.load(Ordering::Acquire) && slot.version == last_seen {
-            return (last_seen, None);
-        }
-        (
-            slot.version,
-            slot.work.as_ref().map(|w| Work {
-                id: w.id,
-                block: w.block.clone(),
-                rpc_block: w.rpc_block.clone(),
-                pow_state: Arc::clone(&w.pow_state),
-            }),
-        )
     }
+}
 
-    fn notify_all(&self) {
-        self.cv.notify_all();
-    }
+// Optimization: Batch hash counting to reduce atomic operations
+// Update metrics every BATCH_SIZE hashes instead of every single hash
+const BATCH_SIZE: u64 = 1000;
+
+// Optimization: Check for work updates less frequently to reduce lock contention
+// Reduced to 250 for faster work updates (critical for high BPS networks like TN12 with 10 BPS)
+// At ~0.28 MH/s per thread, 250 hashes = ~0.9ms, ensuring work updates are detected within ~1ms
+// For single-threaded mining, this ensures minimal delay between finding blocks and getting new work
+// Optimization: Reduced to 200 for faster work detection without excessive lock contention
+// At ~0.28 MH/s per thread, 200 hashes = ~0.7ms check interval
+const CHECK_WORK_INTERVAL: u64 = 200;
+
+/// Errors queued for the caller before new ones are dropped
+const ERROR_CHANNEL_CAPACITY: usize = 32;
+/// Block found events queued for the caller before new ones are dropped
+const BLOCK_FOUND_CHANNEL_CAPACITY: usize = 32;
+
+/// A block the node accepted, sent as soon as the submit task hears back
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockFoundEvent {
+    pub nonce: u64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Id of the work (template) the block was mined from
+    pub work_id: u64,
+}
+
+/// Channels a running session reports on. All end when the miner tasks exit.
+pub struct MinerEvents {
+    pub errors: mpsc::Receiver<MinerError>,
+    pub blocks_found: mpsc::Receiver<BlockFoundEvent>,
+    /// Node address, sent on the first good template after a `ConnectionLost` error
+    pub connection_restored: mpsc::Receiver<String>,
 }
 
+/// A found block on its way from a mining thread to the submit task
+struct SubmitQueueEntry {
+    /// Id of the work (template) the block was mined from
+    work_id: u64,
+    block: RpcRawBlock,
+    submit_enqueued_at: Instant,
+}
+
+/// Maximum number of queued solutions pulled from the submit channel at once
+const SUBMIT_BATCH_MAX: usize = 8;
+/// Maximum number of concurrent SubmitBlock requests within a batch
+const SUBMIT_CONCURRENCY: usize = 3;
+
+/// Start mining. Returns the session handle and the channels background tasks
+/// report errors and found blocks on. The session's async tasks are spawned on
+/// `runtime`, and mining threads use it for emergency template fetches.
 pub async fn start_cpu_miner(
     kaspa_api: Arc<KaspaApi>,
     config: CpuMinerConfig,
-) -> Result<(Arc<CpuMinerMetrics>, watch::Sender<bool>), anyhow::Error> {
-    if config.mining_address.trim().is_empty() {
-        return Err(anyhow::anyhow!("mining address is required"));
+    runtime: Handle,
+) -> Result<(MinerHandle, MinerEvents), MinerError> {
+    config.log_effective();
+
+    if config.mining_addresses.is_empty() {
+        return Err(MinerError::EmptyMiningAddress);
     }
+    // Rewards for an address on another network could never be spent
+    let node_prefix = kaspa_api.network_prefix().await;
+    for (i, address) in config.mining_addresses.iter().enumerate() {
+        let invalid = |reason: String| MinerError::InvalidMiningAddress {
+            index: i + 1,
+            address: address.clone(),
+            reason,
+        };
+        let prefix = Address::try_from(address.as_str())
+            .map_err(|e| invalid(e.to_string()))?
+            .prefix;
+        if node_prefix.is_some_and(|node_prefix| node_prefix != prefix) {
+            return Err(invalid(
+                "address prefix does not match node network".to_string(),
+            ));
+        }
+    }
+    let mining_addresses: Arc<[String]> = config.mining_addresses.clone().into();
+    let current_address_index = Arc::new(AtomicUsize::new(0));
 
     let work = Arc::new(SharedWork::new());
     let shutdown_flag = Arc::new(AtomicBool::new(false));
     let (shutdown_tx, shutdown_rx) = watch::channel(false);
 
+    // `true` pauses mining without ending the session, `false` resumes it
+    let (pause_tx, pause_rx) = watch::channel(false);
+    let pause_gate = Arc::new(PauseGate::default());
+
     let shutdown_flag_clone = Arc::clone(&shutdown_flag);
     let work_clone = Arc::clone(&work);
+    let pause_gate_clone = Arc::clone(&pause_gate);
     let mut shutdown_rx_clone = shutdown_rx.clone();
-    tokio::spawn(async move {
+    runtime.spawn(async move {
         let _ = shutdown_rx_clone.wait_for(|v| *v).await;
         shutdown_flag_clone.store(true, Ordering::Release);
         work_clone.notify_all();
+        pause_gate_clone.notify_all();
+    });
+
+    let pause_gate_clone = Arc::clone(&pause_gate);
+    let mut pause_rx_gate = pause_rx.clone();
+    runtime.spawn(async move {
+        while pause_rx_gate.changed().await.is_ok() {
+            let paused = *pause_rx_gate.borrow_and_update();
+            tracing::info!(
+                "[Miner] Mining {}",
+                if paused { "paused" } else { "resumed" }
+            );
+            pause_gate_clone.set_paused(paused);
+        }
     });
 
-    let metrics = Arc::new(CpuMinerMetrics::default());
+    let threads = config.threads.max(1);
+    let mut metrics = CpuMinerMetrics::new(
+        threads,
+        HashMap::from([
+            ("node".to_string(), kaspa_api.active_address().to_string()),
+            ("address".to_string(), config.mining_address.clone()),
+            ("version".to_string(), env!("CARGO_PKG_VERSION").to_string()),
+        ]),
+    );
+    if let Some(counter) = &config.hashes_tried_counter {
+        metrics.session_start_hashes = counter.load(Ordering::Relaxed);
+        metrics.hashes_tried = Arc::clone(counter);
+    }
+    let metrics = Arc::new(metrics);
     let metrics_submit = Arc::clone(&metrics);
 
-    let (submit_tx, mut submit_rx) = mpsc::unbounded_channel::<RpcRawBlock>();
+    // Background tasks report failures here; they are dropped if the receiver falls behind
+    let (error_tx, error_rx) = mpsc::channel::<MinerError>(ERROR_CHANNEL_CAPACITY);
+    let (block_found_tx, block_found_rx) =
+        mpsc::channel::<BlockFoundEvent>(BLOCK_FOUND_CHANNEL_CAPACITY);
+    let (restored_tx, restored_rx) = mpsc::channel::<String>(ERROR_CHANNEL_CAPACITY);
+
+    // The watchdog may swap in a fresh client, so every task loads the current one
+    let node_address = kaspa_api.active_address().to_string();
+    let kaspa_api = Arc::new(ArcSwap::new(kaspa_api));
+    let consecutive_failures = Arc::new(AtomicU32::new(0));
+    let last_template_error = Arc::new(Mutex::new(String::new()));
+    let mut shutdown_rx_templates = shutdown_rx.clone();
+    runtime.spawn(
+        ConnectionWatchdog {
+            api: Arc::clone(&kaspa_api),
+            address: node_address,
+            consecutive_failures: Arc::clone(&consecutive_failures),
+            last_error: Arc::clone(&last_template_error),
+            max_consecutive_failures: config.max_consecutive_failures,
+            metrics: Arc::clone(&metrics),
+            errors: error_tx.clone(),
+        }
+        .run(shutdown_rx),
+    );
+
+    let metrics_stats = Arc::clone(&metrics);
+    let shutdown_flag_stats = Arc::clone(&shutdown_flag);
+    let hashrate_log_interval = config.emit_hashrate_log_interval;
+    let sample_interval = config.hashrate_sample_interval;
+    let ema_alpha = config.hashrate_ema_alpha;
+    runtime.spawn(async move {
+        let mut last_hashrate_log = Instant::now();
+        let mut sampler = HashrateSampler::new(
+            Arc::clone(&metrics_stats.hashes_tried),
+            sample_interval,
+            ema_alpha,
+        );
+        let mut ticker = tokio::time::interval(sample_interval.max(Duration::from_millis(1)));
+        while !shutdown_flag_stats.load(Ordering::Acquire) {
+            ticker.tick().await;
+            sampler.sample();
+            metrics_stats
+                .hashrate_ema_hps
+                .store(sampler.current_hps() as u64, Ordering::Relaxed);
+            // H/s equals micro-MH/s
+            metrics_stats.record_hashrate(sampler.window_avg_hps() as u64);
+            metrics_stats.record_snapshot(sampler.current_hps());
+
+            if let Some(log_interval) = hashrate_log_interval {
+                if last_hashrate_log.elapsed() >= log_interval {
+                    last_hashrate_log = Instant::now();
+                    metrics_stats.log_hashrate();
+                }
+            }
+        }
+    });
+
+    // Set when a submitted block is accepted so the template poller skips its cache
+    let template_cache_invalidated = Arc::new(AtomicBool::new(false));
+
+    // Tasks are named through their tracing span: `tokio::task::Builder` needs the
+    // `tokio_unstable` cfg, which this crate doesn't build with
+    //
+    // Blocks are sent with the id of the work they were mined from
+    let (submit_tx, mut submit_rx) =
+        mpsc::channel::<SubmitQueueEntry>(config.submit_queue_depth.max(1));
     let kaspa_api_submit = Arc::clone(&kaspa_api);
     let shutdown_flag_submit = Arc::clone(&shutdown_flag);
-    tokio::spawn(async move {
-        while let Some(rpc_block) = submit_rx.recv().await {
-            if shutdown_flag_submit.load(Ordering::Acquire) {
-                break;
-            }
-            let nonce = rpc_block.header.nonce;
-            let res = kaspa_api_submit.submit_rpc_block(rpc_block).await;
-            match res {
-                Ok(response) => {
-                    if response.report.is_success() {
-                        metrics_submit
-                            .blocks_submitted
-                            .fetch_add(1, Ordering::Relaxed);
-                        metrics_submit
-                            .blocks_accepted
-                            .fetch_add(1, Ordering::Relaxed);
-                        tracing::info!("[Miner] Block accepted by node (nonce: {})", nonce);
-                    } else {
-                        tracing::warn!("[Miner] Block rejected by node: {:?}", response.report);
+    let template_cache_invalidated_submit = Arc::clone(&template_cache_invalidated);
+    let address_index_submit = Arc::clone(&current_address_index);
+    let submit_timeout = config.submit_block_timeout;
+    let submit_retries = config.submit_retries;
+    let submit_retry_backoff = config.submit_retry_backoff;
+    let work_submit = Arc::clone(&work);
+    let error_tx_submit = error_tx.clone();
+    let submit_task = runtime.spawn(
+        async move {
+            let mut submitted_hashes: VecDeque<String> = VecDeque::new();
+            // Blocks submitted after shutdown started, for the summary below
+            let mut drained = 0usize;
+            // Not stopped by the shutdown flag: the loop ends once the mining threads
+            // and the handle have dropped their senders, so every queued block is sent
+            while let Some(solution) = submit_rx.recv().await {
+                let shutting_down = shutdown_flag_submit.load(Ordering::Acquire);
+
+                // Drain solutions that arrived together so they are submitted concurrently
+                let mut solutions = vec![solution];
+                while solutions.len() < SUBMIT_BATCH_MAX {
+                    match submit_rx.try_recv() {
+                        Ok(solution) => solutions.push(solution),
+                        Err(_) => break,
                     }
                 }
-                Err(e) => {
-                    tracing::warn!("[Miner] Submit block failed: {e}");
+                metrics_submit
+                    .submit_queue_depth
+                    .fetch_sub(solutions.len(), Ordering::Relaxed);
+                // Work id and queue wait time of each block in the batch
+                let (queued, batch): (Vec<(u64, Duration)>, Vec<RpcRawBlock>) = solutions
+                    .into_iter()
+                    .map(|entry| {
+                        let queue_wait_time = entry.submit_enqueued_at.elapsed();
+                        ((entry.work_id, queue_wait_time), entry.block)
+                    })
+                    .unzip();
+
+                // Block hashes are only needed for the accepted-blocks history (explorer links)
+                // and for detecting duplicate submissions
+                let found: Vec<(u64, u64, Option<String>)> = batch
+                    .iter()
+                    .map(|b| {
+                        let hash = Block::try_from(b.clone())
+                            .ok()
+                            .map(|block| block.hash().to_string());
+                        (b.header.nonce, b.header.daa_score, hash)
+                    })
+                    .collect();
+                let duplicates: Vec<bool> = found
+                    .iter()
+                    .map(|(_, _, hash)| {
+                        let Some(hash) = hash else {
+                            return false;
+                        };
+                        let seen = submitted_hashes.contains(hash);
+                        if !seen {
+                            if submitted_hashes.len() == SUBMITTED_HASH_HISTORY {
+                                submitted_hashes.pop_front();
+                            }
+                            submitted_hashes.push_back(hash.clone());
+                        }
+                        seen
+                    })
+                    .collect();
+                if shutting_down {
+                    drained += batch.len();
+                }
+                let results = kaspa_api_submit
+                    .load_full()
+                    .submit_batch(
+                        batch,
+                        SUBMIT_CONCURRENCY,
+                        submit_timeout,
+                        submit_retries,
+                        submit_retry_backoff,
+                    )
+                    .await;
+                let current_daa_score = work_submit.current_daa_score();
+                let outcomes = found
+                    .into_iter()
+                    .zip(duplicates)
+                    .zip(queued)
+                    .zip(results);
+                for (((found, duplicate), (work_id, queue_wait)), (res, rpc_time)) in outcomes {
+                    let (nonce, daa_score, hash) = found;
+                    metrics_submit
+                        .blocks_submitted
+                        .fetch_add(1, Ordering::Relaxed);
+                    metrics_submit
+                        .submit_latency
+                        .lock()
+                        .record(queue_wait, rpc_time);
+                    match res {
+                        Ok(response) => {
+                            if response.report.is_success() {
+                                metrics_submit
+                                    .blocks_accepted
+                                    .fetch_add(1, Ordering::Relaxed);
+                                template_cache_invalidated_submit.store(true, Ordering::Release);
+                                metrics_submit.session.lock().record_block_found();
+                                if let Some(hash) = hash {
+                                    metrics_submit.record_accepted_block(nonce, hash);
+                                }
+                                let _ = block_found_tx.try_send(BlockFoundEvent {
+                                    nonce,
+                                    timestamp: chrono::Utc::now(),
+                                    work_id,
+                                });
+                                tracing::info!("[Miner] Block accepted by node (nonce: {})", nonce);
+                                // The next template pays to the next address
+                                address_index_submit.fetch_add(1, Ordering::Relaxed);
+                            } else {
+                                let stale = current_daa_score
+                                    .is_some_and(|d| d.saturating_sub(daa_score) > STALE_DAA_GAP);
+                                let kind = RejectKind::classify(&response.report, duplicate, stale);
+                                metrics_submit.record_rejection(kind);
+                                *metrics_submit.last_rejection_reason.lock() =
+                                    Some(format!("{:?}", response.report));
+                                tracing::warn!(
+                                    "[Miner] Block rejected by node ({:?}): {:?}",
+                                    kind,
+                                    response.report
+                                );
+                            }
+                        }
+                        Err(e) if e.is::<tokio::time::error::Elapsed>() => {
+                            metrics_submit
+                                .submit_timeouts
+                                .fetch_add(1, Ordering::Relaxed);
+                            metrics_submit.blocks_dropped.fetch_add(1, Ordering::Relaxed);
+                            if shutting_down {
+                                metrics_submit
+                                    .blocks_dropped_on_shutdown
+                                    .fetch_add(1, Ordering::Relaxed);
+                            }
+                            tracing::error!(
+                                "[Miner] Submit block timed out after {:?}, block dropped (nonce: {})",
+                                submit_timeout,
+                                nonce
+                            );
+                        }
+                        Err(e) => {
+                            metrics_submit.blocks_dropped.fetch_add(1, Ordering::Relaxed);
+                            if shutting_down {
+                                metrics_submit
+                                    .blocks_dropped_on_shutdown
+                                    .fetch_add(1, Ordering::Relaxed);
+                            }
+                            tracing::error!(
+                                "[Miner] Submit block failed after {} retries, block dropped (nonce: {}): {e}",
+                                submit_retries,
+                                nonce
+                            );
+                            let _ = error_tx_submit.try_send(MinerError::SubmitFailed(e));
+                        }
+                    }
                 }
             }
+            let dropped = metrics_submit
+                .blocks_dropped_on_shutdown
+                .load(Ordering::Relaxed);
+            if drained > 0 || dropped > 0 {
+                tracing::info!(
+                    "[Miner] Submitted {} queued blocks during shutdown, {} dropped",
+                    drained,
+                    dropped
+                );
+            }
         }
-    });
+        .instrument(tracing::info_span!("task", name = "kaspa-miner-submit")),
+    );
+
+    let extra_data: Arc<[u8]> = session_extra_data(
+        config.extra_data.as_deref().unwrap_or(DEFAULT_EXTRA_DATA),
+        config.randomize_extra_data,
+    )
+    .into();
+    tracing::info!("[Miner] Coinbase extra_data: {}", to_hex(&extra_data));
+    let extra_data_templates = Arc::clone(&extra_data);
 
     let work_publisher = Arc::clone(&work);
+    let metrics_templates = Arc::clone(&metrics);
     let kaspa_api_templates = Arc::clone(&kaspa_api);
-    let mining_address = config.mining_address.clone();
+    let mining_addresses_templates = Arc::clone(&mining_addresses);
+    let address_index_templates = Arc::clone(&current_address_index);
     let poll = config.template_poll_interval;
+    let cache_duration = config.block_template_cache_duration;
     let shutdown_flag_templates = Arc::clone(&shutdown_flag);
     let next_id = Arc::new(AtomicU64::new(0));
     let next_id_templates = Arc::clone(&next_id);
-    tokio::spawn(async move {
-        // Fetch template immediately on startup
-        match kaspa_api_templates
-            .get_block_template_rpc(&mining_address)
-            .await
-        {
-            Ok((block, rpc_block)) => {
-                let id = next_id_templates.fetch_add(1, Ordering::Relaxed);
-                let header = block.header.clone();
-                let pow_state = Arc::new(PowState::new(&header));
-                work_publisher.publish(Work {
-                    id,
-                    block,
-                    rpc_block,
-                    pow_state,
-                });
-            }
-            Err(e) => {
-                tracing::warn!("[Miner] Initial get_block_template failed: {e}");
+    let error_tx_threads = error_tx.clone();
+    let error_tx_templates = error_tx;
+    let consecutive_failures_templates = consecutive_failures;
+    let node_address_templates = kaspa_api.load().active_address().to_string();
+    let mut pause_rx_templates = pause_rx;
+    let template_task = runtime.spawn(
+        async move {
+            let mut pipeline = WorkPipeline::default();
+            // Time the currently published template was fetched (used by the template cache)
+            let mut last_fetch: Option<Instant> = None;
+            // Client the last template was fetched with
+            let mut api_in_use = kaspa_api_templates.load_full();
+
+            let mut interval = tokio::time::interval(poll);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                if shutdown_flag_templates.load(Ordering::Acquire) {
+                    break;
+                }
+
+                // Don't poll the node while paused; templates fetched before the pause are
+                // stale by the time mining resumes
+                if *pause_rx_templates.borrow() {
+                    tokio::select! {
+                        resumed = pause_rx_templates.wait_for(|paused| !*paused) => {
+                            if resumed.is_err() {
+                                break;
+                            }
+                        }
+                        _ = shutdown_rx_templates.wait_for(|v| *v) => break,
+                    }
+                    pipeline = WorkPipeline::default();
+                    last_fetch = None;
+                    continue;
+                }
+
+                // The cached template is the one threads are already mining, so a cache hit
+                // simply leaves the current work in place instead of polling the node
+                let cache_hit = match (cache_duration, last_fetch) {
+                    (Some(cache_duration), Some(fetched_at)) => {
+                        let invalidated = template_cache_invalidated.swap(false, Ordering::AcqRel);
+                        let cache_age = fetched_at.elapsed();
+                        let hit = !invalidated && cache_age < cache_duration;
+                        if hit {
+                            tracing::trace!(
+                                "[Miner] Template cache hit (age: {:.0}ms)",
+                                cache_age.as_secs_f64() * 1000.0
+                            );
+                        }
+                        hit
+                    }
+                    _ => false,
+                };
+
+                // Fetch the next template into the prefetch slot while threads mine `current`
+                if !cache_hit {
+                    // After the watchdog swapped in a new client, the node may be behind
+                    // the one the current work came from; don't hold its templates to
+                    // the old DAA score
+                    let api = kaspa_api_templates.load_full();
+                    if !Arc::ptr_eq(&api, &api_in_use) {
+                        api_in_use = Arc::clone(&api);
+                        work_publisher.reset_daa_floor();
+                        pipeline = WorkPipeline::default();
+                    }
+                    let started = Instant::now();
+                    // A slow node shouldn't hold up stopping the miner
+                    let fetched = tokio::select! {
+                        fetched = api.get_block_template_rpc(
+                            rotating_address(&mining_addresses_templates, &address_index_templates),
+                            &extra_data_templates,
+                        ) => fetched,
+                        _ = shutdown_rx_templates.wait_for(|v| *v) => {
+                            let _ = error_tx_templates.try_send(MinerError::ShutdownWhileWaiting);
+                            break;
+                        }
+                    };
+                    metrics_templates
+                        .template_fetch_latency
+                        .lock()
+                        .record(started.elapsed());
+                    match fetched {
+                        Ok((block, rpc_block)) => {
+                            consecutive_failures_templates.store(0, Ordering::Release);
+                            if metrics_templates
+                                .connection_lost
+                                .swap(false, Ordering::AcqRel)
+                            {
+                                tracing::info!("[Miner] Templates are coming through again");
+                                let _ = restored_tx.try_send(node_address_templates.clone());
+                            }
+                            last_fetch = Some(Instant::now());
+                            if work_publisher.is_current_template(&block.header) {
+                                // Nothing changed: keep threads on their work and skip
+                                // building another `PowState`
+                                metrics_templates
+                                    .template_stats
+                                    .duplicate_templates_skipped
+                                    .fetch_add(1, Ordering::Relaxed);
+                                pipeline.discard_prefetch();
+                            } else {
+                                let id = next_id_templates.fetch_add(1, Ordering::Relaxed);
+                                let header = block.header.clone();
+                                let pow_state = Arc::new(PowState::new(&header));
+                                pipeline.stage(Work {
+                                    id,
+                                    daa_score: block.header.daa_score,
+                                    block,
+                                    rpc_block,
+                                    pow_state,
+                                    source: WorkSource::Poll,
+                                });
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!("[Miner] Get_block_template failed: {e}");
+                            *last_template_error.lock() = format!("{:#}", e);
+                            let failures =
+                                consecutive_failures_templates.fetch_add(1, Ordering::AcqRel) + 1;
+                            let _ = error_tx_templates.try_send(MinerError::TemplateFetchFailed {
+                                attempt: u8::try_from(failures).unwrap_or(u8::MAX),
+                                source: e,
+                            });
+                        }
+                    }
+                }
+
+                // Publish the fetched template as soon as it arrives; holding it until the
+                // next tick would only age it. A new tip also starts the next fetch right
+                // away instead of waiting for the poll interval. A stale template (lower
+                // DAA score) is rejected and falls through, so a lagging node is polled at
+                // the normal interval rather than in a tight loop.
+                let new_tip = pipeline.prefetch_is_new_tip();
+                if pipeline.promote(&work_publisher, &metrics_templates) && new_tip {
+                    continue;
+                }
+
+                interval.tick().await;
+                if shutdown_flag_templates.load(Ordering::Acquire) {
+                    break;
+                }
             }
         }
+        .instrument(tracing::info_span!("task", name = "kaspa-miner-templates")),
+    );
 
-        let mut interval = tokio::time::interval(poll);
-        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-        loop {
-            if shutdown_flag_templates.load(Ordering::Acquire) {
-                break;
+    // Set while the thermal stress test runs; threads skip their throttle meanwhile
+    let stress_test_active = Arc::new(AtomicBool::new(false));
+    if let Some(duration) = config.thermal_stress_test_duration {
+        stress_test_active.store(true, Ordering::Release);
+        runtime.spawn(run_thermal_stress_test(
+            duration,
+            Arc::clone(&metrics),
+            Arc::clone(&stress_test_active),
+            Arc::clone(&shutdown_flag),
+        ));
+    }
+
+    let thread_context = MiningThreadContext {
+        work: Arc::clone(&work),
+        submit_tx,
+        shutdown_flag: Arc::clone(&shutdown_flag),
+        found_counter: Arc::new(AtomicU64::new(0)),
+        metrics: Arc::clone(&metrics),
+        runtime,
+        kaspa_api,
+        mining_addresses,
+        current_address_index,
+        next_id,
+        emergency_fetch_in_progress: Arc::new(AtomicBool::new(false)),
+        stall_logged_at: Arc::new(Mutex::new(None)),
+        nonce_strategy: config.nonce_strategy,
+        extra_data,
+        stress_test_active,
+        pause_gate,
+        errors: error_tx_threads,
+        pinned_cpus: config.pinned_cpus(),
+        throttle_mode: config.throttle_mode,
+        work_timeout: config.work_timeout,
+        enable_emergency_fetch: config.enable_thread_emergency_fetch,
+    };
+    let thread_shutdowns = (0..threads)
+        .map(|thread_idx| thread_context.spawn(thread_idx))
+        .collect();
+
+    let handle = MinerHandle {
+        work,
+        shutdown_flag,
+        shutdown_tx,
+        pause_tx,
+        metrics,
+        threads: Mutex::new(thread_shutdowns),
+        thread_context,
+        tasks: vec![submit_task, template_task],
+    };
+    Ok((
+        handle,
+        MinerEvents {
+            errors: error_rx,
+            blocks_found: block_found_rx,
+            connection_restored: restored_rx,
+        },
+    ))
+}
+
+/// Round-robin pick from a session's mining addresses
+fn rotating_address<'a>(addresses: &'a [String], index: &AtomicUsize) -> &'a str {
+    &addresses[index.load(Ordering::Relaxed) % addresses.len()]
+}
+
+/// Everything a mining thread shares with the rest of the session. Cloned for
+/// each thread, including ones added later by `set_thread_count`.
+#[derive(Clone)]
+struct MiningThreadContext {
+    work: Arc<SharedWork>,
+    submit_tx: mpsc::Sender<SubmitQueueEntry>,
+    shutdown_flag: Arc<AtomicBool>,
+    found_counter: Arc<AtomicU64>,
+    metrics: Arc<CpuMinerMetrics>,
+    runtime: Handle,
+    kaspa_api: Arc<ArcSwap<KaspaApi>>,
+    mining_addresses: Arc<[String]>,
+    /// Index into `mining_addresses` of the address templates are requested for
+    current_address_index: Arc<AtomicUsize>,
+    next_id: Arc<AtomicU64>,
+    emergency_fetch_in_progress: Arc<AtomicBool>,
+    /// When a thread last logged a stalled poller. Every thread hits the work
+    /// timeout at about the same time, so only one logs per `work_timeout`.
+    stall_logged_at: Arc<Mutex<Option<Instant>>>,
+    nonce_strategy: NonceStrategy,
+    extra_data: Arc<[u8]>,
+    stress_test_active: Arc<AtomicBool>,
+    pause_gate: Arc<PauseGate>,
+    errors: mpsc::Sender<MinerError>,
+    pinned_cpus: Option<Arc<[usize]>>,
+    throttle_mode: ThrottleMode,
+    work_timeout: Duration,
+    enable_emergency_fetch: bool,
+}
+
+impl MiningThreadContext {
+    /// Start mining thread `thread_idx`. It exits on the global shutdown or once
+    /// the returned flag is set.
+    fn spawn(&self, thread_idx: usize) -> ThreadShutdown {
+        let flag = Arc::new(AtomicBool::new(false));
+        let active_guard = ActiveThreadGuard::new(&self.metrics.active_threads);
+        let ctx = self.clone();
+        let thread_shutdown = Arc::clone(&flag);
+        let name = format!("kaspa-miner-{}", thread_idx);
+        let spawned = std::thread::Builder::new()
+            .name(name.clone())
+            .spawn(move || {
+                let _active_guard = active_guard;
+                // The nonce fields are recorded by `run_mining_thread` once they are known
+                let _span = tracing::info_span!(
+                    "thread",
+                    name = %name,
+                    thread_idx,
+                    nonce_step = Empty,
+                    initial_nonce = Empty
+                )
+                .entered();
+                run_mining_thread(ctx, thread_idx, &thread_shutdown);
+            });
+        if let Err(e) = spawned {
+            tracing::error!("[Miner] Failed to start kaspa-miner-{}: {}", thread_idx, e);
+        }
+        ThreadShutdown { flag }
+    }
+}
+
+/// Stop signal of one mining thread
+struct ThreadShutdown {
+    flag: Arc<AtomicBool>,
+}
+
+/// A running mining session
+pub struct MinerHandle {
+    work: Arc<SharedWork>,
+    shutdown_flag: Arc<AtomicBool>,
+    shutdown_tx: watch::Sender<bool>,
+    /// `true` pauses, `false` resumes
+    pause_tx: watch::Sender<bool>,
+    metrics: Arc<CpuMinerMetrics>,
+    /// One entry per running thread, indexed by thread
+    threads: Mutex<Vec<ThreadShutdown>>,
+    thread_context: MiningThreadContext,
+    /// Submit and template tasks
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MinerHandle {
+    pub fn metrics(&self) -> &Arc<CpuMinerMetrics> {
+        &self.metrics
+    }
+
+    /// False once stopped or after every mining thread has exited
+    pub fn is_running(&self) -> bool {
+        !self.shutdown_flag.load(Ordering::Acquire)
+            && self.metrics.active_threads.load(Ordering::Acquire) > 0
+    }
+
+    pub fn thread_count(&self) -> usize {
+        self.threads.lock().len()
+    }
+
+    /// Address the current block templates pay to
+    pub fn current_mining_address(&self) -> &str {
+        rotating_address(
+            &self.thread_context.mining_addresses,
+            &self.thread_context.current_address_index,
+        )
+    }
+
+    /// Park the mining threads and template poller without ending the session
+    pub fn set_paused(&self, paused: bool) -> anyhow::Result<()> {
+        self.pause_tx
+            .send(paused)
+            .map_err(|_| anyhow::anyhow!("Miner has already stopped"))
+    }
+
+    /// Signal shutdown, then wait for the mining threads to exit and the submit
+    /// and template tasks to finish
+    pub fn stop(self) -> impl std::future::Future<Output = ()> {
+        let _ = self.shutdown_tx.send(true);
+        // Don't rely on the shutdown watcher task still being scheduled
+        self.shutdown_flag.store(true, Ordering::Release);
+        self.work.notify_all();
+        self.thread_context.pause_gate.notify_all();
+        let metrics = self.metrics;
+        let tasks = self.tasks;
+        // The context holds a submit sender; the submit task ends once all are gone
+        drop(self.thread_context);
+        async move {
+            while metrics.active_threads.load(Ordering::Acquire) > 0 {
+                tokio::time::sleep(THREAD_EXIT_POLL_INTERVAL).await;
             }
-            interval.tick().await;
-            if shutdown_flag_templates.load(Ordering::Acquire) {
-                break;
+            for task in tasks {
+                let _ = task.await;
             }
+        }
+    }
+}
 
-            match kaspa_api_templates
-                .get_block_template_rpc(&mining_address)
-                .await
-            {
-                Ok((block, rpc_block)) => {
-                    let id = next_id_templates.fetch_add(1, Ordering::Relaxed);
-                    let header = block.header.clone();
-                    let pow_state = Arc::new(PowState::new(&header));
-                    work_publisher.publish(Work {
-                        id,
-                        block,
-                        rpc_block,
-                        pow_state,
-                    });
-                }
-                Err(e) => {
-                    tracing::warn!("[Miner] Get_block_template failed: {e}");
-                }
+/// How often `MinerHandle::stop` checks whether the mining threads have exited
+const THREAD_EXIT_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Add or remove mining threads while mining. New threads join on the current
+/// work; removed threads (the highest indices) exit at their next work check.
+pub fn set_thread_count(handle: &MinerHandle, new_count: usize) -> anyhow::Result<()> {
+    let max_threads = handle.metrics.thread_last_active.len();
+    if new_count == 0 || new_count > max_threads {
+        return Err(anyhow::anyhow!(
+            "Thread count must be between 1 and {}",
+            max_threads
+        ));
+    }
+    if handle.shutdown_flag.load(Ordering::Acquire) {
+        return Err(anyhow::anyhow!("Miner has already stopped"));
+    }
+
+    let mut threads = handle.threads.lock();
+    let previous = threads.len();
+    if new_count == previous {
+        return Ok(());
+    }
+    handle
+        .metrics
+        .thread_count
+        .store(new_count, Ordering::Release);
+    while threads.len() > new_count {
+        if let Some(thread) = threads.pop() {
+            thread.flag.store(true, Ordering::Release);
+        }
+    }
+    if threads.len() < previous {
+        // Wake removed threads parked waiting for work or for a resume
+        handle.work.notify_all();
+        handle.thread_context.pause_gate.notify_all();
+    }
+    while threads.len() < new_count {
+        let thread_idx = threads.len();
+        threads.push(handle.thread_context.spawn(thread_idx));
+    }
+    tracing::info!(
+        "[Miner] Thread count changed from {} to {}",
+        previous,
+        new_count
+    );
+    Ok(())
+}
+
+fn run_mining_thread(ctx: MiningThreadContext, thread_idx: usize, thread_shutdown: &AtomicBool) {
+    let MiningThreadContext {
+        work,
+        submit_tx,
+        shutdown_flag,
+        found_counter,
+        metrics: metrics_threads,
+        runtime,
+        kaspa_api,
+        mining_addresses,
+        current_address_index,
+        next_id,
+        emergency_fetch_in_progress,
+        stall_logged_at,
+        nonce_strategy,
+        extra_data,
+        stress_test_active,
+        pause_gate,
+        errors,
+        pinned_cpus,
+        throttle_mode,
+        work_timeout,
+        enable_emergency_fetch,
+    } = ctx;
+
+    let pinned_cpu = pinned_cpus
+        .as_ref()
+        .map(|cpus| cpus[thread_idx % cpus.len()]);
+    if let Some(cpu) = pinned_cpu {
+        if !topology::pin_current_thread(cpu) {
+            tracing::warn!("[Miner] Failed to pin thread {} to CPU {}", thread_idx, cpu);
+        }
+    }
+    let mut last_version = WorkVersion::initial();
+    let mut threads = metrics_threads.thread_count.load(Ordering::Acquire).max(1);
+    let (mut nonce, mut nonce_step) = nonce_strategy.start_and_step(thread_idx, threads);
+    let thread_span = tracing::Span::current();
+    thread_span.record("nonce_step", nonce_step);
+    thread_span.record("initial_nonce", nonce);
+    // Every strategy walks with a fixed step, so hash counts follow from nonces
+    let mut first_nonce = nonce;
+    // Hashes walked before the last re-split of the nonce space
+    let mut hashes_before_resplit = 0u64;
+    // Set when `nonce` wraps around u64; once it is back at `first_nonce` the whole
+    // space has been searched
+    let mut nonce_wrapped = false;
+    metrics_threads.record_thread_activity(thread_idx, nonce, 0, 0);
+
+    // Local hash counter to batch atomic updates
+    let mut local_hash_count = 0u64;
+    let mut throttle = ThreadThrottle::new(throttle_mode);
+
+    let on_stalled_poller = || {
+        let log_now = {
+            let mut logged_at = stall_logged_at.lock();
+            let due = !logged_at.is_some_and(|t| t.elapsed() < work_timeout);
+            if due {
+                *logged_at = Some(Instant::now());
             }
+            due
+        };
+        if log_now {
+            tracing::error!(
+                "[Miner] No new work for {:.1}s, template poller may be stalled",
+                work_timeout.as_secs_f64()
+            );
         }
-    });
+        if enable_emergency_fetch {
+            emergency_fetch(
+                &runtime,
+                &kaspa_api.load(),
+                rotating_address(&mining_addresses, &current_address_index),
+                &extra_data,
+                &work,
+                &metrics_threads,
+                &next_id,
+                &emergency_fetch_in_progress,
+            );
+        }
+    };
 
-    let threads = config.threads.max(1);
-    let throttle = config.throttle;
-    let found_counter = Arc::new(AtomicU64::new(0));
-
-    // Optimization: Batch hash counting to reduce atomic operations
-    // Update metrics every BATCH_SIZE hashes instead of every single hash
-    const BATCH_SIZE: u64 = 1000;
-
-    // Optimization: Check for work updates less frequently to reduce lock contention
-    // Reduced to 250 for faster work updates (critical for high BPS networks like TN12 with 10 BPS)
-    // At ~0.28 MH/s per thread, 250 hashes = ~0.9ms, ensuring work updates are detected within ~1ms
-    // For single-threaded mining, this ensures minimal delay between finding blocks and getting new work
-    // Optimization: Reduced to 200 for faster work detection without excessive lock contention
-    // At ~0.28 MH/s per thread, 200 hashes = ~0.7ms check interval
-    const CHECK_WORK_INTERVAL: u64 = 200;
-
-    for thread_idx in 0..threads {
-        let work = Arc::clone(&work);
-        let submit_tx = submit_tx.clone();
-        let shutdown_flag = Arc::clone(&shutdown_flag);
-        let found_counter = Arc::clone(&found_counter);
-        let metrics_threads = Arc::clone(&metrics);
-
-        std::thread::spawn(move || {
-            let mut last_version = 0u64;
-            // Optimization: Use thread index as initial nonce offset for better distribution
-            // Simple offset is faster than large prime multiplication
-            let nonce_step = threads as u64;
-            let mut nonce = thread_idx as u64;
-
-            // Local hash counter to batch atomic updates
-            let mut local_hash_count = 0u64;
+    loop {
+        if shutdown_flag.load(Ordering::Acquire) || thread_shutdown.load(Ordering::Acquire) {
+            break;
+        }
+        if pause_gate.is_paused() {
+            pause_gate.wait_while_paused(&shutdown_flag, thread_shutdown);
+            continue;
+        }
 
-            loop {
-                if shutdown_flag.load(Ordering::Acquire) {
-                    break;
-                }
+        let Some((ver, maybe_work)) = work.wait_for_update_timeout(
+            last_version,
+            &shutdown_flag,
+            thread_shutdown,
+            work_timeout,
+        ) else {
+            on_stalled_poller();
+            continue;
+        };
+        last_version = ver;
 
-                let (ver, maybe_work) = work.wait_for_update(last_version, &shutdown_flag);
-                last_version = ver;
+        let Some(w) = maybe_work else {
+            continue;
+        };
 
-                let Some(w) = maybe_work else {
-                    continue;
-                };
+        // `set_thread_count` changed the thread count: split the nonce space again.
+        // Until every thread has picked up new work, strides may briefly overlap.
+        let current_threads = metrics_threads.thread_count.load(Ordering::Acquire).max(1);
+        if current_threads != threads {
+            hashes_before_resplit += nonce.wrapping_sub(first_nonce) / nonce_step;
+            threads = current_threads;
+            (nonce, nonce_step) = nonce_strategy.start_and_step(thread_idx, threads);
+            first_nonce = nonce;
+            nonce_wrapped = false;
+            thread_span.record("nonce_step", nonce_step);
+            thread_span.record("initial_nonce", nonce);
+        }
+
+        let work_span = tracing::info_span!(
+            "mining_work",
+            work_id = w.id,
+            nonce = Empty,
+            pow_passed = Empty
+        );
+        let _work_guard = work_span.enter();
+        // Checked once per work: a per-nonce event is far too hot to leave in unconditionally
+        let trace_nonces = tracing::enabled!(Level::TRACE);
 
-                // Optimization: Reset work check counter when new work arrives
-                let mut hashes_since_work_check = 0u64;
+        // Optimization: Reset work check counter when new work arrives
+        let mut hashes_since_work_check = 0u64;
+        // Separate from `hashes_since_work_check`, which is reset early when a block is found,
+        // so every throttle batch really is `THROTTLE_BATCH` hashes long
+        let mut hashes_since_throttle = 0u64;
+        let mut work_received_at = Instant::now();
+        let work_first_nonce = nonce;
+        // Time spent waiting for work doesn't count towards the hashrate target
+        throttle.reset();
 
-                // Mining loop for current work
-                loop {
-                    // Increment local counter
-                    local_hash_count += 1;
-                    hashes_since_work_check += 1;
+        // Mining loop for current work
+        loop {
+            // Increment local counter
+            local_hash_count += 1;
+            hashes_since_work_check += 1;
+            hashes_since_throttle += 1;
+
+            // Check PoW - this is the hot path, optimized for speed
+            // Increment nonce BEFORE checking to optimize branch prediction
+            let current_nonce = nonce;
+            nonce = nonce.wrapping_add(nonce_step);
+            nonce_wrapped |= nonce < current_nonce;
 
-                    // Check PoW - this is the hot path, optimized for speed
-                    // Increment nonce BEFORE checking to optimize branch prediction
-                    let current_nonce = nonce;
-                    nonce = nonce.wrapping_add(nonce_step);
+            let (passed, _) = w.pow_state.check_pow(current_nonce);
+            if trace_nonces {
+                tracing::event!(Level::TRACE, nonce = current_nonce, passed, "nonce attempt");
+            }
+            if passed {
+                work_span.record("nonce", current_nonce);
+                work_span.record("pow_passed", true);
+                // Batch update hash count before submitting
+                if local_hash_count > 0 {
+                    metrics_threads
+                        .hashes_tried
+                        .fetch_add(local_hash_count, Ordering::Relaxed);
+                    local_hash_count = 0;
+                }
 
-                    let (passed, _) = w.pow_state.check_pow(current_nonce);
-                    if passed {
-                        // Batch update hash count before submitting
-                        if local_hash_count > 0 {
+                // Optimization: Minimize cloning - only clone header and update nonce
+                // Transactions are already Arc'd internally, so clone is cheap
+                let mined_rpc_block = RpcRawBlock {
+                    header: {
+                        let mut h = w.rpc_block.header.clone();
+                        h.nonce = current_nonce;
+                        h
+                    },
+                    transactions: w.rpc_block.transactions.clone(), // Preserve original transactions with covenant data
+                };
+                // Counted before sending so the submit task never sees a depth below zero
+                let depth = metrics_threads
+                    .submit_queue_depth
+                    .fetch_add(1, Ordering::Relaxed)
+                    + 1;
+                if depth == SUBMIT_QUEUE_WARN_DEPTH + 1 {
+                    tracing::warn!(
+                        "[Miner] {} found blocks waiting to be submitted; the node is not keeping up",
+                        depth
+                    );
+                }
+                let entry = SubmitQueueEntry {
+                    work_id: w.id,
+                    block: mined_rpc_block,
+                    submit_enqueued_at: Instant::now(),
+                };
+                // Never block the thread on a full queue: keep hashing and drop the block
+                if let Err(e) = submit_tx.try_send(entry) {
+                    metrics_threads
+                        .submit_queue_depth
+                        .fetch_sub(1, Ordering::Relaxed);
+                    match e {
+                        mpsc::error::TrySendError::Full(entry) => {
                             metrics_threads
-                                .hashes_tried
-                                .fetch_add(local_hash_count, Ordering::Relaxed);
-                            local_hash_count = 0;
+                                .blocks_dropped_backpressure
+                                .fetch_add(1, Ordering::Relaxed);
+                            tracing::warn!(
+                                "[Miner] Submit queue full, block dropped (nonce: {})",
+                                entry.block.header.nonce
+                            );
                         }
-
-                        // Optimization: Minimize cloning - only clone header and update nonce
-                        // Transactions are already Arc'd internally, so clone is cheap
-                        let mined_rpc_block = RpcRawBlock {
-                            header: {
-                                let mut h = w.rpc_block.header.clone();
-                                h.nonce = current_nonce;
-                                h
-                            },
-                            transactions: w.rpc_block.transactions.clone(), // Preserve original transactions with covenant data
-                        };
-                        let _ = submit_tx.send(mined_rpc_block);
-                        found_counter.fetch_add(1, Ordering::Relaxed);
-
-                        // Optimization: Quick work check after finding block (minimal lock time)
-                        // Only check version number - if changed, we'll get new work in outer loop
-                        // Use try_lock for non-blocking check - if lock is busy, skip check and continue mining
-                        if let Some(slot) = work.slot.try_lock() {
-                            if slot.version != last_version {
-                                drop(slot);
-                                break; // New work available, get it immediately
-                            }
-                            // Lock released here automatically
+                        // The submit task has already exited, so we are shutting down
+                        mpsc::error::TrySendError::Closed(_) => {
+                            metrics_threads
+                                .blocks_dropped_on_shutdown
+                                .fetch_add(1, Ordering::Relaxed);
                         }
-                        // No new work yet - continue mining current work (still valid)
-                        // Reset counter to check more frequently for new work
-                        hashes_since_work_check = 0;
                     }
+                }
+                found_counter.fetch_add(1, Ordering::Relaxed);
+
+                // Quick work check after finding block - if the version changed,
+                // we'll get new work in outer loop
+                if work.version().changed_since(last_version) {
+                    break; // New work available, get it immediately
+                }
+                // No new work yet - continue mining current work (still valid)
+                // Reset counter to check more frequently for new work
+                hashes_since_work_check = 0;
+            }
+
+            // Batch update hash count periodically to reduce atomic operations
+            if local_hash_count >= BATCH_SIZE {
+                metrics_threads
+                    .hashes_tried
+                    .fetch_add(BATCH_SIZE, Ordering::Relaxed);
+                local_hash_count -= BATCH_SIZE;
+            }
+
+            // Apply throttle if configured (optimized: use counter instead of expensive modulo)
+            if throttle_mode != ThrottleMode::None {
+                if hashes_since_throttle >= THROTTLE_BATCH {
+                    hashes_since_throttle = 0;
+                    if stress_test_active.load(Ordering::Relaxed) {
+                        throttle.reset();
+                    } else {
+                        throttle.on_batch();
+                    }
+                }
+            }
 
-                    // Batch update hash count periodically to reduce atomic operations
-                    if local_hash_count >= BATCH_SIZE {
+            // Periodically check for shutdown or work updates (reduces lock contention)
+            if hashes_since_work_check >= CHECK_WORK_INTERVAL {
+                // Check shutdown first (cheap atomic read)
+                if shutdown_flag.load(Ordering::Acquire) || thread_shutdown.load(Ordering::Acquire)
+                {
+                    // Update remaining hash count before exiting
+                    if local_hash_count > 0 {
                         metrics_threads
                             .hashes_tried
-                            .fetch_add(BATCH_SIZE, Ordering::Relaxed);
-                        local_hash_count -= BATCH_SIZE;
+                            .fetch_add(local_hash_count, Ordering::Relaxed);
                     }
+                    return;
+                }
 
-                    // Apply throttle if configured (optimized: use counter instead of expensive modulo)
-                    if let Some(d) = throttle {
-                        // Use bitwise AND for power-of-2 check (faster than modulo)
-                        // Check every 128 hashes (2^7) - use hashes_since_work_check for consistent throttling
-                        if (hashes_since_work_check & 127) == 0 {
-                            std::thread::sleep(d);
-                        }
+                if pause_gate.is_paused() {
+                    if local_hash_count > 0 {
+                        metrics_threads
+                            .hashes_tried
+                            .fetch_add(local_hash_count, Ordering::Relaxed);
+                        local_hash_count = 0;
                     }
+                    pause_gate.wait_while_paused(&shutdown_flag, thread_shutdown);
+                    // The pause doesn't count towards the throttle or the work timeout
+                    throttle.reset();
+                    work_received_at = Instant::now();
+                    hashes_since_work_check = 0;
+                    hashes_since_throttle = 0;
+                    continue;
+                }
 
-                    // Periodically check for shutdown or work updates (reduces lock contention)
-                    if hashes_since_work_check >= CHECK_WORK_INTERVAL {
-                        // Check shutdown first (cheap atomic read)
-                        if shutdown_flag.load(Ordering::Acquire) {
-                            // Update remaining hash count before exiting
-                            if local_hash_count > 0 {
-                                metrics_threads
-                                    .hashes_tried
-                                    .fetch_add(local_hash_count, Ordering::Relaxed);
-                            }
-                            return;
-                        }
+                // Check if work has been updated (lock-free load)
+                if work.version().changed_since(last_version) {
+                    // Update remaining hash count before getting new work
+                    if local_hash_count > 0 {
+                        metrics_threads
+                            .hashes_tried
+                            .fetch_add(local_hash_count, Ordering::Relaxed);
+                        local_hash_count = 0;
+                    }
+                    break; // Break to outer loop to get new work
+                }
 
-                        // Check if work has been updated (less frequent lock acquisition)
-                        let slot = work.slot.lock();
-                        if slot.version != last_version {
-                            drop(slot);
-                            // Update remaining hash count before getting new work
-                            if local_hash_count > 0 {
-                                metrics_threads
-                                    .hashes_tried
-                                    .fetch_add(local_hash_count, Ordering::Relaxed);
-                                local_hash_count = 0;
-                            }
-                            break; // Break to outer loop to get new work
-                        }
-                        drop(slot);
+                if nonce_wrapped && nonce >= first_nonce {
+                    nonce_wrapped = false;
+                    metrics_threads.nonce_cycles.fetch_add(1, Ordering::Relaxed);
+                    metrics_threads.thread_nonces_exhausted[thread_idx]
+                        .store(true, Ordering::Relaxed);
+                    let error = MinerError::NonceExhausted { thread: thread_idx };
+                    tracing::warn!("[Miner] {}", error);
+                    let _ = errors.try_send(error);
+                }
 
-                        // Reset counter for next batch
-                        hashes_since_work_check = 0;
-                    }
+                metrics_threads.record_thread_activity(
+                    thread_idx,
+                    nonce,
+                    nonce.wrapping_sub(work_first_nonce) / nonce_step,
+                    hashes_before_resplit + nonce.wrapping_sub(first_nonce) / nonce_step,
+                );
+
+                // Still on the same work: detect a stalled template poller
+                if work_received_at.elapsed() >= work_timeout {
+                    on_stalled_poller();
+                    work_received_at = Instant::now();
                 }
+
+                // Reset counter for next batch
+                hashes_since_work_check = 0;
             }
+        }
+    }
 
-            // Final hash count update on thread exit
-            if local_hash_count > 0 {
-                metrics_threads
-                    .hashes_tried
-                    .fetch_add(local_hash_count, Ordering::Relaxed);
+    // Final hash count update on thread exit
+    if local_hash_count > 0 {
+        metrics_threads
+            .hashes_tried
+            .fetch_add(local_hash_count, Ordering::Relaxed);
+    }
+}
+
+/// Outcome of `benchmark_cpu_miner`
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkResult {
+    pub total_hashes: u64,
+    pub elapsed: Duration,
+    pub hashes_per_second: f64,
+    pub per_thread_hps: Vec<f64>,
+}
+
+/// Hash a fixed fake template for `duration` without a node, using the thread count,
+/// nonce strategy, throttle and pinning from `config`. Blocks the calling thread.
+pub fn benchmark_cpu_miner(config: CpuMinerConfig, duration: Duration) -> BenchmarkResult {
+    // All-zero header: its zero target can't be met, so no solution is ever found
+    let header = Header::from_precomputed_hash(Hash::default(), vec![]);
+    let pow_state = Arc::new(PowState::new(&header));
+    let threads = config.threads.max(1);
+    let pinned_cpus = config.pinned_cpus();
+    tracing::info!(
+        "[Miner] Benchmarking {} thread(s) for {:?}",
+        threads,
+        duration
+    );
+
+    let started = Instant::now();
+    let deadline = started + duration;
+    let handles: Vec<_> = (0..threads)
+        .map(|thread_idx| {
+            let pow_state = Arc::clone(&pow_state);
+            let nonce_strategy = config.nonce_strategy;
+            let throttle_mode = config.throttle_mode;
+            let pinned_cpu = pinned_cpus
+                .as_ref()
+                .map(|cpus| cpus[thread_idx % cpus.len()]);
+            std::thread::spawn(move || {
+                if let Some(cpu) = pinned_cpu {
+                    topology::pin_current_thread(cpu);
+                }
+                let (mut nonce, nonce_step) = nonce_strategy.start_and_step(thread_idx, threads);
+                let mut throttle = ThreadThrottle::new(throttle_mode);
+                let thread_started = Instant::now();
+                let mut hashes = 0u64;
+                while Instant::now() < deadline {
+                    for _ in 0..THROTTLE_BATCH {
+                        std::hint::black_box(pow_state.check_pow(nonce));
+                        nonce = nonce.wrapping_add(nonce_step);
+                    }
+                    hashes += THROTTLE_BATCH;
+                    if throttle_mode != ThrottleMode::None {
+                        throttle.on_batch();
+                    }
+                }
+                (hashes, thread_started.elapsed())
+            })
+        })
+        .collect();
+
+    let per_thread: Vec<(u64, Duration)> = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap_or_default())
+        .collect();
+    let elapsed = started.elapsed();
+    let total_hashes = per_thread.iter().map(|(hashes, _)| hashes).sum();
+    let result = BenchmarkResult {
+        total_hashes,
+        elapsed,
+        hashes_per_second: total_hashes as f64 / elapsed.as_secs_f64(),
+        per_thread_hps: per_thread
+            .iter()
+            .map(|(hashes, elapsed)| *hashes as f64 / elapsed.as_secs_f64())
+            .collect(),
+    };
+    tracing::info!(
+        "[Miner] Benchmark: {:.3} MH/s over {:?} ({} hashes)",
+        result.hashes_per_second / 1_000_000.0,
+        result.elapsed,
+        result.total_hashes
+    );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BENCH_THREADS: usize = 16;
+    const BENCH_READS_PER_THREAD: u64 = 20_000_000;
+
+    /// `BENCH_THREADS` threads each doing `BENCH_READS_PER_THREAD` work-version checks
+    /// through `read` while another thread publishes a new version every millisecond.
+    /// Returns the aggregate number of checks per second.
+    fn work_reads_per_second(
+        read: impl Fn() -> WorkVersion + Sync,
+        publish: impl Fn() + Sync,
+    ) -> f64 {
+        let done = AtomicBool::new(false);
+        let started = Instant::now();
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                while !done.load(Ordering::Acquire) {
+                    publish();
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+            });
+            let readers: Vec<_> = (0..BENCH_THREADS)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut last = WorkVersion::initial();
+                        let mut changes = 0u64;
+                        for _ in 0..BENCH_READS_PER_THREAD {
+                            let version = std::hint::black_box(read());
+                            if version.changed_since(last) {
+                                last = version;
+                                changes += 1;
+                            }
+                        }
+                        changes
+                    })
+                })
+                .collect();
+            for reader in readers {
+                std::hint::black_box(reader.join().unwrap());
             }
+            done.store(true, Ordering::Release);
+        });
+        (BENCH_THREADS as u64 * BENCH_READS_PER_THREAD) as f64 / started.elapsed().as_secs_f64()
+    }
+
+    /// Compares the `ArcSwap` read path against the `Mutex<WorkSlot>` it replaced.
+    /// Run with `cargo test --release -- --ignored --nocapture work_reads`.
+    #[test]
+    #[ignore = "benchmark"]
+    fn benchmark_work_reads() {
+        let locked = Mutex::new(WorkSlot {
+            work: None,
+            version: WorkVersion::initial(),
         });
+        let mutex_rate = work_reads_per_second(
+            || locked.lock().version,
+            || {
+                let mut slot = locked.lock();
+                slot.version = slot.version.wrapping_next();
+            },
+        );
+
+        let shared = SharedWork::new();
+        let arc_swap_rate = work_reads_per_second(
+            || shared.version(),
+            || {
+                let _guard = shared.wait_lock.lock();
+                let version = shared.slot.load().version.wrapping_next();
+                shared.slot.store(Arc::new(WorkSlot {
+                    work: None,
+                    version,
+                }));
+                shared.cv.notify_all();
+            },
+        );
+
+        println!(
+            "{} threads: Mutex {:.1} M reads/s, ArcSwap {:.1} M reads/s ({:.2}x)",
+            BENCH_THREADS,
+            mutex_rate / 1e6,
+            arc_swap_rate / 1e6,
+            arc_swap_rate / mutex_rate
+        );
     }
 
-    Ok((metrics, shutdown_tx))
+    /// `benchmark_cpu_miner` on 4 threads with each `NonceStrategy`.
+    /// Run with `cargo test --release -- --ignored --nocapture nonce_strategies`.
+    #[test]
+    #[ignore = "benchmark"]
+    fn benchmark_nonce_strategies() {
+        for strategy in [
+            NonceStrategy::Strided,
+            NonceStrategy::Contiguous,
+            NonceStrategy::Random,
+        ] {
+            let config = CpuMinerConfig::builder()
+                .threads(4)
+                .nonce_strategy(strategy)
+                .build_benchmark()
+                .unwrap();
+            let result = benchmark_cpu_miner(config, Duration::from_secs(10));
+            println!(
+                "{:?}: {:.3} MH/s total, per thread {:?}",
+                strategy,
+                result.hashes_per_second / 1e6,
+                result
+                    .per_thread_hps
+                    .iter()
+                    .map(|hps| format!("{:.3}", hps / 1e6))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
 }
@@ -0,0 +1,80 @@
+use crate::miner::CpuMinerMetrics;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A finished (or in-progress) mining session, kept in memory only
+#[derive(Clone, Debug, Serialize)]
+pub struct SessionRecord {
+    pub id: u64,
+    /// Unix timestamps in seconds
+    pub started_at: u64,
+    pub stopped_at: Option<u64>,
+    pub hashes: u64,
+    pub blocks_accepted: u64,
+    pub avg_hashrate_mhs: f64,
+    pub node: String,
+    pub address: String,
+    /// Settings needed to start an identical session again
+    pub threads: usize,
+    pub throttle_ms: Option<u64>,
+}
+
+/// In-memory history of mining sessions for this run of the app
+#[derive(Default)]
+pub struct SessionLog {
+    records: Mutex<Vec<SessionRecord>>,
+    active: Mutex<Option<SessionRecord>>,
+    next_id: AtomicU64,
+}
+
+impl SessionLog {
+    /// Begin tracking a new session, replacing any session that was never finished
+    pub fn start(&self, node: String, address: String, threads: usize, throttle_ms: Option<u64>) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        *self.active.lock() = Some(SessionRecord {
+            id,
+            started_at: unix_now(),
+            stopped_at: None,
+            hashes: 0,
+            blocks_accepted: 0,
+            avg_hashrate_mhs: 0.0,
+            node,
+            address,
+            threads,
+            throttle_ms,
+        });
+    }
+
+    /// Close the active session with the final counters from `metrics`
+    pub fn finish(&self, metrics: &CpuMinerMetrics) {
+        let Some(mut record) = self.active.lock().take() else {
+            return;
+        };
+        let stopped_at = unix_now();
+        record.stopped_at = Some(stopped_at);
+        record.hashes = metrics.session_hashes_tried();
+        record.blocks_accepted = metrics.blocks_accepted.load(Ordering::Relaxed);
+        let secs = stopped_at.saturating_sub(record.started_at);
+        if secs > 0 {
+            record.avg_hashrate_mhs = record.hashes as f64 / secs as f64 / 1_000_000.0;
+        }
+        self.records.lock().push(record);
+    }
+
+    /// Finished sessions, oldest first
+    pub fn list(&self) -> Vec<SessionRecord> {
+        self.records.lock().clone()
+    }
+
+    pub fn clear(&self) {
+        self.records.lock().clear();
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,109 @@
+use crate::miner::{CpuMinerMetrics, SessionStats};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// First line of an exported stats file
+pub const CSV_HEADER: &str =
+    "timestamp_utc,hashes_tried,blocks_submitted,blocks_accepted,hashrate_hps,uptime_secs";
+
+/// One line of an exported stats file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRow {
+    pub timestamp_utc: DateTime<Utc>,
+    pub hashes_tried: u64,
+    pub blocks_submitted: u64,
+    pub blocks_accepted: u64,
+    pub hashrate_hps: f64,
+    pub uptime_secs: u64,
+}
+
+impl fmt::Display for CsvRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{:.2},{}",
+            self.timestamp_utc.to_rfc3339(),
+            self.hashes_tried,
+            self.blocks_submitted,
+            self.blocks_accepted,
+            self.hashrate_hps,
+            self.uptime_secs
+        )
+    }
+}
+
+impl FromStr for CsvRow {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let fields: Vec<&str> = line.trim().split(',').collect();
+        let [timestamp, hashes, submitted, accepted, hashrate, uptime] = fields[..] else {
+            return Err(anyhow::anyhow!(
+                "Expected 6 fields, found {}: {}",
+                fields.len(),
+                line
+            ));
+        };
+        Ok(Self {
+            timestamp_utc: DateTime::parse_from_rfc3339(timestamp)
+                .with_context(|| format!("Bad timestamp_utc: {}", timestamp))?
+                .with_timezone(&Utc),
+            hashes_tried: hashes.parse().context("Bad hashes_tried")?,
+            blocks_submitted: submitted.parse().context("Bad blocks_submitted")?,
+            blocks_accepted: accepted.parse().context("Bad blocks_accepted")?,
+            hashrate_hps: hashrate.parse().context("Bad hashrate_hps")?,
+            uptime_secs: uptime.parse().context("Bad uptime_secs")?,
+        })
+    }
+}
+
+/// Write every recorded stats snapshot of the session to `path` as CSV
+pub fn export_session_csv(
+    path: &Path,
+    metrics: &CpuMinerMetrics,
+    session: &SessionStats,
+) -> Result<()> {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+    for snapshot in metrics.snapshots.lock().iter() {
+        let row = CsvRow {
+            timestamp_utc: snapshot.timestamp,
+            hashes_tried: snapshot.hashes_tried,
+            blocks_submitted: snapshot.blocks_submitted,
+            blocks_accepted: snapshot.blocks_accepted,
+            hashrate_hps: snapshot.hashrate_hps,
+            uptime_secs: snapshot.at.duration_since(session.start_time).as_secs(),
+        };
+        out.push_str(&row.to_string());
+        out.push('\n');
+    }
+    std::fs::write(path, out).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn csv_row_round_trips_through_display_and_from_str() {
+        let row = CsvRow {
+            timestamp_utc: Utc.with_ymd_and_hms(2024, 5, 17, 8, 30, 15).unwrap(),
+            hashes_tried: 1_234_567_890,
+            blocks_submitted: 42,
+            blocks_accepted: 40,
+            hashrate_hps: 987_654.25,
+            uptime_secs: 3_600,
+        };
+        let parsed: CsvRow = row.to_string().parse().unwrap();
+        assert_eq!(parsed, row);
+    }
+
+    #[test]
+    fn csv_row_rejects_wrong_field_count() {
+        assert!("2024-05-17T08:30:15+00:00,1,2,3".parse::<CsvRow>().is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use serde::Serialize;
+
+/// A logical CPU the miner can pin a thread to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CpuId {
+    /// Logical CPU index, as accepted by `CpuMinerConfig::cpu_affinity`
+    pub id: usize,
+    /// Physical core this CPU belongs to, if the topology can be read
+    pub core: Option<usize>,
+}
+
+/// Logical CPUs available to this process, sorted by id
+pub fn detect_cpus() -> Vec<CpuId> {
+    let mut ids: Vec<usize> = match core_affinity::get_core_ids() {
+        Some(ids) => ids.into_iter().map(|core| core.id).collect(),
+        None => (0..std::thread::available_parallelism().map_or(1, |n| n.get())).collect(),
+    };
+    ids.sort_unstable();
+    ids.into_iter()
+        .map(|id| CpuId {
+            id,
+            core: physical_core_id(id),
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn physical_core_id(cpu: usize) -> Option<usize> {
+    std::fs::read_to_string(format!(
+        "/sys/devices/system/cpu/cpu{}/topology/core_id",
+        cpu
+    ))
+    .ok()?
+    .trim()
+    .parse()
+    .ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn physical_core_id(_cpu: usize) -> Option<usize> {
+    None
+}
+
+/// Logical CPUs that are the first hardware thread of their physical core, sorted.
+/// HyperThreading siblings share execution units, so mining on only these gives
+/// the best throughput per thread. `None` if the topology can't be read.
+#[cfg(target_os = "linux")]
+pub fn primary_logical_cpus() -> Option<Vec<usize>> {
+    let mut primaries = std::collections::BTreeSet::new();
+    for entry in std::fs::read_dir("/sys/devices/system/cpu").ok()? {
+        let entry = entry.ok()?;
+        let name = entry.file_name();
+        let Some(index) = name.to_str().and_then(|n| n.strip_prefix("cpu")) else {
+            continue;
+        };
+        if index.parse::<usize>().is_err() {
+            // cpufreq, cpuidle, ...
+            continue;
+        }
+        let siblings =
+            std::fs::read_to_string(entry.path().join("topology/thread_siblings_list")).ok()?;
+        primaries.insert(*parse_cpu_list(&siblings)?.iter().min()?);
+    }
+    (!primaries.is_empty()).then(|| primaries.into_iter().collect())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn primary_logical_cpus() -> Option<Vec<usize>> {
+    None
+}
+
+/// Parse a kernel CPU list such as `0,4` or `0-1,8-9`
+#[cfg(target_os = "linux")]
+fn parse_cpu_list(list: &str) -> Option<Vec<usize>> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => cpus.extend(start.parse::<usize>().ok()?..=end.parse().ok()?),
+            None => cpus.push(part.parse().ok()?),
+        }
+    }
+    Some(cpus)
+}
+
+/// Pin the calling thread to logical CPU `cpu`. Returns false if the OS refused.
+pub fn pin_current_thread(cpu: usize) -> bool {
+    core_affinity::set_for_current(core_affinity::CoreId { id: cpu })
+}
@@ -1,5 +1,7 @@
+use crate::tls::{self, TlsTunnel};
 use anyhow::{Context, Result};
-use kaspa_addresses::Address;
+use kaspa_addresses::{Address, AddressError, Prefix, Version};
+use kaspa_consensus_core::network::NetworkId;
 use kaspa_grpc_client::GrpcClient;
 use kaspa_rpc_core::{
     api::rpc::RpcApi, notify::mode::NotificationMode, GetBlockTemplateRequest, RpcRawBlock,
@@ -8,46 +10,276 @@ use kaspa_rpc_core::{
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
+
+/// Snapshot of the node's DAG state used by the network info section
+#[derive(Debug, Clone)]
+pub struct DagInfo {
+    pub virtual_daa_score: u64,
+    pub blue_score: u64,
+    pub difficulty: f64,
+    /// Timestamp (ms) of the current sink block header
+    pub sink_timestamp: u64,
+    /// Blocks per second, estimated against the previous snapshot
+    pub bps: Option<f64>,
+}
+
+impl DagInfo {
+    /// Estimated network hashrate in H/s. Kaspa difficulty is `2^255 / target`,
+    /// so a block takes `2 * difficulty` hashes on average.
+    pub fn network_hashrate(&self) -> Option<f64> {
+        self.bps.map(|bps| 2.0 * self.difficulty * bps)
+    }
+}
+
+/// Circulating and maximum KAS supply, in sompi
+#[derive(Debug, Clone)]
+pub struct CoinSupply {
+    pub circulating_sompi: u64,
+    pub max_sompi: u64,
+}
+
+impl CoinSupply {
+    /// Share of the maximum supply mined so far, in percent
+    pub fn mining_progress(&self) -> f64 {
+        if self.max_sompi == 0 {
+            return 0.0;
+        }
+        self.circulating_sompi as f64 / self.max_sompi as f64 * 100.0
+    }
+}
+
+/// Structured result of validating a Kaspa address
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatedAddress {
+    pub is_valid: bool,
+    /// Network prefix, e.g. `kaspa` or `kaspatest`. Set even if the rest fails to decode.
+    pub network: String,
+    /// Script type and payload size, e.g. `Schnorr P2PK (32-byte payload)`
+    pub address_type: String,
+    pub error: Option<String>,
+}
+
+/// Validate a Kaspa address and extract its network prefix, version and payload length
+pub fn validate_address(address: &str) -> ValidatedAddress {
+    let mut info = ValidatedAddress {
+        is_valid: false,
+        network: String::new(),
+        address_type: String::new(),
+        error: None,
+    };
+
+    let address = address.trim();
+    if address.is_empty() {
+        info.error = Some("Address is empty".to_string());
+        return info;
+    }
+
+    // Check the prefix separately so an unknown network is reported even if
+    // the rest of the address would fail to decode
+    match address.split_once(':') {
+        Some((prefix, _)) => match Prefix::try_from(prefix) {
+            Ok(prefix) => info.network = prefix.to_string(),
+            Err(_) => {
+                info.error = Some(format!("Unknown network prefix \"{}\"", prefix));
+                return info;
+            }
+        },
+        None => {
+            info.error = Some("Missing network prefix (e.g. kaspatest:)".to_string());
+            return info;
+        }
+    }
+
+    match Address::try_from(address) {
+        Ok(parsed) => {
+            info.is_valid = true;
+            info.network = parsed.prefix.to_string();
+            let kind = match parsed.version {
+                Version::PubKey => "Schnorr P2PK",
+                Version::PubKeyECDSA => "ECDSA P2PK",
+                Version::ScriptHash => "P2SH",
+            };
+            info.address_type = format!("{} ({}-byte payload)", kind, parsed.payload.len());
+        }
+        Err(AddressError::BadChecksum) => info.error = Some("Wrong checksum".to_string()),
+        Err(AddressError::BadPayload) => info.error = Some("Wrong length".to_string()),
+        Err(e) => info.error = Some(e.to_string()),
+    }
+
+    info
+}
+
+/// A peer connected to the node
+#[derive(Debug, Clone)]
+pub struct PeerInfo {
+    pub id: String,
+    pub address: String,
+    pub is_outbound: bool,
+    pub time_connected_secs: u64,
+}
+
+/// Largest gRPC message accepted from the node unless configured otherwise.
+/// Block templates on large DAGs can exceed the old 500 kB limit.
+pub const DEFAULT_GRPC_MAX_MESSAGE_BYTES: usize = 1024 * 1024;
+
+/// Options for how the client connects to the node
+#[derive(Debug, Clone)]
+pub struct ConnectOptions {
+    pub use_tls: bool,
+    /// Expected SHA-256 fingerprint of the node's DER-encoded certificate, as 64
+    /// hex characters with or without `:` separators
+    pub cert_fingerprint: Option<String>,
+    /// Give up after this many connection attempts; `None` retries forever
+    pub max_attempts: Option<u32>,
+    /// Largest gRPC message accepted from the node; templates above it fail to decode
+    pub grpc_max_message_bytes: usize,
+}
+
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        Self {
+            use_tls: false,
+            cert_fingerprint: None,
+            max_attempts: None,
+            grpc_max_message_bytes: DEFAULT_GRPC_MAX_MESSAGE_BYTES,
+        }
+    }
+}
+
+impl ConnectOptions {
+    /// Parse `cert_fingerprint` into raw bytes. `Ok(None)` when no fingerprint is set.
+    pub fn parsed_fingerprint(&self) -> Result<Option<[u8; 32]>> {
+        let Some(fingerprint) = self
+            .cert_fingerprint
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+        else {
+            return Ok(None);
+        };
+        parse_cert_fingerprint(fingerprint).map(Some)
+    }
+}
+
+/// Parse a SHA-256 certificate fingerprint: 64 hex characters, optionally
+/// separated into byte pairs by colons (`AB:CD:...`)
+pub fn parse_cert_fingerprint(fingerprint: &str) -> Result<[u8; 32]> {
+    let hex: String = fingerprint.chars().filter(|c| *c != ':').collect();
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow::anyhow!(
+            "Certificate fingerprint must be 64 hex characters (colons optional)"
+        ));
+    }
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)?;
+    }
+    Ok(bytes)
+}
 
 /// Simplified Kaspa API client for standalone miner
 pub struct KaspaApi {
     client: Arc<GrpcClient>,
+    address: String,
+    /// Network the node reported right after connecting, if it answered
+    network: Option<NetworkId>,
+    server_version: Option<String>,
+    /// Carries the connection when `ConnectOptions::use_tls` is set
+    _tls_tunnel: Option<TlsTunnel>,
 }
 
 impl KaspaApi {
     /// Create a new Kaspa API client
     pub async fn new(address: String) -> Result<Arc<Self>> {
-        // Add grpc:// prefix if not present
-        let grpc_address = if address.starts_with("grpc://") {
-            address.clone()
+        Self::connect(&[address], &ConnectOptions::default()).await
+    }
+
+    /// Create a new Kaspa API client with explicit connection options. `addresses`
+    /// are tried in order; after each full pass the backoff doubles (up to 5s)
+    /// and the list is tried again from the start, until `options.max_attempts` is used up.
+    pub async fn connect(addresses: &[String], options: &ConnectOptions) -> Result<Arc<Self>> {
+        let fingerprint = options.parsed_fingerprint()?;
+        // A pinned fingerprint only makes sense over TLS
+        let tls_connector = if options.use_tls || fingerprint.is_some() {
+            Some(tls::connector(fingerprint)?)
         } else {
-            format!("grpc://{}", address)
+            None
         };
 
-        debug!("Connecting to Kaspa node at {}", grpc_address);
+        let addresses: Vec<&str> = addresses
+            .iter()
+            .map(|a| a.trim())
+            .filter(|a| !a.is_empty())
+            .collect();
+        if addresses.is_empty() {
+            return Err(anyhow::anyhow!("No node address given"));
+        }
+
+        // kaspa-grpc-client always builds its tonic client with gzip send/accept
+        // compression, and servers without gzip support fall back to identity
+        // encoding. There is no channel option to change that, so it isn't configurable.
+        info!("RPC compression: gzip (negotiated with the node)");
 
+        debug!(
+            "Max gRPC message size: {} bytes",
+            options.grpc_max_message_bytes
+        );
         let mut attempt = 0;
         let mut backoff_ms = 250u64;
 
-        let client = loop {
+        let (client, address, tls_tunnel) = loop {
+            let address = addresses[attempt % addresses.len()];
             attempt += 1;
+            let tls_tunnel = match &tls_connector {
+                Some(connector) => Some(
+                    TlsTunnel::start(address.trim_start_matches("grpc://"), connector.clone())
+                        .await?,
+                ),
+                None => None,
+            };
+            // Add grpc:// prefix if not present
+            let grpc_address = match &tls_tunnel {
+                Some(tunnel) => format!("grpc://{}", tunnel.local_addr()),
+                None if address.starts_with("grpc://") => address.to_string(),
+                None => format!("grpc://{}", address),
+            };
+            debug!("Connecting to Kaspa node at {}", grpc_address);
             let connect_fut = GrpcClient::connect_with_args(
                 NotificationMode::Direct,
-                grpc_address.clone(),
+                grpc_address,
                 None,
                 true,
                 None,
                 false,
-                Some(500_000),
+                Some(options.grpc_max_message_bytes),
                 Default::default(),
             );
 
             match connect_fut.await {
-                Ok(client) => break Arc::new(client),
+                Ok(client) => break (Arc::new(client), address.to_string(), tls_tunnel),
+                Err(e)
+                    if options
+                        .max_attempts
+                        .is_some_and(|max| attempt >= max as usize) =>
+                {
+                    return Err(anyhow::anyhow!(
+                        "Failed to connect to kaspa node {} after {} attempts: {}",
+                        address,
+                        attempt,
+                        e
+                    ));
+                }
+                Err(e) if attempt % addresses.len() != 0 => {
+                    warn!(
+                        "Failed to connect to kaspa node {} (attempt {}): {}, trying next address",
+                        address, attempt, e
+                    );
+                }
                 Err(e) => {
                     warn!(
-                        "Failed to connect to kaspa node (attempt {}): {}, retrying in {:.2}s",
+                        "Failed to connect to kaspa node {} (attempt {}): {}, retrying in {:.2}s",
+                        address,
                         attempt,
                         e,
                         Duration::from_millis(backoff_ms).as_secs_f64()
@@ -62,9 +294,65 @@ impl KaspaApi {
         // Start the client
         client.start(None).await;
 
-        debug!("Connected to Kaspa node successfully");
+        debug!("Connected to Kaspa node {} successfully", address);
+
+        // GetInfo doesn't say which network the node is on; the DAG info does
+        let network = match client.get_block_dag_info().await {
+            Ok(dag) => Some(dag.network),
+            Err(e) => {
+                warn!("Failed to get node network: {}", e);
+                None
+            }
+        };
+        let server_version = match client.get_info().await {
+            Ok(info) => Some(info.server_version),
+            Err(e) => {
+                warn!("Failed to get node version: {}", e);
+                None
+            }
+        };
+
+        Ok(Arc::new(Self {
+            client,
+            address,
+            network,
+            server_version,
+            _tls_tunnel: tls_tunnel,
+        }))
+    }
+
+    /// Node address this client is connected to
+    pub fn active_address(&self) -> &str {
+        &self.address
+    }
+
+    /// Network name the node reported, e.g. `testnet-10`
+    pub fn network_name(&self) -> Option<String> {
+        self.network.map(|network| network.to_string())
+    }
 
-        Ok(Arc::new(Self { client }))
+    /// Node software version, e.g. `1.0.0`
+    pub fn server_version(&self) -> Option<&str> {
+        self.server_version.as_deref()
+    }
+
+    /// Address prefix of the node's network, or `None` if it didn't say
+    pub fn network_prefix(&self) -> Option<Prefix> {
+        self.network
+            .map(|network| Prefix::from(network.network_type()))
+    }
+
+    /// Refuse addresses from another network than the node's, whose rewards
+    /// could never be spent. Passes if the node's network is unknown.
+    pub fn check_address_network(&self, mining_address: &str) -> Result<()> {
+        let address = Address::try_from(mining_address)
+            .map_err(|e| anyhow::anyhow!("Invalid mining address {}: {}", mining_address, e))?;
+        match self.network_prefix() {
+            Some(prefix) if prefix != address.prefix => Err(anyhow::anyhow!(
+                "Address prefix does not match node network"
+            )),
+            _ => Ok(()),
+        }
     }
 
     /// Wait for node to sync
@@ -86,19 +374,17 @@ impl KaspaApi {
         }
     }
 
-    /// Get block template for mining
+    /// Get block template for mining, with `extra_data` in the coinbase
     pub async fn get_block_template_rpc(
         &self,
         mining_address: &str,
+        extra_data: &[u8],
     ) -> Result<(kaspa_consensus_core::block::Block, RpcRawBlock)> {
         // Parse address string to Address type
         let address = Address::try_from(mining_address)
             .map_err(|e| anyhow::anyhow!("Invalid mining address {}: {}", mining_address, e))?;
 
-        // Convert extra_data string to Vec<u8>
-        let extra_data = b"internal".to_vec();
-
-        let request = GetBlockTemplateRequest::new(address, extra_data);
+        let request = GetBlockTemplateRequest::new(address, extra_data.to_vec());
 
         let response = self
             .client
@@ -113,6 +399,101 @@ impl KaspaApi {
         Ok((block, response.block))
     }
 
+    /// Fetch the current DAG state. Pass the previous snapshot to estimate BPS
+    /// from the DAA score and sink timestamp progress between the two.
+    pub async fn get_dag_info(&self, previous: Option<&DagInfo>) -> Result<DagInfo> {
+        let dag = self
+            .client
+            .get_block_dag_info()
+            .await
+            .context("Failed to get block DAG info")?;
+        let sink = self
+            .client
+            .get_block(dag.sink, false)
+            .await
+            .context("Failed to get sink block")?;
+
+        let bps = previous.and_then(|prev| {
+            let blocks = dag.virtual_daa_score.checked_sub(prev.virtual_daa_score)?;
+            let elapsed_ms = sink.header.timestamp.checked_sub(prev.sink_timestamp)?;
+            if elapsed_ms == 0 {
+                return prev.bps;
+            }
+            Some(blocks as f64 * 1000.0 / elapsed_ms as f64)
+        });
+
+        Ok(DagInfo {
+            virtual_daa_score: dag.virtual_daa_score,
+            blue_score: sink.header.blue_score,
+            difficulty: dag.difficulty,
+            sink_timestamp: sink.header.timestamp,
+            bps,
+        })
+    }
+
+    /// Fetch the circulating and maximum coin supply
+    pub async fn get_coin_supply(&self) -> Result<CoinSupply> {
+        let response = self
+            .client
+            .get_coin_supply()
+            .await
+            .context("Failed to get coin supply")?;
+        Ok(CoinSupply {
+            circulating_sompi: response.circulating_sompi,
+            max_sompi: response.max_sompi,
+        })
+    }
+
+    /// Current per-block coinbase reward (subsidy plus fees) in sompi, taken from
+    /// the sink block's coinbase. The coinbase pays every blue block in the sink's
+    /// merge set, so its total is divided by that count.
+    pub async fn get_block_reward(&self) -> Result<Option<u64>> {
+        let dag = self
+            .client
+            .get_block_dag_info()
+            .await
+            .context("Failed to get block DAG info")?;
+        let sink = self
+            .client
+            .get_block(dag.sink, true)
+            .await
+            .context("Failed to get sink block")?;
+
+        let Some(coinbase) = sink.transactions.first() else {
+            return Ok(None);
+        };
+        let merged_blues = sink
+            .verbose_data
+            .as_ref()
+            .map(|v| v.merge_set_blues_hashes.len())
+            .unwrap_or(0);
+        if merged_blues == 0 {
+            return Ok(None);
+        }
+        let total: u64 = coinbase.outputs.iter().map(|o| o.value).sum();
+        Ok(Some(total / merged_blues as u64))
+    }
+
+    /// Peers currently connected to the node
+    pub async fn get_peers(&self) -> Result<Vec<PeerInfo>> {
+        let response = self
+            .client
+            .get_connected_peer_info()
+            .await
+            .context("Failed to get connected peer info")?;
+        Ok(response
+            .peer_info
+            .into_iter()
+            .map(|peer| PeerInfo {
+                id: peer.id.to_string(),
+                address: peer.address.to_string(),
+                is_outbound: peer.is_outbound,
+                // The node reports connection time in milliseconds
+                time_connected_secs: peer.time_connected / 1000,
+            })
+            .collect())
+    }
+
     /// Submit a mined block
     pub async fn submit_rpc_block(&self, rpc_block: RpcRawBlock) -> Result<SubmitBlockResponse> {
         let request = SubmitBlockRequest::new(rpc_block, false);
@@ -0,0 +1,234 @@
+use crate::config::profiles::Profiles;
+use crate::miner::{start_cpu_miner, CpuMinerConfig, SUBMIT_QUEUE_DEPTH};
+use crate::prometheus;
+use crate::ui::format;
+use crate::{ConnectOptions, KaspaApi, DEFAULT_GRPC_MAX_MESSAGE_BYTES};
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
+use tokio::sync::watch;
+
+/// How often headless mode prints metrics
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Command line options. Without `--headless` only the GUI is started and the
+/// mining options are ignored.
+#[derive(Debug, Clone, Parser)]
+#[command(name = "kaspa-cpu-miner", version, about = "Kaspa CPU miner")]
+pub struct CliArgs {
+    /// Mine without a window, printing metrics to stdout
+    #[arg(long)]
+    pub headless: bool,
+    /// Node gRPC address; repeat for failover, tried in order
+    /// [default: 127.0.0.1:16210]
+    #[arg(long = "node-address")]
+    pub node_addresses: Vec<String>,
+    /// Load settings from this profile in profiles.json. Options given on
+    /// the command line take precedence over it.
+    #[arg(long)]
+    pub profile: Option<String>,
+    /// Address block rewards are paid to (required with --headless); repeat
+    /// to use the next one after every accepted block
+    #[arg(long = "mining-address")]
+    pub mining_addresses: Vec<String>,
+    /// Mining threads [default: one per physical core]
+    #[arg(long)]
+    pub threads: Option<usize>,
+    /// Pause between hash batches, in milliseconds
+    #[arg(long)]
+    pub throttle_ms: Option<u64>,
+    /// Coinbase extra data, at most 64 bytes
+    #[arg(long)]
+    pub extra_data: Option<String>,
+    /// How often to poll the node for a new block template, in milliseconds
+    #[arg(long, default_value_t = 50)]
+    pub poll_interval_ms: u64,
+    /// Mine normally but log found blocks instead of submitting them, to check
+    /// connectivity, the address and thread scaling
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Pin mining threads to these logical CPUs, one each in turn (e.g. 0,2,4,6)
+    #[arg(long, value_delimiter = ',')]
+    pub cpu_affinity: Vec<usize>,
+    /// Found blocks queued for submission before further ones are dropped
+    #[arg(long, default_value_t = SUBMIT_QUEUE_DEPTH)]
+    pub submit_queue_depth: usize,
+    /// Stop after this many seconds instead of running until Ctrl-C
+    #[arg(long)]
+    pub run_for_secs: Option<u64>,
+    /// Give up connecting after this many attempts [default: retry forever]
+    #[arg(long)]
+    pub max_reconnect_attempts: Option<u32>,
+    /// Largest gRPC message accepted from the node; raise it if block
+    /// templates fail to convert
+    #[arg(long, default_value_t = DEFAULT_GRPC_MAX_MESSAGE_BYTES)]
+    pub grpc_max_message_bytes: usize,
+    /// Also log to this file, rotated daily (or set KASPA_MINER_LOG_FILE)
+    #[arg(long)]
+    pub log_file: Option<PathBuf>,
+    /// Serve Prometheus metrics at http://0.0.0.0:PORT/metrics
+    #[arg(long)]
+    pub metrics_port: Option<u16>,
+}
+
+/// Node used when neither `--node-address` nor the profile names one
+const DEFAULT_NODE_ADDRESS: &str = "127.0.0.1:16210";
+
+impl CliArgs {
+    /// Fill in options not given on the command line from `--profile`
+    fn apply_profile(&mut self) -> Result<()> {
+        if let Some(name) = self.profile.as_deref() {
+            let profiles = Profiles::load(&Profiles::default_path()?)?;
+            let profile = profiles
+                .get(name)
+                .with_context(|| format!("No profile named \"{}\"", name))?
+                .config
+                .clone();
+            if self.node_addresses.is_empty() {
+                self.node_addresses = profile.node_addresses;
+            }
+            if self.mining_addresses.is_empty() && !profile.mining_address.is_empty() {
+                self.mining_addresses.push(profile.mining_address);
+            }
+            self.threads.get_or_insert(profile.threads);
+            if self.throttle_ms.is_none() {
+                self.throttle_ms = profile.throttle_ms;
+            }
+        }
+        if self.node_addresses.is_empty() {
+            self.node_addresses.push(DEFAULT_NODE_ADDRESS.to_string());
+        }
+        Ok(())
+    }
+
+    fn mining_config(&self) -> Result<CpuMinerConfig> {
+        if self.mining_addresses.iter().all(|a| a.trim().is_empty()) {
+            return Err(anyhow::anyhow!(
+                "--mining-address is required in headless mode"
+            ));
+        }
+        let mut builder = CpuMinerConfig::builder()
+            .mining_addresses(self.mining_addresses.clone())
+            .poll_interval(Duration::from_millis(self.poll_interval_ms))
+            .dry_run(self.dry_run)
+            .submit_queue_depth(self.submit_queue_depth)
+            .cpu_affinity(self.cpu_affinity.clone());
+        if let Some(threads) = self.threads {
+            builder = builder.threads(threads);
+        }
+        if let Some(ms) = self.throttle_ms {
+            builder = builder.throttle(Duration::from_millis(ms));
+        }
+        if let Some(text) = self.extra_data.as_deref().map(str::trim) {
+            if !text.is_empty() {
+                builder = builder.extra_data(text.as_bytes().to_vec());
+            }
+        }
+        builder.build()
+    }
+}
+
+/// Connect, mine until Ctrl-C (or `--run-for-secs`), then shut down
+pub fn run_headless(mut args: CliArgs) -> Result<()> {
+    args.apply_profile()?;
+    let config = args.mining_config()?;
+    let runtime = tokio::runtime::Runtime::new().context("Failed to create runtime")?;
+
+    let (stop_tx, mut stop_rx) = watch::channel(false);
+    ctrlc::set_handler(move || {
+        let _ = stop_tx.send(true);
+    })
+    .context("Failed to install Ctrl-C handler")?;
+
+    runtime.block_on(async move {
+        let options = ConnectOptions {
+            max_attempts: args.max_reconnect_attempts,
+            grpc_max_message_bytes: args.grpc_max_message_bytes,
+            ..ConnectOptions::default()
+        };
+        let api = KaspaApi::connect(&args.node_addresses, &options).await?;
+        println!("Connected to {}, waiting for sync...", api.active_address());
+        api.wait_for_sync().await?;
+
+        let handle = start_cpu_miner(Arc::clone(&api), config, &Handle::current())?;
+        let metrics = Arc::clone(handle.metrics());
+        println!(
+            "Mining with {} threads. Press Ctrl-C to stop.",
+            handle.thread_count()
+        );
+        if args.dry_run {
+            println!("Dry run: found blocks are logged, not submitted");
+        }
+
+        let started = Instant::now();
+        if let Some(port) = args.metrics_port {
+            prometheus::serve(port, Arc::clone(&metrics), started)?;
+        }
+        let run_for = async {
+            match args.run_for_secs {
+                Some(secs) => tokio::time::sleep(Duration::from_secs(secs)).await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::pin!(run_for);
+        let mut ticker = tokio::time::interval(REPORT_INTERVAL);
+        ticker.tick().await;
+        let mut last_hashes = 0;
+        loop {
+            tokio::select! {
+                _ = stop_rx.wait_for(|stop| *stop) => {
+                    println!("Interrupted, stopping");
+                    break;
+                }
+                _ = &mut run_for => {
+                    println!("Run time reached, stopping");
+                    break;
+                }
+                _ = ticker.tick() => {
+                    let hashes = metrics.hashes_tried.load(Ordering::Relaxed);
+                    let rate =
+                        hashes.saturating_sub(last_hashes) as f64 / REPORT_INTERVAL.as_secs_f64();
+                    last_hashes = hashes;
+                    println!(
+                        "[{}s] hashrate {} | hashes {} | submitted {} | accepted {}",
+                        started.elapsed().as_secs(),
+                        format::hashrate(rate),
+                        format::thousands(hashes),
+                        metrics.blocks_submitted.load(Ordering::Relaxed),
+                        metrics.blocks_accepted.load(Ordering::Relaxed),
+                    );
+                }
+            }
+        }
+
+        handle.stop().await;
+        println!(
+            "Stopped after {}s: {} hashes, {} blocks accepted",
+            started.elapsed().as_secs(),
+            format::thousands(metrics.hashes_tried.load(Ordering::Relaxed)),
+            metrics.blocks_accepted.load(Ordering::Relaxed),
+        );
+        let dropped = metrics.blocks_dropped_on_shutdown.load(Ordering::Relaxed);
+        if dropped > 0 {
+            println!("{} blocks could not be submitted while stopping", dropped);
+        }
+        let dropped = metrics.blocks_dropped_backpressure.load(Ordering::Relaxed);
+        if dropped > 0 {
+            println!(
+                "{} blocks dropped because the submit queue was full (see --submit-queue-depth)",
+                dropped
+            );
+        }
+        if args.dry_run {
+            println!(
+                "Dry run: {} blocks found and not submitted",
+                metrics.blocks_found_not_submitted.load(Ordering::Relaxed)
+            );
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,250 @@
+pub mod address_book;
+pub mod profiles;
+
+use crate::AppState;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Current on-disk format of `MinerProfile`
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// User-settable miner settings, persisted between runs. Missing fields fall
+/// back to their defaults so older files keep loading.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MinerProfile {
+    /// Missing in files written before the field existed, which are schema 0
+    #[serde(default)]
+    pub schema_version: u32,
+    /// Node addresses in failover order
+    pub node_addresses: Vec<String>,
+    pub mining_address: String,
+    pub threads: usize,
+    pub throttle_ms: Option<u64>,
+    pub cpu_limit_percent: Option<u8>,
+    pub thermal_throttle: bool,
+    pub max_temp_c: u8,
+    /// GUI body text size (see `FontSizeConfig::base_size`)
+    pub font_size_base: f32,
+}
+
+impl Default for MinerProfile {
+    fn default() -> Self {
+        Self::from_state(&AppState::default())
+    }
+}
+
+impl MinerProfile {
+    pub fn from_state(state: &AppState) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            node_addresses: state.node_addresses.clone(),
+            mining_address: state.mining_address.clone(),
+            threads: state.threads,
+            throttle_ms: state.throttle_ms,
+            cpu_limit_percent: state.cpu_limit_percent,
+            thermal_throttle: state.thermal_throttle,
+            max_temp_c: state.max_temp_c,
+            font_size_base: state.font_size.base_size,
+        }
+    }
+
+    /// Copy the saved settings into `state`. Takes effect on the next start.
+    pub fn apply_to(&self, state: &mut AppState) {
+        if !self.node_addresses.is_empty() {
+            state.node_addresses = self.node_addresses.clone();
+        }
+        state.mining_address = self.mining_address.clone();
+        state.threads = self.threads.max(1);
+        state.threads_auto_set = false;
+        state.throttle_ms = self.throttle_ms;
+        state.cpu_limit_percent = self.cpu_limit_percent;
+        state.thermal_throttle = self.thermal_throttle;
+        state.max_temp_c = self.max_temp_c;
+        state.font_size.set_base_size(self.font_size_base);
+    }
+
+    /// `<config dir>/rkstratum-cpu-miner/config.toml`
+    pub fn default_path() -> Result<PathBuf> {
+        dirs::config_dir()
+            .map(|dir| dir.join("rkstratum-cpu-miner").join("config.toml"))
+            .context("No configuration directory on this system")
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::load_with(path, migrate)
+    }
+
+    /// `load` with the migration step passed in, so a failing one can be tested
+    fn load_with(path: &Path, migrate: impl FnOnce(Self, u32) -> Result<Self>) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let profile: Self =
+            toml::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))?;
+        let from_version = profile.schema_version;
+        if from_version > SCHEMA_VERSION {
+            return Err(anyhow::anyhow!(
+                "{} was written by a newer version (schema {}, supported {})",
+                path.display(),
+                from_version,
+                SCHEMA_VERSION
+            ));
+        }
+        if from_version == SCHEMA_VERSION {
+            return Ok(profile);
+        }
+
+        match migrate(profile, from_version) {
+            Ok(migrated) => {
+                migrated.save(path)?;
+                tracing::info!(
+                    "Migrated {} from schema {} to {}",
+                    path.display(),
+                    from_version,
+                    SCHEMA_VERSION
+                );
+                Ok(migrated)
+            }
+            Err(e) => {
+                // Keep the original around: the next save overwrites it
+                let backup = backup_path(path);
+                std::fs::copy(path, &backup)
+                    .with_context(|| format!("Failed to back up {}", path.display()))?;
+                Err(e.context(format!(
+                    "Failed to migrate {} (original saved as {})",
+                    path.display(),
+                    backup.display()
+                )))
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let text = toml::to_string_pretty(self).context("Failed to serialize profile")?;
+        std::fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Upgrade a profile read with schema `from_version` to `SCHEMA_VERSION`, one
+/// version at a time
+pub fn migrate(old: MinerProfile, from_version: u32) -> Result<MinerProfile> {
+    let mut profile = old;
+    for version in from_version..SCHEMA_VERSION {
+        profile = match version {
+            0 => migrate_v0_to_v1(profile),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "No migration from config schema {}",
+                    version
+                ))
+            }
+        };
+    }
+    Ok(profile)
+}
+
+/// Schema 0 is any file written before `schema_version` existed. It has the same
+/// fields as schema 1, so only the version changes.
+fn migrate_v0_to_v1(mut profile: MinerProfile) -> MinerProfile {
+    profile.schema_version = 1;
+    profile
+}
+
+/// `config.toml` -> `config.toml.bak`
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A file written before `schema_version` existed
+    const V0_CONFIG: &str = r#"
+node_addresses = ["10.0.0.2:16210"]
+mining_address = "kaspatest:qz0000000000000000000000000000000000000000000000000000000000"
+threads = 4
+throttle_ms = 10
+thermal_throttle = true
+max_temp_c = 80
+"#;
+
+    fn temp_config(name: &str, contents: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rkstratum-gui-config-{}-{}",
+            name,
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn missing_schema_version_is_v0() {
+        let profile: MinerProfile = toml::from_str(V0_CONFIG).unwrap();
+        assert_eq!(profile.schema_version, 0);
+        assert_eq!(profile.threads, 4);
+    }
+
+    #[test]
+    fn migrate_v0_to_v1_keeps_settings() {
+        let old: MinerProfile = toml::from_str(V0_CONFIG).unwrap();
+        let migrated = migrate(old.clone(), 0).unwrap();
+        assert_eq!(migrated.schema_version, 1);
+        assert_eq!(
+            migrated,
+            MinerProfile {
+                schema_version: 1,
+                ..old
+            }
+        );
+    }
+
+    #[test]
+    fn load_rewrites_migrated_file() {
+        let path = temp_config("migrate", V0_CONFIG);
+        let profile = MinerProfile::load(&path).unwrap();
+        assert_eq!(profile.schema_version, SCHEMA_VERSION);
+        assert_eq!(profile.max_temp_c, 80);
+
+        let saved: MinerProfile = toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved, profile);
+        assert!(!backup_path(&path).exists());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn failed_migration_backs_up_original() {
+        let path = temp_config("backup", V0_CONFIG);
+        let result = MinerProfile::load_with(&path, |_, from| {
+            Err(anyhow::anyhow!("No migration from config schema {}", from))
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&path)).unwrap(),
+            V0_CONFIG
+        );
+        // The original is left alone, not half-rewritten
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), V0_CONFIG);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn load_rejects_newer_schema() {
+        let path = temp_config(
+            "newer",
+            &format!("schema_version = {}\nthreads = 2\n", SCHEMA_VERSION + 1),
+        );
+        assert!(MinerProfile::load(&path).is_err());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
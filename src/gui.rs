@@ -0,0 +1,763 @@
+use crate::config::address_book::AddressBook;
+use crate::config::profiles::Profiles;
+use crate::config::MinerProfile;
+use crate::state::{ConnectedState, ConnectingState};
+use crate::stats::StatsHistory;
+use crate::tuning::SystemLoad;
+use crate::ui::{Components, FontSizeConfig, Sections, Theme};
+use crate::{
+    AppState, BlockHistory, CoinSupply, CpuMinerConfig, DagInfo, KaspaApi, LogBuffer, LogFilter,
+    MinerState, PeerInfo, StatusType, UiAction,
+};
+use egui::{Color32, RichText};
+use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
+
+/// Status update produced by a background task, applied on the next frame
+type PendingStatus = Arc<std::sync::Mutex<Option<(String, StatusType)>>>;
+/// Node data produced by the background network poller
+#[derive(Default)]
+struct NetworkSnapshot {
+    dag_info: Option<DagInfo>,
+    coin_supply: Option<CoinSupply>,
+    block_reward_sompi: Option<u64>,
+    peers: Option<Vec<PeerInfo>>,
+}
+type SharedNetworkSnapshot = Arc<std::sync::Mutex<NetworkSnapshot>>;
+
+/// How often the network info section is refreshed from the node
+const DAG_INFO_INTERVAL: Duration = Duration::from_secs(30);
+/// Submissions needed before a high rejection rate is reported
+const HIGH_REJECTION_MIN_SUBMITTED: u64 = 4;
+/// Fewer connected peers than this suggests the node may be isolated
+const MIN_HEALTHY_PEERS: usize = 3;
+/// Coin supply and block reward change slowly, so they are refreshed less often
+const COIN_SUPPLY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// How often overall CPU usage is sampled for the thread recommendation
+const CPU_LOAD_INTERVAL: Duration = Duration::from_secs(10);
+/// Minimum spacing between samples used for the local hashrate estimate
+const HASHRATE_SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+/// Hashrate samples kept for the sparkline (one per `SPARKLINE_INTERVAL`)
+const SPARKLINE_SAMPLES: usize = 60;
+const SPARKLINE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Poll network info until the client is dropped (disconnected)
+async fn poll_network_info(api: Weak<KaspaApi>, snapshot: SharedNetworkSnapshot) {
+    let mut previous: Option<DagInfo> = None;
+    let mut last_supply_fetch: Option<Instant> = None;
+    loop {
+        let Some(api) = api.upgrade() else {
+            break;
+        };
+        match api.get_dag_info(previous.as_ref()).await {
+            Ok(info) => {
+                if let Ok(mut slot) = snapshot.lock() {
+                    slot.dag_info = Some(info.clone());
+                }
+                previous = Some(info);
+            }
+            Err(e) => tracing::warn!("Failed to refresh DAG info: {}", e),
+        }
+        match api.get_peers().await {
+            Ok(peers) => {
+                if let Ok(mut slot) = snapshot.lock() {
+                    slot.peers = Some(peers);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to refresh peers: {}", e),
+        }
+        if last_supply_fetch.is_none_or(|at| at.elapsed() >= COIN_SUPPLY_INTERVAL) {
+            last_supply_fetch = Some(Instant::now());
+            let supply = api.get_coin_supply().await;
+            let reward = api.get_block_reward().await;
+            if let Err(e) = &supply {
+                tracing::warn!("Failed to refresh coin supply: {}", e);
+            }
+            if let Err(e) = &reward {
+                tracing::warn!("Failed to refresh block reward: {}", e);
+            }
+            if let Ok(mut slot) = snapshot.lock() {
+                if let Ok(supply) = supply {
+                    slot.coin_supply = Some(supply);
+                }
+                if let Ok(reward) = reward {
+                    slot.block_reward_sompi = reward;
+                }
+            }
+        }
+        // Don't keep the connection alive while sleeping
+        drop(api);
+        tokio::time::sleep(DAG_INFO_INTERVAL).await;
+    }
+    if let Ok(mut slot) = snapshot.lock() {
+        *slot = NetworkSnapshot::default();
+    }
+}
+
+/// Main egui application
+pub struct MinerApp {
+    pub state: AppState,
+    pub logs: Arc<tokio::sync::Mutex<LogBuffer>>,
+    pub show_logs: bool,
+    log_filter: LogFilter,
+    /// Runtime that executes async API calls triggered from the UI
+    runtime: Handle,
+    /// Runtime the miner's template and submit tasks run on (see `start_miner_runtime`)
+    miner_runtime: Handle,
+    num_cpus: usize,
+    pending_status: PendingStatus,
+    /// Written by the network poller, copied into `state` each frame
+    network: SharedNetworkSnapshot,
+    /// Last `(time, hashes_tried)` sample and the rate derived from it
+    hashrate_sample: Option<(Instant, u64)>,
+    local_hashrate: Option<f64>,
+    /// Recent `local_hashrate` values for the sparkline, oldest first
+    hashrate_history: VecDeque<f32>,
+    hashrate_history_at: Option<Instant>,
+    /// Samples of the current (or last) session, for Export Stats
+    stats_history: Option<StatsHistory>,
+    /// Accepted blocks, appended by the miner's submit task
+    block_history: BlockHistory,
+    /// Written by the CPU load sampler, copied into `state.cpu_load` each frame
+    cpu_load: Arc<std::sync::Mutex<Option<f32>>>,
+    /// Whether the low peer count warning is currently shown
+    low_peers_warned: bool,
+    connection_open: bool,
+    mining_open: bool,
+    status_open: bool,
+    stats_open: bool,
+    block_history_open: bool,
+    network_open: bool,
+    auto_threads_notice_dismissed: bool,
+    theme_applied: bool,
+    /// Font size last applied to the egui context; re-applied whenever
+    /// `state.font_size` differs (first frame, +/-, loaded profile)
+    applied_font_size: Option<FontSizeConfig>,
+}
+
+impl MinerApp {
+    pub fn new(runtime: Handle, miner_runtime: Handle) -> Self {
+        let cpu_load = Arc::new(std::sync::Mutex::new(None));
+        let cpu_load_writer = Arc::clone(&cpu_load);
+        runtime.spawn(async move {
+            let mut system_load = SystemLoad::new();
+            loop {
+                tokio::time::sleep(CPU_LOAD_INTERVAL).await;
+                let usage = system_load.cpu_usage();
+                if let Ok(mut slot) = cpu_load_writer.lock() {
+                    *slot = Some(usage);
+                }
+            }
+        });
+
+        let mut state = AppState::default();
+        match AddressBook::default_path().and_then(|path| AddressBook::load(&path)) {
+            Ok(book) => state.address_book = book,
+            Err(e) => tracing::warn!("Failed to load address book: {:#}", e),
+        }
+        match Profiles::default_path().and_then(|path| Profiles::load(&path)) {
+            Ok(profiles) => state.profiles = profiles,
+            Err(e) => tracing::warn!("Failed to load profiles: {:#}", e),
+        }
+        // Only the text size is restored at launch; the rest of the saved config is
+        // applied with "Load Profile"
+        if let Ok(path) = MinerProfile::default_path() {
+            if path.exists() {
+                match MinerProfile::load(&path) {
+                    Ok(profile) => state.font_size.set_base_size(profile.font_size_base),
+                    Err(e) => tracing::warn!("Failed to load config: {:#}", e),
+                }
+            }
+        }
+
+        Self {
+            state,
+            logs: Arc::new(tokio::sync::Mutex::new(LogBuffer::default())),
+            show_logs: false,
+            log_filter: LogFilter::default(),
+            runtime,
+            miner_runtime,
+            num_cpus: num_cpus::get().max(1),
+            pending_status: Arc::new(std::sync::Mutex::new(None)),
+            network: Arc::new(std::sync::Mutex::new(NetworkSnapshot::default())),
+            hashrate_sample: None,
+            local_hashrate: None,
+            hashrate_history: VecDeque::with_capacity(SPARKLINE_SAMPLES),
+            hashrate_history_at: None,
+            stats_history: None,
+            block_history: BlockHistory::default(),
+            cpu_load,
+            low_peers_warned: false,
+            connection_open: true,
+            mining_open: true,
+            status_open: true,
+            stats_open: true,
+            block_history_open: true,
+            network_open: true,
+            auto_threads_notice_dismissed: false,
+            theme_applied: false,
+            applied_font_size: None,
+        }
+    }
+
+    /// Ask where to save and write the session's stats as CSV
+    fn export_stats(&mut self) {
+        let Some(history) = self.stats_history.as_ref() else {
+            return;
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name("kaspa-miner-stats.csv")
+            .save_file()
+        else {
+            return;
+        };
+        match history.write_csv(&path) {
+            Ok(()) => self.set_status(
+                format!("Stats exported to {}", path.display()),
+                StatusType::Success,
+            ),
+            Err(e) => self.set_status(format!("Export failed: {:#}", e), StatusType::Error),
+        }
+    }
+
+    fn set_status(&mut self, message: impl Into<String>, status_type: StatusType) {
+        self.state.status_message = message.into();
+        self.state.status_type = status_type;
+    }
+
+    /// Pull results of background tasks into the UI state
+    fn sync_state(&mut self) {
+        let pending = self.pending_status.lock().ok().and_then(|mut p| p.take());
+        if let Some((message, status_type)) = pending {
+            self.set_status(message, status_type);
+        }
+
+        if let MinerState::Connecting(connecting) = &self.state.miner {
+            let result = connecting.result.lock().ok().and_then(|mut r| r.take());
+            match result {
+                Some(Ok(api)) => {
+                    self.runtime.spawn(poll_network_info(
+                        Arc::downgrade(&api),
+                        Arc::clone(&self.network),
+                    ));
+                    self.state.miner = MinerState::Connected(ConnectedState { api });
+                    self.set_status("Connected and synced", StatusType::Success);
+                }
+                Some(Err(e)) => {
+                    self.state.miner = MinerState::Idle;
+                    self.set_status(format!("Connection failed: {}", e), StatusType::Error);
+                }
+                None => {}
+            }
+        }
+
+        match self
+            .state
+            .miner
+            .metrics()
+            .filter(|_| self.state.miner.is_mining())
+        {
+            Some(m) => {
+                let now = Instant::now();
+                let hashes = m.hashes_tried.load(Ordering::Relaxed);
+                match self.hashrate_sample {
+                    Some((at, prev)) if now.duration_since(at) >= HASHRATE_SAMPLE_INTERVAL => {
+                        let secs = now.duration_since(at).as_secs_f64();
+                        let hashrate = hashes.saturating_sub(prev) as f64 / secs;
+                        self.local_hashrate = Some(hashrate);
+                        self.hashrate_sample = Some((now, hashes));
+                        if let Some(history) = self.stats_history.as_mut() {
+                            history.record(m, hashrate);
+                        }
+                    }
+                    Some(_) => {}
+                    None => {
+                        // First sample of a new session
+                        self.hashrate_sample = Some((now, hashes));
+                        self.stats_history = Some(StatsHistory::new());
+                        self.hashrate_history.clear();
+                        self.hashrate_history_at = None;
+                    }
+                }
+                if let Some(hashrate) = self.local_hashrate {
+                    let due = self
+                        .hashrate_history_at
+                        .is_none_or(|at| now.duration_since(at) >= SPARKLINE_INTERVAL);
+                    if due {
+                        if self.hashrate_history.len() == SPARKLINE_SAMPLES {
+                            self.hashrate_history.pop_front();
+                        }
+                        self.hashrate_history.push_back(hashrate as f32);
+                        self.hashrate_history_at = Some(now);
+                    }
+                }
+            }
+            None => {
+                self.hashrate_sample = None;
+                self.local_hashrate = None;
+            }
+        }
+        if let Ok(cpu_load) = self.cpu_load.lock() {
+            self.state.cpu_load = *cpu_load;
+        }
+        if let Ok(network) = self.network.lock() {
+            self.state.dag_info = network.dag_info.clone();
+            self.state.coin_supply = network.coin_supply.clone();
+            self.state.block_reward_sompi = network.block_reward_sompi;
+            self.state.peers = network.peers.clone();
+        }
+
+        // Warn once each time the peer count drops below the threshold
+        let low_peers = self
+            .state
+            .peers
+            .as_ref()
+            .is_some_and(|peers| peers.len() < MIN_HEALTHY_PEERS);
+        if low_peers && !self.low_peers_warned {
+            let count = self.state.peers.as_ref().map_or(0, Vec::len);
+            self.set_status(
+                format!("Node has only {} peers and may be isolated", count),
+                StatusType::Warning,
+            );
+        }
+        self.low_peers_warned = low_peers;
+    }
+
+    /// Apply a user action to the current state. Actions that don't apply to the
+    /// current state are ignored (the controls for them aren't shown).
+    fn handle_action(&mut self, action: UiAction) {
+        // Stopping the miner spawns a task on the current runtime. Entered through a
+        // clone so `self` stays free for the arms below.
+        let runtime = self.runtime.clone();
+        let _runtime_guard = runtime.enter();
+        let miner_runtime = self.miner_runtime.clone();
+        let state = std::mem::replace(&mut self.state.miner, MinerState::Idle);
+        self.state.miner = match (state, action) {
+            (MinerState::Idle, UiAction::Connect) => self.connect(),
+            (MinerState::Connected(_), UiAction::Disconnect) => {
+                self.set_status("Disconnected from node", StatusType::Info);
+                MinerState::Idle
+            }
+            (MinerState::Mining(mining), UiAction::Disconnect) => {
+                mining.on_stop();
+                self.set_status("Disconnected from node", StatusType::Info);
+                MinerState::Idle
+            }
+            (MinerState::Paused(_), UiAction::Disconnect) => {
+                self.set_status("Disconnected from node", StatusType::Info);
+                MinerState::Idle
+            }
+            (MinerState::Connected(connected), UiAction::Start) => match self.mining_config() {
+                Ok(config) => match connected.on_start(config, &miner_runtime) {
+                    Ok(mining) => {
+                        self.set_status("Mining started", StatusType::Success);
+                        MinerState::Mining(mining)
+                    }
+                    Err((connected, e)) => {
+                        self.set_status(format!("Failed to start miner: {}", e), StatusType::Error);
+                        MinerState::Connected(connected)
+                    }
+                },
+                Err(e) => {
+                    self.set_status(e.to_string(), StatusType::Error);
+                    MinerState::Connected(connected)
+                }
+            },
+            (MinerState::Mining(mining), UiAction::Stop) => {
+                self.set_status("Mining stopped", StatusType::Info);
+                MinerState::Connected(mining.on_stop())
+            }
+            (MinerState::Paused(paused), UiAction::Stop) => {
+                self.set_status("Mining stopped", StatusType::Info);
+                MinerState::Connected(paused.on_stop())
+            }
+            (MinerState::Mining(mining), UiAction::Pause) => {
+                self.set_status("Mining paused", StatusType::Info);
+                MinerState::Paused(mining.on_pause())
+            }
+            (MinerState::Paused(paused), UiAction::Resume) => {
+                match paused.on_resume(&miner_runtime) {
+                    Ok(mining) => {
+                        self.set_status("Mining resumed", StatusType::Success);
+                        MinerState::Mining(mining)
+                    }
+                    Err((paused, e)) => {
+                        self.set_status(
+                            format!("Failed to resume miner: {}", e),
+                            StatusType::Error,
+                        );
+                        MinerState::Paused(paused)
+                    }
+                }
+            }
+            (state, _) => state,
+        };
+    }
+
+    /// Start a background connect + sync, returning the state to switch to
+    fn connect(&mut self) -> MinerState {
+        let addresses: Vec<String> = self
+            .state
+            .node_addresses
+            .iter()
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect();
+        if addresses.is_empty() {
+            self.set_status("Please enter a node address", StatusType::Error);
+            return MinerState::Idle;
+        }
+        if let Err(e) = self.state.connect_options.parsed_fingerprint() {
+            self.set_status(e.to_string(), StatusType::Error);
+            return MinerState::Idle;
+        }
+        let options = self.state.connect_options.clone();
+
+        let address = addresses.join(", ");
+        self.set_status(format!("Connecting to {}...", address), StatusType::Info);
+        let result = Arc::new(std::sync::Mutex::new(None));
+        let result_writer = Arc::clone(&result);
+        // Spawn rather than block_on so the UI keeps rendering while the node syncs
+        self.runtime.spawn(async move {
+            let connected = async {
+                let api = KaspaApi::connect(&addresses, &options).await?;
+                api.wait_for_sync().await?;
+                Ok::<_, anyhow::Error>(api)
+            }
+            .await
+            .map_err(|e| e.to_string());
+            if let Ok(mut slot) = result_writer.lock() {
+                *slot = Some(connected);
+            }
+        });
+        MinerState::Connecting(ConnectingState { address, result })
+    }
+
+    fn mining_config(&self) -> anyhow::Result<CpuMinerConfig> {
+        let mut builder = CpuMinerConfig::builder()
+            .mining_address(self.state.mining_address.as_str())
+            .threads(self.state.threads)
+            .block_history(Arc::clone(&self.block_history));
+        if let Some(ms) = self.state.throttle_ms {
+            builder = builder.throttle(Duration::from_millis(ms));
+        }
+        if let Some(percent) = self.state.cpu_limit_percent {
+            builder = builder.cpu_limit_percent(percent);
+        }
+        if self.state.thermal_throttle {
+            builder = builder.max_temp_c(self.state.max_temp_c);
+        }
+        let extra_data = self.state.extra_data.trim();
+        if !extra_data.is_empty() {
+            builder = builder.extra_data(extra_data.as_bytes().to_vec());
+        }
+        builder.build()
+    }
+
+    fn top_bar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("⚡").size(20.0).color(Theme::PRIMARY_TEAL));
+            ui.label(
+                RichText::new("Kaspa CPU Miner")
+                    .size(24.0)
+                    .color(Theme::WHITE),
+            );
+            ui.label(RichText::new("♥").size(16.0).color(Theme::RED));
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let logs_label = if self.show_logs {
+                    "📎 Hide Logs"
+                } else {
+                    "📎 Show Logs"
+                };
+                if ui.button(logs_label).clicked() {
+                    self.show_logs = !self.show_logs;
+                }
+                ui.add_space(16.0);
+
+                // Right-to-left layout: "+" is added first so it ends up on the right
+                if ui.small_button("A+").on_hover_text("Larger text").clicked() {
+                    self.state.font_size.increase();
+                }
+                if ui
+                    .small_button("A-")
+                    .on_hover_text("Smaller text")
+                    .clicked()
+                {
+                    self.state.font_size.decrease();
+                }
+                ui.add_space(16.0);
+
+                let [(node_color, node_text), (mining_color, mining_text)] =
+                    self.state.miner.view().indicators();
+                Components::status_indicator(ui, node_color, node_text);
+                ui.add_space(16.0);
+                Components::status_indicator(ui, mining_color, mining_text);
+            });
+        });
+    }
+
+    fn logs_panel(&mut self, ui: &mut egui::Ui) {
+        // The log writer appends from runtime tasks; skip a frame rather than block
+        let Ok(mut logs) = self.logs.try_lock() else {
+            return;
+        };
+        if logs.max_lines() != self.state.log_max_lines {
+            logs.set_max_lines(self.state.log_max_lines);
+        }
+        let mut clear_clicked = false;
+        let mut copy_all_clicked = false;
+        Sections::log_panel(
+            ui,
+            &logs,
+            &mut self.log_filter,
+            &mut self.state.selected_line,
+            || clear_clicked = true,
+            || copy_all_clicked = true,
+        );
+        if copy_all_clicked {
+            let text = logs
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n");
+            ui.output_mut(|o| o.copied_text = text);
+        }
+        if clear_clicked {
+            logs.clear();
+            self.state.selected_line = None;
+        }
+    }
+
+    fn auto_threads_notice(&mut self, ui: &mut egui::Ui) {
+        if !self.state.threads_auto_set || self.auto_threads_notice_dismissed {
+            return;
+        }
+        Components::content_frame().show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "Thread count auto-set to {}. You can change this.",
+                        self.state.threads
+                    ))
+                    .color(Theme::ACCENT_TEAL),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("×").clicked() {
+                        self.auto_threads_notice_dismissed = true;
+                    }
+                });
+            });
+        });
+        ui.add_space(12.0);
+    }
+
+    /// Conditions worth showing regardless of which sections are open, as
+    /// `(id, message, color)`
+    fn active_alerts(&self) -> Vec<(String, String, Color32)> {
+        let mut alerts = Vec::new();
+        if let MinerState::Mining(mining) = &self.state.miner {
+            let throttle = self.state.throttle_ms.map(Duration::from_millis);
+            if mining.config.threads != self.state.threads.max(1)
+                || mining.config.throttle != throttle
+                || mining.config.cpu_limit_percent != self.state.cpu_limit_percent
+            {
+                alerts.push((
+                    format!(
+                        "config-restart:{}:{:?}:{:?}",
+                        self.state.threads, self.state.throttle_ms, self.state.cpu_limit_percent
+                    ),
+                    "Mining settings changed. Restart mining to apply them.".to_string(),
+                    Theme::ACCENT_TEAL,
+                ));
+            }
+        }
+        if let Some(metrics) = self.state.miner.metrics() {
+            let submitted = metrics.blocks_submitted.load(Ordering::Relaxed);
+            let accepted = metrics.blocks_accepted.load(Ordering::Relaxed);
+            let rejected = submitted.saturating_sub(accepted);
+            if submitted >= HIGH_REJECTION_MIN_SUBMITTED && rejected * 2 > submitted {
+                alerts.push((
+                    "high-rejection-rate".to_string(),
+                    format!(
+                        "High rejection rate: {} of {} submitted blocks were rejected",
+                        rejected, submitted
+                    ),
+                    Theme::RED,
+                ));
+            }
+        }
+        alerts
+    }
+
+    fn alert_banners(&mut self, ui: &mut egui::Ui) {
+        let alerts = self.active_alerts();
+        // Forget dismissals of conditions that have cleared so they show again
+        // the next time they occur
+        self.state
+            .dismissed_banners
+            .retain(|id| alerts.iter().any(|(active, _, _)| active == id));
+        for (id, text, color) in alerts {
+            if self.state.dismissed_banners.contains(&id) {
+                continue;
+            }
+            let dismissed = &mut self.state.dismissed_banners;
+            Components::alert_banner(ui, &text, color, || {
+                dismissed.insert(id);
+            });
+            ui.add_space(8.0);
+        }
+    }
+
+    fn main_sections(&mut self, ui: &mut egui::Ui) {
+        self.alert_banners(ui);
+        self.auto_threads_notice(ui);
+
+        if Components::section_header(
+            ui,
+            "■",
+            "Connection",
+            "Connect to Kaspa node",
+            self.connection_open,
+        ) {
+            self.connection_open = !self.connection_open;
+        }
+        if self.connection_open {
+            let mut action = None;
+            Sections::node_connection(ui, &mut self.state, |a| action = Some(a));
+            if let Some(action) = action {
+                self.handle_action(action);
+            }
+        }
+        ui.add_space(16.0);
+
+        if Components::section_header(
+            ui,
+            "■",
+            "Mining",
+            "Configure mining settings",
+            self.mining_open,
+        ) {
+            self.mining_open = !self.mining_open;
+        }
+        if self.mining_open {
+            let mut action = None;
+            Sections::mining_config(ui, &mut self.state, self.num_cpus, |a| action = Some(a));
+            if let Some(action) = action {
+                self.handle_action(action);
+            }
+        }
+        ui.add_space(16.0);
+
+        if Components::section_header(
+            ui,
+            "■",
+            "Status",
+            "Current status messages",
+            self.status_open,
+        ) {
+            self.status_open = !self.status_open;
+        }
+        if self.status_open {
+            Sections::status(ui, &self.state.status_message, &self.state.status_type);
+        }
+        ui.add_space(16.0);
+
+        if Components::section_header(
+            ui,
+            "■",
+            "Statistics",
+            "Mining performance metrics",
+            self.stats_open,
+        ) {
+            self.stats_open = !self.stats_open;
+        }
+        if self.stats_open {
+            let metrics = self.state.miner.metrics().map(Arc::as_ref);
+            let difficulty = self.state.dag_info.as_ref().map(|info| info.difficulty);
+            Sections::mining_stats(
+                ui,
+                metrics.is_some(),
+                metrics,
+                difficulty,
+                self.local_hashrate,
+                self.hashrate_history.make_contiguous(),
+            );
+            if self.stats_history.is_some() {
+                ui.add_space(8.0);
+                if ui.button("Export Stats").clicked() {
+                    self.export_stats();
+                }
+            }
+        }
+        ui.add_space(16.0);
+
+        if Components::section_header(
+            ui,
+            "■",
+            "Block History",
+            "Blocks accepted by the node",
+            self.block_history_open,
+        ) {
+            self.block_history_open = !self.block_history_open;
+        }
+        if self.block_history_open {
+            Sections::block_history(ui, &self.block_history.lock());
+        }
+        ui.add_space(16.0);
+
+        if Components::section_header(
+            ui,
+            "■",
+            "Network",
+            "Kaspa network overview",
+            self.network_open,
+        ) {
+            self.network_open = !self.network_open;
+        }
+        if self.network_open {
+            Sections::network_info(ui, &self.state, self.local_hashrate);
+        }
+    }
+}
+
+impl eframe::App for MinerApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if !self.theme_applied {
+            let mut visuals = egui::Visuals::dark();
+            Theme::apply(&mut visuals);
+            ctx.set_visuals(visuals);
+            self.theme_applied = true;
+        }
+
+        if self.applied_font_size.as_ref() != Some(&self.state.font_size) {
+            self.state.font_size.apply(ctx);
+            self.applied_font_size = Some(self.state.font_size.clone());
+        }
+
+        self.sync_state();
+
+        egui::TopBottomPanel::top("top_bar").show(ctx, |ui| {
+            ui.add_space(8.0);
+            self.top_bar(ui);
+            ui.add_space(8.0);
+        });
+
+        if self.show_logs {
+            egui::SidePanel::right("logs_panel")
+                .default_width(400.0)
+                .show(ctx, |ui| self.logs_panel(ui));
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| self.main_sections(ui));
+        });
+
+        // Keep stats and logs fresh while idle
+        ctx.request_repaint_after(Duration::from_millis(500));
+    }
+}
@@ -1,51 +1,134 @@
 pub mod api;
+pub mod cli;
+pub mod config;
 pub mod gui;
+pub mod logs;
 pub mod miner;
+pub mod prometheus;
+pub mod state;
+pub mod stats;
+pub mod tls;
+pub mod tuning;
 pub mod ui;
 
-pub use api::KaspaApi;
-pub use miner::{CpuMinerConfig, CpuMinerMetrics};
+pub use api::{
+    CoinSupply, ConnectOptions, DagInfo, KaspaApi, PeerInfo, ValidatedAddress,
+    DEFAULT_GRPC_MAX_MESSAGE_BYTES,
+};
+pub use logs::{LogBuffer, LogEntry, LogFilter, LogLayer};
+pub use miner::{
+    BlockFoundEntry, BlockHistory, CpuMinerConfig, CpuMinerConfigBuilder, CpuMinerMetrics,
+    MinerHandle,
+};
+pub use state::{MinerState, UiAction};
+
+use std::collections::HashSet;
+use std::time::Instant;
 
 // Re-export StatusType for UI modules
 #[derive(Clone, PartialEq)]
 pub enum StatusType {
     Info,
     Success,
+    Warning,
     Error,
 }
 
 // AppState - application state structure
-use std::sync::Arc;
-use tokio::sync::Mutex;
-
 pub struct AppState {
-    pub api: Arc<Mutex<Option<Arc<KaspaApi>>>>,
-    pub metrics: Arc<Mutex<Option<Arc<CpuMinerMetrics>>>>,
-    pub shutdown: Arc<Mutex<Option<tokio::sync::watch::Sender<bool>>>>,
-    pub node_address: String,
+    pub miner: MinerState,
+    /// Node addresses in failover order
+    pub node_addresses: Vec<String>,
+    pub connect_options: ConnectOptions,
     pub mining_address: String,
+    /// Last validation result and the address it was computed for
+    pub address_validation: Option<(String, ValidatedAddress)>,
+    /// When the unvalidated address last changed, for debouncing validation
+    pub address_edited_at: Option<Instant>,
     pub threads: usize,
     pub throttle_ms: Option<u64>,
+    /// Coinbase extra data; blank uses `miner::DEFAULT_EXTRA_DATA`
+    pub extra_data: String,
     pub status_message: String,
     pub status_type: StatusType,
-    pub is_connected: bool,
-    pub is_mining: bool,
+    /// True when `threads` was picked automatically rather than by the user
+    pub threads_auto_set: bool,
+    /// Latest overall CPU usage in percent, sampled in the background
+    pub cpu_load: Option<f32>,
+    /// Index of the highlighted row in the log panel
+    pub selected_line: Option<usize>,
+    /// Log panel capacity; older lines are dropped
+    pub log_max_lines: usize,
+    pub font_size: ui::FontSizeConfig,
+    /// Latest DAG snapshot from the node, refreshed in the background while connected
+    pub dag_info: Option<DagInfo>,
+    pub coin_supply: Option<CoinSupply>,
+    /// Current per-block coinbase reward in sompi
+    pub block_reward_sompi: Option<u64>,
+    pub peers: Option<Vec<PeerInfo>>,
+    /// IDs of alert banners the user has closed. An ID encodes the condition it
+    /// describes, so a changed condition shows a new banner.
+    pub dismissed_banners: HashSet<String>,
+    /// Overall CPU usage the miner should stay under, in percent
+    pub cpu_limit_percent: Option<u8>,
+    /// Back off mining when the CPU gets hotter than `max_temp_c`
+    pub thermal_throttle: bool,
+    pub max_temp_c: u8,
+    /// Preset waiting for the user to confirm it
+    pub pending_preset: Option<tuning::Preset>,
+    /// Saved node and mining addresses offered next to the address fields
+    pub address_book: config::address_book::AddressBook,
+    /// Label typed into the address book menu's save row
+    pub address_book_label: String,
+    /// Named settings from `profiles.json`
+    pub profiles: config::profiles::Profiles,
+    /// Profile last picked or saved in the profile selector
+    pub selected_profile: Option<String>,
+    /// Name typed next to "Save as Profile"
+    pub profile_name: String,
 }
 
-impl Default for AppState {
-    fn default() -> Self {
+impl AppState {
+    /// Default state with the thread count set to one less than
+    /// `CpuMinerConfig::default_threads`, leaving a core free for the OS and the GUI
+    pub fn with_auto_threads() -> Self {
         Self {
-            api: Arc::new(Mutex::new(None)),
-            metrics: Arc::new(Mutex::new(None)),
-            shutdown: Arc::new(Mutex::new(None)),
-            node_address: "127.0.0.1:16210".to_string(),
+            miner: MinerState::Idle,
+            node_addresses: vec!["127.0.0.1:16210".to_string()],
+            connect_options: ConnectOptions::default(),
             mining_address: String::new(),
-            threads: 1,
+            address_validation: None,
+            address_edited_at: None,
+            threads: CpuMinerConfig::default_threads().saturating_sub(1).max(1),
             throttle_ms: None,
+            extra_data: String::new(),
             status_message: String::new(),
             status_type: StatusType::Info,
-            is_connected: false,
-            is_mining: false,
+            threads_auto_set: true,
+            cpu_load: None,
+            selected_line: None,
+            log_max_lines: LogBuffer::MAX_LINES,
+            font_size: ui::FontSizeConfig::default(),
+            dag_info: None,
+            coin_supply: None,
+            block_reward_sompi: None,
+            peers: None,
+            dismissed_banners: HashSet::new(),
+            cpu_limit_percent: None,
+            thermal_throttle: false,
+            max_temp_c: 85,
+            pending_preset: None,
+            address_book: config::address_book::AddressBook::default(),
+            address_book_label: String::new(),
+            profiles: config::profiles::Profiles::default(),
+            selected_profile: None,
+            profile_name: String::new(),
         }
     }
 }
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::with_auto_threads()
+    }
+}
@@ -0,0 +1,192 @@
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+/// One captured log event
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub level: Level,
+    /// The event message followed by its other fields as `key=value`
+    pub message: String,
+}
+
+impl std::fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:>5} {}",
+            self.timestamp.format("%H:%M:%S%.3f"),
+            self.level,
+            self.message
+        )
+    }
+}
+
+/// Recent log entries shown in the log panel. Oldest entries are dropped once
+/// the buffer holds `max_lines`.
+pub struct LogBuffer {
+    lines: VecDeque<LogEntry>,
+    max_lines: usize,
+}
+
+impl LogBuffer {
+    /// Default capacity
+    pub const MAX_LINES: usize = 2000;
+
+    pub fn new(max_lines: usize) -> Self {
+        Self {
+            lines: VecDeque::with_capacity(max_lines),
+            max_lines: max_lines.max(1),
+        }
+    }
+
+    pub fn push(&mut self, entry: LogEntry) {
+        while self.lines.len() >= self.max_lines {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(entry);
+    }
+
+    /// Change the capacity, dropping the oldest entries if it shrank
+    pub fn set_max_lines(&mut self, max_lines: usize) {
+        self.max_lines = max_lines.max(1);
+        while self.lines.len() > self.max_lines {
+            self.lines.pop_front();
+        }
+    }
+
+    pub fn max_lines(&self) -> usize {
+        self.max_lines
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&LogEntry> {
+        self.lines.get(idx)
+    }
+
+    /// Entries from oldest to newest
+    pub fn iter(&self) -> impl Iterator<Item = &LogEntry> {
+        self.lines.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(Self::MAX_LINES)
+    }
+}
+
+/// Tracing layer that turns events into `LogEntry` items for the log panel
+pub struct LogLayer {
+    logs: Arc<tokio::sync::Mutex<LogBuffer>>,
+    /// Events can fire inside the runtime, where the buffer can't be locked
+    /// blocking, so entries are appended from a spawned task
+    rt_handle: tokio::runtime::Handle,
+}
+
+impl LogLayer {
+    pub fn new(
+        logs: Arc<tokio::sync::Mutex<LogBuffer>>,
+        rt_handle: tokio::runtime::Handle,
+    ) -> Self {
+        Self { logs, rt_handle }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let entry = LogEntry {
+            timestamp: Local::now(),
+            level: *event.metadata().level(),
+            message: visitor.finish(),
+        };
+        let logs = Arc::clone(&self.logs);
+        self.rt_handle.spawn(async move {
+            logs.lock().await.push(entry);
+        });
+    }
+}
+
+/// Collects the `message` field and appends the others as `key=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(mut self) -> String {
+        self.message.push_str(&self.fields);
+        self.message
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// What the log panel shows. Applied while rendering; the buffer keeps everything.
+#[derive(Debug, Clone)]
+pub struct LogFilter {
+    /// Least severe level shown
+    pub min_level: Level,
+    /// Case-insensitive substring the message must contain; empty shows all
+    pub search: String,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            min_level: Level::TRACE,
+            search: String::new(),
+        }
+    }
+}
+
+impl LogFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        // More verbose levels compare greater in `tracing`
+        if entry.level > self.min_level {
+            return false;
+        }
+        let search = self.search.trim();
+        search.is_empty()
+            || entry
+                .message
+                .to_lowercase()
+                .contains(&search.to_lowercase())
+    }
+}
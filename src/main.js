@@ -21,8 +21,23 @@ function getInvoke() {
 
 let isConnected = false;
 let isMining = false;
+let isPaused = false;
+// Set while the miner's watchdog is reconnecting to the node
+let connectionLost = false;
 let metricsInterval = null;
+let nodeInfoInterval = null;
+const NODE_INFO_REFRESH_MS = 10000;
+let networkInfoInterval = null;
+const NETWORK_INFO_REFRESH_MS = 30000;
 let logs = [];
+let addressValidationTimer = null;
+let detectedCpus = [];
+// Saved node and mining addresses, see list_address_book_entries
+let addressBook = [];
+const STRESS_TEST_SECS = 30;
+const BENCHMARK_SECS = 10;
+// Same limit as MAX_USER_EXTRA_DATA_LEN in the backend
+const MAX_EXTRA_DATA_BYTES = 64;
 
 // Initialize - wait for Tauri API to be ready
 document.addEventListener('DOMContentLoaded', () => {
@@ -34,6 +49,10 @@ document.addEventListener('DOMContentLoaded', () => {
             clearInterval(initInterval);
             setupEventListeners();
             setupLogListener();
+            loadSavedConfig();
+            loadAddressBook();
+            loadProfiles();
+            loadCpus();
             updateUI();
             addLog('Application initialized');
             showStatus('Ready', 'success');
@@ -59,6 +78,49 @@ function setupLogListener() {
                 addLog(event.payload);
             }
         });
+        // Miner errors, one event per MinerError variant
+        window.__TAURI__.event.listen('template_fetch_failed', (event) => {
+            showStatus(`Miner error: ${event.payload}`, 'error');
+        });
+        window.__TAURI__.event.listen('submit_failed', (event) => {
+            showStatus(`Miner error: ${event.payload}`, 'error');
+        });
+        window.__TAURI__.event.listen('nonce_exhausted', (event) => {
+            showStatus(`Nonce space exhausted: ${event.payload}`, 'warning');
+            addLog(`WARNING: ${event.payload}`);
+        });
+        window.__TAURI__.event.listen('connection_lost', (event) => {
+            const lost = event.payload || {};
+            connectionLost = true;
+            showStatus(
+                `Connection to ${lost.address} lost after ${lost.consecutive_failures} failed template requests, reconnecting: ${lost.last_error}`,
+                'warning'
+            );
+            updateUI();
+        });
+        window.__TAURI__.event.listen('connection_restored', () => {
+            connectionLost = false;
+            showStatus('Reconnected to node', 'success');
+            updateUI();
+        });
+        // Sent as soon as the node accepts a block, ahead of the next metrics poll
+        window.__TAURI__.event.listen('block_found', (event) => {
+            const block = event.payload;
+            if (block) {
+                showStatus(`Block found! (nonce ${block.nonce})`, 'success');
+            }
+        });
+        window.__TAURI__.event.listen('log_saved', (event) => {
+            showStatus(`Logs saved to ${event.payload}`, 'success');
+        });
+        window.__TAURI__.event.listen('deep_link', (event) => applyDeepLink(event.payload));
+        window.__TAURI__.event.listen('deep_link_error', (event) => {
+            showStatus(`Could not open link: ${event.payload}`, 'error');
+        });
+        // Replay the link the app was launched with, now that we are listening
+        getInvoke()?.('take_startup_deep_link').catch((error) => {
+            console.warn('Failed to check startup deep link:', error);
+        });
     } else {
         console.warn('Tauri event API not available, logs will not update in real-time');
     }
@@ -74,6 +136,26 @@ function setupEventListeners() {
     if (disconnectBtn) {
         disconnectBtn.addEventListener('click', disconnectNode);
     }
+    const testConnectionBtn = document.getElementById('test-connection-btn');
+    if (testConnectionBtn) {
+        testConnectionBtn.addEventListener('click', testConnection);
+    }
+    const benchmarkBtn = document.getElementById('benchmark-btn');
+    if (benchmarkBtn) {
+        benchmarkBtn.addEventListener('click', runBenchmark);
+    }
+    const exportStatsBtn = document.getElementById('export-stats-btn');
+    if (exportStatsBtn) {
+        exportStatsBtn.addEventListener('click', exportStats);
+    }
+    const saveLogsBtn = document.getElementById('save-logs-btn');
+    if (saveLogsBtn) {
+        saveLogsBtn.addEventListener('click', saveLogs);
+    }
+    const extraDataInput = document.getElementById('extra-data');
+    if (extraDataInput) {
+        extraDataInput.addEventListener('input', updateExtraDataCount);
+    }
     
     // Mining
     const startBtn = document.getElementById('start-mining-btn');
@@ -84,6 +166,58 @@ function setupEventListeners() {
     if (stopBtn) {
         stopBtn.addEventListener('click', stopMining);
     }
+    const pauseBtn = document.getElementById('pause-mining-btn');
+    const resumeBtn = document.getElementById('resume-mining-btn');
+    if (pauseBtn) {
+        pauseBtn.addEventListener('click', () => setPaused(true));
+    }
+    if (resumeBtn) {
+        resumeBtn.addEventListener('click', () => setPaused(false));
+    }
+    
+    // Mining address validation (debounced)
+    const miningAddressInput = document.getElementById('mining-address');
+    if (miningAddressInput) {
+        miningAddressInput.addEventListener('input', () => {
+            if (addressValidationTimer) {
+                clearTimeout(addressValidationTimer);
+            }
+            addressValidationTimer = setTimeout(validateMiningAddress, 300);
+        });
+    }
+    
+    // Explorer links
+    const viewAddressLink = document.getElementById('view-address-link');
+    if (viewAddressLink) {
+        viewAddressLink.addEventListener('click', (e) => {
+            e.preventDefault();
+            openAddressInExplorer();
+        });
+    }
+    const blockHistory = document.getElementById('block-history');
+    if (blockHistory) {
+        blockHistory.addEventListener('click', (e) => {
+            const button = e.target.closest('[data-nonce]');
+            if (button) {
+                openBlockInExplorer(button.dataset.nonce);
+            }
+        });
+    }
+    
+    // Sessions
+    const sessionsBody = document.getElementById('sessions-body');
+    if (sessionsBody) {
+        sessionsBody.addEventListener('click', (e) => {
+            const button = e.target.closest('[data-session-id]');
+            if (button) {
+                resumeSession(Number(button.dataset.sessionId));
+            }
+        });
+    }
+    const clearSessionsBtn = document.getElementById('clear-sessions-btn');
+    if (clearSessionsBtn) {
+        clearSessionsBtn.addEventListener('click', clearSessions);
+    }
     
     // Threads slider
     const threadsSlider = document.getElementById('threads-slider');
@@ -93,8 +227,53 @@ function setupEventListeners() {
             if (valueDisplay) {
                 valueDisplay.textContent = e.target.value;
             }
+            updateThreadsCpus();
+        });
+        // Applied to the running session once the slider is released
+        threadsSlider.addEventListener('change', (e) => {
+            if (isMining) {
+                setThreadCount(parseInt(e.target.value));
+            }
         });
     }
+    const pinThreadsInput = document.getElementById('pin-threads');
+    if (pinThreadsInput) {
+        pinThreadsInput.addEventListener('change', updateThreadsCpus);
+    }
+    
+    // Profiles
+    const profileSelect = document.getElementById('profile-select');
+    if (profileSelect) {
+        profileSelect.addEventListener('change', () => selectProfile(profileSelect.value));
+    }
+    const saveProfileBtn = document.getElementById('save-profile-btn');
+    if (saveProfileBtn) {
+        saveProfileBtn.addEventListener('click', saveProfile);
+    }
+    const deleteProfileBtn = document.getElementById('delete-profile-btn');
+    if (deleteProfileBtn) {
+        deleteProfileBtn.addEventListener('click', deleteProfile);
+    }
+    
+    // Address book dropdowns next to the node and mining address fields
+    document.querySelectorAll('.address-book').forEach((book) => {
+        const select = book.querySelector('.address-book-select');
+        const target = document.getElementById(book.dataset.target);
+        select.addEventListener('change', () => {
+            const entry = addressBook.find(e =>
+                e.address_type === book.dataset.addressType && e.label === select.value);
+            if (entry && target) {
+                target.value = entry.address;
+                if (target.id === 'mining-address') validateMiningAddress();
+            }
+        });
+        book.querySelector('.address-book-save').addEventListener('click', () => {
+            saveAddressBookEntry(book.dataset.addressType, target ? target.value.trim() : '');
+        });
+        book.querySelector('.address-book-remove').addEventListener('click', () => {
+            removeAddressBookEntry(book.dataset.addressType, select.value);
+        });
+    });
     
     // Logs
     const logsBtn = document.getElementById('show-logs-btn');
@@ -165,6 +344,38 @@ function escapeHtml(text) {
     return div.innerHTML;
 }
 
+async function validateMiningAddress() {
+    const input = document.getElementById('mining-address');
+    const indicator = document.getElementById('mining-address-validation');
+    if (!input || !indicator) return;
+    
+    const address = input.value.trim();
+    if (!address) {
+        indicator.textContent = '';
+        indicator.className = 'address-validation';
+        indicator.title = '';
+        return;
+    }
+    
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    
+    try {
+        const info = await currentInvoke('validate_address', { address });
+        if (info.is_valid) {
+            indicator.textContent = '✓';
+            indicator.className = 'address-validation valid';
+            indicator.title = `Valid ${info.network} address: ${info.address_type}`;
+        } else {
+            indicator.textContent = '✗';
+            indicator.className = 'address-validation invalid';
+            indicator.title = info.error || 'Invalid address';
+        }
+    } catch (error) {
+        console.error('Address validation error:', error);
+    }
+}
+
 async function connectNode() {
     console.log('connectNode called');
     
@@ -189,18 +400,22 @@ async function connectNode() {
         return;
     }
     
+    // Connecting includes waiting for the node to sync, which can take a while
+    const stopProgress = showProgressStatus('Connecting and waiting for node sync');
     try {
         addLog(`Connecting to node at ${address}...`);
-        showStatus('Connecting...', 'info');
         console.log('Calling invoke with:', { command: 'connect_node', address });
         
         const result = await invoke('connect_node', { address });
+        stopProgress();
         console.log('Invoke result:', result);
         isConnected = true;
         showStatus(result, 'success');
         addLog(`Connected: ${result}`);
         updateUI();
+        startNodeInfoPolling();
     } catch (error) {
+        stopProgress();
         const errorMsg = error.toString();
         console.error('Connection error:', error);
         showStatus(`Connection failed: ${errorMsg}`, 'error');
@@ -218,20 +433,150 @@ async function disconnectNode() {
     }
     
     try {
-        await invoke('disconnect_node');
+        await invoke('disconnect_and_stop');
         isConnected = false;
+        stopNodeInfoPolling();
         if (isMining) {
-            await stopMining();
+            isMining = false;
+            isPaused = false;
+            stopMetricsPolling();
         }
         showStatus('Disconnected from node', 'info');
         addLog('Disconnected from node');
         updateUI();
+        refreshSessions();
     } catch (error) {
         showStatus(`Disconnect failed: ${error}`, 'error');
         addLog(`Disconnect error: ${error}`);
     }
 }
 
+// Address prefix expected for each network the node can report
+function addressPrefixForNetwork(network) {
+    if (network === 'mainnet') return 'kaspa:';
+    if (network.startsWith('testnet')) return 'kaspatest:';
+    if (network.startsWith('devnet')) return 'kaspadev:';
+    if (network.startsWith('simnet')) return 'kaspasim:';
+    return null;
+}
+
+async function refreshNodeInfo() {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke || !isConnected) return;
+    try {
+        const info = await currentInvoke('get_node_info');
+        const setText = (id, text) => {
+            const el = document.getElementById(id);
+            if (el) el.textContent = text;
+        };
+        setText('node-network', info.network_name);
+        setText('node-version', info.server_version);
+        setText('node-synced', info.is_synced ? 'Yes' : 'No');
+        setText('node-daa-score', info.virtual_daa_score.toLocaleString());
+        setText('node-mempool', `${info.mempool_size.toLocaleString()} tx`);
+        setText('node-p2p-id', info.p2p_id);
+        setText('node-connect-attempts', info.connection_attempts.toString());
+
+        // Catch e.g. a mainnet address pointed at a testnet node before mining starts
+        const addressInput = document.getElementById('mining-address');
+        const address = addressInput ? addressInput.value.trim() : '';
+        const prefix = addressPrefixForNetwork(info.network_name);
+        const mismatch = address && prefix && !address.startsWith(prefix);
+        setText('node-info-warning', mismatch
+            ? `⚠ The mining address is not a ${info.network_name} address (expected ${prefix}...)`
+            : '');
+    } catch (error) {
+        console.warn('Failed to get node info:', error);
+    }
+}
+
+// Scale a hashes-per-second figure to the largest fitting unit
+function formatHashrate(hps) {
+    const units = ['H/s', 'kH/s', 'MH/s', 'GH/s', 'TH/s', 'PH/s', 'EH/s'];
+    let value = hps;
+    let unit = 0;
+    while (value >= 1000 && unit < units.length - 1) {
+        value /= 1000;
+        unit++;
+    }
+    return `${value.toFixed(2)} ${units[unit]}`;
+}
+
+async function refreshNetworkInfo() {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke || !isConnected) return;
+    try {
+        const info = await currentInvoke('get_network_info');
+        const setText = (id, text) => {
+            const el = document.getElementById(id);
+            if (el) el.textContent = text;
+        };
+        setText('net-hashrate', formatHashrate(info.network_hashrate_hps));
+        setText('net-difficulty', info.difficulty.toLocaleString(undefined, { maximumFractionDigits: 0 }));
+        setText('net-blocks', info.block_count.toLocaleString());
+        setText('net-headers', info.header_count.toLocaleString());
+        setText('net-daa-score', info.virtual_daa_score.toLocaleString());
+
+        let share = '-';
+        if (isMining && info.network_hashrate_hps > 0) {
+            const metrics = await currentInvoke('get_metrics');
+            const localHps = metrics.hashrate_mhs * 1e6;
+            share = `${(localHps / info.network_hashrate_hps * 100).toPrecision(3)}%`;
+        }
+        setText('net-share', share);
+    } catch (error) {
+        console.warn('Failed to get network info:', error);
+    }
+}
+
+function startNodeInfoPolling() {
+    stopNodeInfoPolling();
+    refreshNodeInfo();
+    refreshNetworkInfo();
+    nodeInfoInterval = setInterval(refreshNodeInfo, NODE_INFO_REFRESH_MS);
+    networkInfoInterval = setInterval(refreshNetworkInfo, NETWORK_INFO_REFRESH_MS);
+}
+
+function stopNodeInfoPolling() {
+    if (nodeInfoInterval) {
+        clearInterval(nodeInfoInterval);
+        nodeInfoInterval = null;
+    }
+    if (networkInfoInterval) {
+        clearInterval(networkInfoInterval);
+        networkInfoInterval = null;
+    }
+}
+
+let connectionTestTimer = null;
+
+// Check the node address over a throwaway connection; the result clears itself
+async function testConnection() {
+    const currentInvoke = getInvoke();
+    const nodeInput = document.getElementById('node-address');
+    const resultEl = document.getElementById('connection-test-result');
+    const testBtn = document.getElementById('test-connection-btn');
+    if (!currentInvoke || !nodeInput || !resultEl) return;
+
+    clearTimeout(connectionTestTimer);
+    resultEl.className = 'connection-test-result';
+    resultEl.textContent = 'Testing...';
+    if (testBtn) testBtn.disabled = true;
+    try {
+        resultEl.textContent = await currentInvoke('test_connection', { address: nodeInput.value.trim() });
+        resultEl.classList.add('success');
+    } catch (error) {
+        resultEl.textContent = error.toString();
+        resultEl.classList.add('error');
+    } finally {
+        if (testBtn) testBtn.disabled = false;
+    }
+    connectionTestTimer = setTimeout(() => {
+        resultEl.textContent = '';
+        resultEl.className = 'connection-test-result';
+    }, 5000);
+}
+
 async function startMining() {
     invoke = getInvoke();
     if (!invoke) {
@@ -256,12 +601,45 @@ async function startMining() {
         return;
     }
     
-    const threadsSlider = document.getElementById('threads-slider');
-    const throttleInput = document.getElementById('throttle-ms');
-    
-    const threads = threadsSlider ? parseInt(threadsSlider.value) : 1;
-    const throttleStr = throttleInput ? throttleInput.value.trim() : '';
-    const throttleMs = throttleStr ? parseInt(throttleStr) : null;
+    // Further addresses, paid in turn after the first, one accepted block each
+    const rotationInput = document.getElementById('extra-mining-addresses');
+    const extraMiningAddresses = (rotationInput ? rotationInput.value : '')
+        .split(/[\s,]+/)
+        .filter(Boolean);
+
+    const { threads, throttleMs, targetHashrate } = readMiningSettings();
+    const pinned = pinnedCpus();
+    const cpuAffinity = pinned === null ? null : pinned.map(cpu => cpu.id);
+
+    const extraDataInput = document.getElementById('extra-data');
+    const extraDataStr = extraDataInput ? extraDataInput.value.trim() : '';
+    if (extraDataBytes(extraDataStr) > MAX_EXTRA_DATA_BYTES) {
+        showStatus(`Extra data must be at most ${MAX_EXTRA_DATA_BYTES} bytes`, 'error');
+        return;
+    }
+    const extraData = extraDataStr || null;
+
+    // Blank lets the backend pick from the network's BPS
+    const pollInput = document.getElementById('poll-interval-ms');
+    const pollStr = pollInput ? pollInput.value.trim() : '';
+    const pollIntervalMs = pollStr ? parseInt(pollStr) : null;
+    if (pollStr && !(pollIntervalMs > 0)) {
+        showStatus('Template poll interval must be a positive number of milliseconds', 'error');
+        return;
+    }
+
+    const stressTestInput = document.getElementById('stress-test');
+    let stressTestSecs = null;
+    if (stressTestInput && stressTestInput.checked) {
+        const confirmed = confirm(
+            'The thermal stress test runs every mining thread at full speed for ' +
+            `${STRESS_TEST_SECS} seconds, ignoring the throttle. Your CPU will get as hot ` +
+            'as it can. Make sure cooling is adequate.\n\nRun the stress test?'
+        );
+        if (!confirmed) return;
+        stressTestSecs = STRESS_TEST_SECS;
+        stressTestInput.checked = false;
+    }
     
     try {
         addLog(`Starting mining with ${threads} thread(s)...`);
@@ -269,10 +647,17 @@ async function startMining() {
         
         const result = await invoke('start_mining', {
             miningAddress,
+            extraMiningAddresses,
             threads,
-            throttleMs
+            throttleMs,
+            targetHashrate,
+            stressTestSecs,
+            extraData,
+            pollIntervalMs,
+            cpuAffinity
         });
         isMining = true;
+        isPaused = false;
         showStatus(result, 'success');
         addLog(`Mining started: ${result}`);
         updateUI();
@@ -286,6 +671,92 @@ async function startMining() {
     }
 }
 
+// Thread count and throttle settings from the mining form
+// UTF-8 length, which is what ends up in the coinbase
+function extraDataBytes(text) {
+    return new TextEncoder().encode(text).length;
+}
+
+function updateExtraDataCount() {
+    const input = document.getElementById('extra-data');
+    const countEl = document.getElementById('extra-data-count');
+    if (!input || !countEl) return;
+    const bytes = extraDataBytes(input.value.trim());
+    countEl.textContent = `${bytes}/${MAX_EXTRA_DATA_BYTES} bytes`;
+    countEl.classList.toggle('over-limit', bytes > MAX_EXTRA_DATA_BYTES);
+}
+
+function readMiningSettings() {
+    const threadsSlider = document.getElementById('threads-slider');
+    const throttleInput = document.getElementById('throttle-ms');
+    const targetInput = document.getElementById('target-hashrate');
+
+    const threads = threadsSlider ? parseInt(threadsSlider.value) : 1;
+    const throttleStr = throttleInput ? throttleInput.value.trim() : '';
+    const throttleMs = throttleStr ? parseInt(throttleStr) : null;
+    const targetStr = targetInput ? targetInput.value.trim() : '';
+    const targetHashrate = targetStr ? parseFloat(targetStr) : null;
+    return { threads, throttleMs, targetHashrate };
+}
+
+// Hash a fake template with the current settings; works without a node
+async function runBenchmark() {
+    const currentInvoke = getInvoke();
+    const resultEl = document.getElementById('benchmark-result');
+    const benchmarkBtn = document.getElementById('benchmark-btn');
+    if (!currentInvoke || !resultEl) return;
+
+    const { threads, throttleMs, targetHashrate } = readMiningSettings();
+    resultEl.textContent = `Running for ${BENCHMARK_SECS}s...`;
+    if (benchmarkBtn) benchmarkBtn.disabled = true;
+    try {
+        const result = await currentInvoke('benchmark', {
+            threads,
+            throttleMs,
+            targetHashrate,
+            durationSecs: BENCHMARK_SECS
+        });
+        const perThread = result.per_thread_hps
+            .map(hps => `${(hps / 1000).toFixed(1)}`)
+            .join(', ');
+        resultEl.textContent =
+            `${(result.hashes_per_second / 1e6).toFixed(3)} MH/s ` +
+            `(per thread kH/s: ${perThread})`;
+        addLog(`Benchmark: ${resultEl.textContent}`);
+    } catch (error) {
+        resultEl.textContent = `Benchmark failed: ${error}`;
+    } finally {
+        if (benchmarkBtn) benchmarkBtn.disabled = isMining;
+    }
+}
+
+// Save the current or last session's stats snapshots as CSV (the backend asks where)
+async function exportStats() {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    try {
+        const path = await currentInvoke('export_stats');
+        if (path) {
+            showStatus(`Stats exported to ${path}`, 'success');
+            addLog(`Exported stats to ${path}`);
+        }
+    } catch (error) {
+        showStatus(`Export failed: ${error}`, 'error');
+    }
+}
+
+// Save the backend's buffered log entries (the backend asks where and reports
+// success through the log_saved event)
+async function saveLogs() {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    try {
+        await currentInvoke('save_logs');
+    } catch (error) {
+        showStatus(`Saving logs failed: ${error}`, 'error');
+    }
+}
+
 async function stopMining() {
     invoke = getInvoke();
     if (!invoke) {
@@ -296,16 +767,49 @@ async function stopMining() {
     try {
         const result = await invoke('stop_mining');
         isMining = false;
+        isPaused = false;
         showStatus(result, 'info');
         addLog(`Mining stopped: ${result}`);
         stopMetricsPolling();
         updateUI();
+        refreshSessions();
     } catch (error) {
         showStatus(`Failed to stop mining: ${error}`, 'error');
         addLog(`Mining stop error: ${error}`);
     }
 }
 
+async function setThreadCount(threads) {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    try {
+        const result = await currentInvoke('set_thread_count', { threads });
+        showStatus(result, 'info');
+        addLog(result);
+    } catch (error) {
+        showStatus(`Failed to change thread count: ${error}`, 'error');
+    }
+}
+
+// Pause keeps the session (and its counters); resume continues it
+async function setPaused(paused) {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) {
+        showStatus('Tauri API not available', 'error');
+        return;
+    }
+
+    try {
+        const result = await currentInvoke(paused ? 'pause_mining' : 'resume_mining');
+        isPaused = paused;
+        showStatus(result, 'info');
+        addLog(result);
+        updateUI();
+    } catch (error) {
+        showStatus(`Failed to ${paused ? 'pause' : 'resume'} mining: ${error}`, 'error');
+    }
+}
+
 function startMetricsPolling() {
     if (metricsInterval) {
         clearInterval(metricsInterval);
@@ -317,18 +821,453 @@ function startMetricsPolling() {
             if (!currentInvoke) return;
             const metrics = await currentInvoke('get_metrics');
             const hashesEl = document.getElementById('hashes-tried');
+            const totalHashesEl = document.getElementById('total-hashes-tried');
             const submittedEl = document.getElementById('blocks-submitted');
             const acceptedEl = document.getElementById('blocks-accepted');
+            const hashrateEl = document.getElementById('hashrate');
+            const peakEl = document.getElementById('peak-hashrate');
+            const rejectedEl = document.getElementById('blocks-rejected');
             
-            if (hashesEl) hashesEl.textContent = metrics.hashes_tried.toLocaleString();
+            if (hashesEl) hashesEl.textContent = metrics.session_hashes_tried.toLocaleString();
+            if (totalHashesEl) totalHashesEl.textContent = metrics.total_hashes_tried.toLocaleString();
+            if (hashrateEl) hashrateEl.textContent = `${metrics.hashrate_mhs.toFixed(2)} MH/s`;
+            if (peakEl) peakEl.textContent = `${metrics.peak_hashrate_mhs.toFixed(2)} MH/s`;
             if (submittedEl) submittedEl.textContent = metrics.blocks_submitted.toLocaleString();
             if (acceptedEl) acceptedEl.textContent = metrics.blocks_accepted.toLocaleString();
+            if (rejectedEl) {
+                rejectedEl.textContent = `${metrics.blocks_rejected_stale} stale, ` +
+                    `${metrics.blocks_rejected_duplicate} duplicate, ${metrics.blocks_rejected_invalid} invalid`;
+                rejectedEl.title = metrics.last_rejection_reason
+                    ? `Last rejection: ${metrics.last_rejection_reason}`
+                    : '';
+            }
+            const droppedEl = document.getElementById('blocks-dropped');
+            if (droppedEl) {
+                droppedEl.textContent = metrics.blocks_dropped.toLocaleString();
+                // Blocks lost to submit timeouts and to errors after all retries
+                droppedEl.classList.toggle('stat-alert', metrics.blocks_dropped > 0);
+                droppedEl.title = metrics.submit_timeouts > 0
+                    ? `${metrics.submit_timeouts.toLocaleString()} timed out`
+                    : '';
+            }
+            const backpressureEl = document.getElementById('blocks-dropped-backpressure');
+            if (backpressureEl) {
+                backpressureEl.textContent = metrics.blocks_dropped_backpressure.toLocaleString();
+                // Blocks found while the submit queue was full
+                backpressureEl.classList.toggle('stat-alert', metrics.blocks_dropped_backpressure > 0);
+            }
+            const uptimeEl = document.getElementById('session-uptime');
+            const avgIntervalEl = document.getElementById('avg-block-interval');
+            const lastBlockEl = document.getElementById('last-block-ago');
+            if (uptimeEl) uptimeEl.textContent = formatDuration(metrics.uptime_secs);
+            if (avgIntervalEl) {
+                avgIntervalEl.textContent = metrics.avg_block_interval_secs == null
+                    ? '-'
+                    : formatDuration(Math.round(metrics.avg_block_interval_secs));
+            }
+            if (lastBlockEl) {
+                lastBlockEl.textContent = metrics.last_block_ago_secs == null
+                    ? 'None yet'
+                    : `${formatDuration(metrics.last_block_ago_secs)} ago`;
+            }
+            const latencyEl = document.getElementById('node-template-latency');
+            if (latencyEl) {
+                latencyEl.textContent = `avg ${metrics.template_fetch_latency_ms_avg.toFixed(0)} ms, ` +
+                    `p95 ${metrics.template_fetch_latency_ms_p95.toFixed(0)} ms`;
+                // A slow link to the node delays new work to the threads
+                latencyEl.classList.toggle('stat-warning', metrics.template_fetch_latency_high);
+            }
+            renderBlockHistory(metrics.accepted_blocks || []);
+            if (connectionLost !== metrics.connection_lost) {
+                connectionLost = metrics.connection_lost;
+                if (!connectionLost) showStatus('Reconnected to node', 'success');
+                updateUI();
+            }
+            await checkThreadActivity(currentInvoke);
         } catch (error) {
             // Metrics not available yet - this is normal when not mining
         }
     }, 1000);
 }
 
+// A thread that hasn't reported in this long is considered stalled
+const THREAD_STALL_MS = 5000;
+let stalledThreads = '';
+
+async function checkThreadActivity(currentInvoke) {
+    const details = await currentInvoke('get_thread_details');
+    const stalled = details
+        .filter(t => t.last_active_ms_ago > THREAD_STALL_MS)
+        .map(t => t.idx);
+    const key = stalled.join(',');
+    // Only report changes so the status box isn't overwritten every second
+    if (key !== stalledThreads) {
+        stalledThreads = key;
+        if (stalled.length > 0) {
+            const message = `Mining thread(s) ${key} inactive for over ${THREAD_STALL_MS / 1000}s`;
+            showStatus(message, 'error');
+            addLog(`Warning: ${message}`);
+        }
+    }
+}
+
+function renderBlockHistory(blocks) {
+    const history = document.getElementById('block-history');
+    if (!history) return;
+    // Newest first
+    history.innerHTML = blocks.slice().reverse().map(block => `
+        <div class="block-history-entry">
+            <span class="block-time">${escapeHtml(new Date(block.timestamp).toLocaleTimeString())}</span>
+            <span class="block-hash" title="${escapeHtml(block.hash)}">${escapeHtml(block.hash.slice(0, 16))}…</span>
+            <button class="explorer-button" data-nonce="${escapeHtml(String(block.nonce))}">View in Explorer</button>
+        </div>`).join('');
+}
+
+async function openBlockInExplorer(nonce) {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    try {
+        await currentInvoke('open_block_in_explorer', { nonce: Number(nonce) });
+    } catch (error) {
+        showStatus(`Failed to open explorer: ${error}`, 'error');
+    }
+}
+
+async function openAddressInExplorer() {
+    const currentInvoke = getInvoke();
+    const input = document.getElementById('mining-address');
+    if (!currentInvoke || !input) return;
+    const address = input.value.trim();
+    if (!address) {
+        showStatus('Please enter a mining address', 'error');
+        return;
+    }
+    try {
+        await currentInvoke('open_address_in_explorer', { address });
+    } catch (error) {
+        showStatus(`Failed to open explorer: ${error}`, 'error');
+    }
+}
+
+let sessions = [];
+
+async function refreshSessions() {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    try {
+        sessions = await currentInvoke('list_sessions');
+        renderSessions();
+    } catch (error) {
+        addLog(`Failed to load sessions: ${error}`);
+    }
+}
+
+function renderSessions() {
+    const body = document.getElementById('sessions-body');
+    if (!body) return;
+    // Newest first
+    body.innerHTML = sessions.slice().reverse().map(session => {
+        const started = new Date(session.started_at * 1000).toLocaleString();
+        const duration = session.stopped_at !== null
+            ? formatDuration(session.stopped_at - session.started_at)
+            : '—';
+        return `
+        <tr>
+            <td>${escapeHtml(started)}</td>
+            <td>${escapeHtml(duration)}</td>
+            <td>${session.hashes.toLocaleString()}</td>
+            <td>${session.blocks_accepted.toLocaleString()}</td>
+            <td>${session.avg_hashrate_mhs.toFixed(2)}</td>
+            <td>${session.threads}</td>
+            <td><button class="explorer-button" data-session-id="${session.id}">Resume</button></td>
+        </tr>`;
+    }).join('');
+}
+
+function formatDuration(totalSeconds) {
+    const hours = Math.floor(totalSeconds / 3600);
+    const minutes = Math.floor((totalSeconds % 3600) / 60);
+    const seconds = totalSeconds % 60;
+    return hours > 0 ? `${hours}h ${minutes}m` : `${minutes}m ${seconds}s`;
+}
+
+// Pre-fill the connection and mining fields from a previous session
+function resumeSession(id) {
+    const session = sessions.find(s => s.id === id);
+    if (!session) return;
+    const nodeInput = document.getElementById('node-address');
+    const addressInput = document.getElementById('mining-address');
+    const threadsSlider = document.getElementById('threads-slider');
+    const threadsValue = document.getElementById('threads-value');
+    const throttleInput = document.getElementById('throttle-ms');
+    if (nodeInput) nodeInput.value = session.node.replace(/^grpc:\/\//, '');
+    if (addressInput) {
+        addressInput.value = session.address;
+        validateMiningAddress();
+    }
+    if (threadsSlider) threadsSlider.value = session.threads;
+    if (threadsValue) threadsValue.textContent = session.threads;
+    updateThreadsCpus();
+    if (throttleInput) throttleInput.value = session.throttle_ms ?? '';
+    showStatus('Settings loaded from session. Connect and start mining to resume.', 'info');
+}
+
+// Pre-fill the fields with the settings saved by the last successful start
+async function loadSavedConfig() {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    try {
+        const profile = await currentInvoke('load_config');
+        if (!profile) return;
+        applyProfile(profile);
+        addLog('Loaded saved settings');
+    } catch (error) {
+        addLog(`Could not load saved settings: ${error}`);
+    }
+}
+
+// Fill the connection and mining fields from a MinerProfile
+function applyProfile(profile) {
+    const nodeInput = document.getElementById('node-address');
+    const addressInput = document.getElementById('mining-address');
+    const threadsSlider = document.getElementById('threads-slider');
+    const threadsValue = document.getElementById('threads-value');
+    const throttleInput = document.getElementById('throttle-ms');
+    if (nodeInput && profile.node_addresses.length > 0) {
+        nodeInput.value = profile.node_addresses.join(', ');
+    }
+    if (addressInput && profile.mining_address) {
+        addressInput.value = profile.mining_address;
+        validateMiningAddress();
+    }
+    if (threadsSlider) threadsSlider.value = profile.threads;
+    if (threadsValue) threadsValue.textContent = threadsSlider ? threadsSlider.value : profile.threads;
+    updateThreadsCpus();
+    if (throttleInput) throttleInput.value = profile.throttle_ms ?? '';
+}
+
+// The current fields as a MinerProfile, the inverse of applyProfile
+function profileFromForm() {
+    const nodeInput = document.getElementById('node-address');
+    const addressInput = document.getElementById('mining-address');
+    const threadsSlider = document.getElementById('threads-slider');
+    const throttleInput = document.getElementById('throttle-ms');
+    const throttle = throttleInput ? parseInt(throttleInput.value) : NaN;
+    return {
+        schema_version: 1,
+        node_addresses: nodeInput
+            ? nodeInput.value.split(',').map(a => a.trim()).filter(a => a)
+            : [],
+        mining_address: addressInput ? addressInput.value.trim() : '',
+        threads: threadsSlider ? parseInt(threadsSlider.value) : 1,
+        throttle_ms: Number.isNaN(throttle) ? null : throttle,
+    };
+}
+
+async function loadProfiles(selected = '') {
+    const currentInvoke = getInvoke();
+    const select = document.getElementById('profile-select');
+    if (!currentInvoke || !select) return;
+    try {
+        const names = await currentInvoke('list_profiles');
+        select.replaceChildren(select.options[0]);
+        names.forEach((name) => {
+            const option = document.createElement('option');
+            option.value = name;
+            option.textContent = name;
+            select.appendChild(option);
+        });
+        select.value = names.includes(selected) ? selected : '';
+    } catch (error) {
+        addLog(`Could not load profiles: ${error}`);
+    }
+}
+
+async function selectProfile(name) {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke || !name) return;
+    try {
+        applyProfile(await currentInvoke('load_profile', { name }));
+        showStatus(`Loaded profile "${name}"`, 'success');
+    } catch (error) {
+        showStatus(`Could not load profile: ${error}`, 'error');
+    }
+}
+
+// Save the current fields under a new or existing profile name
+async function saveProfile() {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    const select = document.getElementById('profile-select');
+    const name = (prompt('Profile name:', select ? select.value : '') || '').trim();
+    if (!name) return;
+    try {
+        const names = await currentInvoke('list_profiles');
+        if (!names.includes(name)) {
+            await currentInvoke('create_profile', { name });
+        }
+        await currentInvoke('update_profile', { name, config: profileFromForm() });
+        await loadProfiles(name);
+        showStatus(`Saved profile "${name}"`, 'success');
+    } catch (error) {
+        showStatus(`Could not save profile: ${error}`, 'error');
+    }
+}
+
+async function deleteProfile() {
+    const currentInvoke = getInvoke();
+    const select = document.getElementById('profile-select');
+    if (!currentInvoke || !select) return;
+    const name = select.value;
+    if (!name) {
+        showStatus('Pick a profile to delete', 'error');
+        return;
+    }
+    try {
+        await currentInvoke('delete_profile', { name });
+        await loadProfiles();
+        showStatus(`Deleted profile "${name}"`, 'success');
+    } catch (error) {
+        showStatus(`Could not delete profile: ${error}`, 'error');
+    }
+}
+
+async function loadAddressBook() {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    try {
+        addressBook = await currentInvoke('list_address_book_entries');
+        renderAddressBook();
+    } catch (error) {
+        addLog(`Could not load address book: ${error}`);
+    }
+}
+
+// Refill each dropdown with the saved entries of its type
+function renderAddressBook() {
+    document.querySelectorAll('.address-book').forEach((book) => {
+        const select = book.querySelector('.address-book-select');
+        const placeholder = select.options[0];
+        select.replaceChildren(placeholder);
+        addressBook
+            .filter(e => e.address_type === book.dataset.addressType)
+            .forEach((entry) => {
+                const option = document.createElement('option');
+                option.value = entry.label;
+                option.textContent = `${entry.label} (${entry.address})`;
+                select.appendChild(option);
+            });
+        select.value = '';
+    });
+}
+
+async function saveAddressBookEntry(addressType, address) {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    if (!address) {
+        showStatus('Enter an address to save', 'error');
+        return;
+    }
+    const label = prompt('Name for this address:', address);
+    if (label === null) return;
+    try {
+        addressBook = await currentInvoke('add_address_book_entry', {
+            label,
+            address,
+            addressType,
+        });
+        renderAddressBook();
+        showStatus(`Saved "${label.trim()}" to the address book`, 'success');
+    } catch (error) {
+        showStatus(`Could not save address: ${error}`, 'error');
+    }
+}
+
+async function removeAddressBookEntry(addressType, label) {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    if (!label) {
+        showStatus('Pick a saved address to remove', 'error');
+        return;
+    }
+    try {
+        addressBook = await currentInvoke('remove_address_book_entry', { label, addressType });
+        renderAddressBook();
+        showStatus(`Removed "${label}" from the address book`, 'success');
+    } catch (error) {
+        showStatus(`Could not remove address: ${error}`, 'error');
+    }
+}
+
+// Size the thread slider to the logical CPUs of this machine
+async function loadCpus() {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    try {
+        detectedCpus = await currentInvoke('detect_cpus');
+        const threadsSlider = document.getElementById('threads-slider');
+        if (threadsSlider && detectedCpus.length > 0) {
+            threadsSlider.max = detectedCpus.length;
+        }
+        updateThreadsCpus();
+    } catch (error) {
+        console.warn('Failed to detect CPUs:', error);
+    }
+}
+
+// Logical CPUs to pin the selected threads to, one each, or null when pinning is off
+function pinnedCpus() {
+    const threadsSlider = document.getElementById('threads-slider');
+    const pinInput = document.getElementById('pin-threads');
+    if (!threadsSlider || !pinInput || !pinInput.checked || detectedCpus.length === 0) {
+        return null;
+    }
+    return detectedCpus.slice(0, Number(threadsSlider.value));
+}
+
+// Show which logical CPUs (and physical cores) the threads will be pinned to
+function updateThreadsCpus() {
+    const hint = document.getElementById('threads-cpus');
+    if (!hint) return;
+    const selected = pinnedCpus();
+    hint.textContent = selected === null ? '' : 'Pinned to CPUs: ' + selected
+        .map(cpu => cpu.core === null ? `${cpu.id}` : `${cpu.id} (core ${cpu.core})`)
+        .join(', ');
+}
+
+// Pre-fill the connection and mining fields from a kaspa-miner:// link
+function applyDeepLink(link) {
+    if (!link) return;
+    const nodeInput = document.getElementById('node-address');
+    const addressInput = document.getElementById('mining-address');
+    const threadsSlider = document.getElementById('threads-slider');
+    const threadsValue = document.getElementById('threads-value');
+    if (link.node && nodeInput) nodeInput.value = link.node;
+    if (link.address && addressInput) {
+        addressInput.value = link.address;
+        validateMiningAddress();
+    }
+    if (link.threads && threadsSlider) {
+        const threads = Math.min(link.threads, Number(threadsSlider.max));
+        threadsSlider.value = threads;
+        if (threadsValue) threadsValue.textContent = threads;
+        updateThreadsCpus();
+    }
+    showStatus('Settings loaded from link. Connect and start mining when ready.', 'info');
+}
+
+async function clearSessions() {
+    const currentInvoke = getInvoke();
+    if (!currentInvoke) return;
+    try {
+        await currentInvoke('clear_sessions');
+        sessions = [];
+        renderSessions();
+    } catch (error) {
+        showStatus(`Failed to clear sessions: ${error}`, 'error');
+    }
+}
+
 function stopMetricsPolling() {
     if (metricsInterval) {
         clearInterval(metricsInterval);
@@ -344,12 +1283,28 @@ function showStatus(message, type = 'info') {
     }
 }
 
+// Show `message` with animated trailing dots until the returned function is called
+function showProgressStatus(message) {
+    let dots = 0;
+    showStatus(message, 'info');
+    const timer = setInterval(() => {
+        dots = (dots + 1) % 4;
+        showStatus(message + '.'.repeat(dots), 'info');
+    }, 500);
+    return () => clearInterval(timer);
+}
+
 function updateUI() {
     // Update connection status
     const nodeDot = document.getElementById('node-dot');
     const nodeStatus = document.getElementById('node-status');
     if (nodeDot && nodeStatus) {
-        if (isConnected) {
+        const reconnecting = isConnected && isMining && connectionLost;
+        nodeDot.classList.toggle('warning', reconnecting);
+        if (reconnecting) {
+            nodeDot.classList.remove('active');
+            nodeStatus.textContent = 'Node: Reconnecting';
+        } else if (isConnected) {
             nodeDot.classList.add('active');
             nodeStatus.textContent = 'Node: Connected';
         } else {
@@ -362,7 +1317,11 @@ function updateUI() {
     const miningDot = document.getElementById('mining-dot');
     const miningStatus = document.getElementById('mining-status');
     if (miningDot && miningStatus) {
-        if (isMining) {
+        miningDot.classList.toggle('warning', isMining && isPaused);
+        if (isMining && isPaused) {
+            miningDot.classList.remove('active');
+            miningStatus.textContent = 'Mining: Paused';
+        } else if (isMining) {
             miningDot.classList.add('active');
             miningStatus.textContent = 'Mining: Active';
         } else {
@@ -389,4 +1348,17 @@ function updateUI() {
     if (stopBtn) {
         stopBtn.style.display = isMining ? 'inline-block' : 'none';
     }
+    const pauseBtn = document.getElementById('pause-mining-btn');
+    const resumeBtn = document.getElementById('resume-mining-btn');
+    if (pauseBtn) {
+        pauseBtn.style.display = isMining && !isPaused ? 'inline-block' : 'none';
+    }
+    if (resumeBtn) {
+        resumeBtn.style.display = isMining && isPaused ? 'inline-block' : 'none';
+    }
+    // A benchmark would compete with the miner for the same cores
+    const benchmarkBtn = document.getElementById('benchmark-btn');
+    if (benchmarkBtn) {
+        benchmarkBtn.disabled = isMining;
+    }
 }
@@ -1,25 +1,45 @@
-use kaspa_cpu_miner_gui::AppState;
+use clap::Parser;
+use kaspa_cpu_miner_gui::cli::{self, CliArgs};
+use kaspa_cpu_miner_gui::{LogBuffer, LogLayer};
+use std::path::PathBuf;
 use std::sync::Arc;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::format::{DefaultFields, Format};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
 
 fn main() -> eframe::Result<()> {
+    let args = CliArgs::parse();
+    if args.headless {
+        // No log panel to feed: log straight to stderr, keeping stdout for metrics
+        let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+            .with(log_file_layer(&args))
+            .init();
+        if let Err(e) = cli::run_headless(args) {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Create log collector
-    let logs = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
-    let logs_clone = Arc::clone(&logs);
+    let logs = Arc::new(tokio::sync::Mutex::new(LogBuffer::default()));
 
     // Setup tracing subscriber that captures logs
     let filter = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
 
-    // Create a runtime handle for the log writer
+    // The log layer appends entries from tasks on this runtime
     let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
-    let rt_handle = rt.handle().clone();
-
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_writer(move || LogWriter {
-            logs: Arc::clone(&logs_clone),
-            rt_handle: rt_handle.clone(),
-        })
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(LogLayer::new(Arc::clone(&logs), rt.handle().clone()))
+        .with(log_file_layer(&args))
         .init();
 
     let options = eframe::NativeOptions {
@@ -29,7 +49,11 @@ fn main() -> eframe::Result<()> {
         ..Default::default()
     };
 
-    let mut app = kaspa_cpu_miner_gui::gui::MinerApp::default();
+    // Separate from `rt` so template fetches and submits don't share threads with
+    // the log layer and GUI tasks
+    let miner_runtime =
+        kaspa_cpu_miner_gui::miner::start_miner_runtime().expect("Failed to create miner runtime");
+    let mut app = kaspa_cpu_miner_gui::gui::MinerApp::new(rt.handle().clone(), miner_runtime);
     app.logs = logs;
 
     eframe::run_native(
@@ -39,37 +63,45 @@ fn main() -> eframe::Result<()> {
     )
 }
 
-// Custom writer that captures logs
-struct LogWriter {
-    logs: Arc<tokio::sync::Mutex<Vec<String>>>,
-    rt_handle: tokio::runtime::Handle,
+/// Log layer for `--log-file`, with full timestamps and targets for long-running deployments
+fn log_file_layer<S>(args: &CliArgs) -> Option<FileLayer<S>>
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    log_file_appender(args).map(|appender| {
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_target(true)
+            .with_writer(appender)
+    })
 }
 
-impl std::io::Write for LogWriter {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        if let Ok(s) = std::str::from_utf8(buf) {
-            let logs = Arc::clone(&self.logs);
-            let lines: Vec<String> = s.lines().map(|l| l.to_string()).collect();
+type FileLayer<S> = tracing_subscriber::fmt::Layer<S, DefaultFields, Format, RollingFileAppender>;
 
-            if !lines.is_empty() {
-                self.rt_handle.spawn(async move {
-                    let mut logs_guard = logs.lock().await;
-                    for line in lines {
-                        if !line.trim().is_empty() {
-                            logs_guard.push(line);
-                        }
-                    }
-                    // Keep only last 1000 lines
-                    while logs_guard.len() > 1000 {
-                        logs_guard.remove(0);
-                    }
-                });
-            }
+/// Daily rolling log file from `--log-file` or `KASPA_MINER_LOG_FILE`, if either is set
+fn log_file_appender(args: &CliArgs) -> Option<RollingFileAppender> {
+    let path = args
+        .log_file
+        .clone()
+        .or_else(|| std::env::var_os("KASPA_MINER_LOG_FILE").map(PathBuf::from))
+        .filter(|path| !path.as_os_str().is_empty())?;
+    let Some(file_name) = path.file_name() else {
+        eprintln!("Invalid log file path: {}", path.display());
+        return None;
+    };
+    let directory = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    match RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(file_name.to_string_lossy())
+        .build(&directory)
+    {
+        Ok(appender) => Some(appender),
+        Err(e) => {
+            eprintln!("Failed to open log file {}: {}", path.display(), e);
+            None
         }
-        Ok(buf.len())
-    }
-
-    fn flush(&mut self) -> std::io::Result<()> {
-        Ok(())
     }
 }
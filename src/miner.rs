@@ -1,12 +1,17 @@
 use crate::api::KaspaApi;
+use crate::tuning::{CpuTemperature, ProcessLoad};
+use anyhow::Context;
+use chrono::{DateTime, Local};
 use kaspa_consensus_core::block::Block;
 use kaspa_pow::State as PowState;
 use kaspa_rpc_core::RpcRawBlock;
 use parking_lot::{Condvar, Mutex};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
 use tokio::sync::{mpsc, watch};
+use tracing::Instrument;
 
 // Performance optimizations inspired by kaspanet/cpuminer:
 // 1. Batch hash counting: Update atomic counter every BATCH_SIZE hashes instead of every hash
@@ -17,16 +22,250 @@ use tokio::sync::{mpsc, watch};
 
 #[derive(Clone)]
 pub struct CpuMinerConfig {
+    /// First of `mining_addresses`
     pub mining_address: String,
+    /// Block rewards go to these in turn, moving to the next after every
+    /// accepted block, so earnings aren't all linked to one address
+    pub mining_addresses: Vec<String>,
     pub threads: usize,
     pub throttle: Option<Duration>,
     pub template_poll_interval: Duration,
+    /// Keep the miner's CPU usage near this percentage of all cores, shared across all threads
+    pub cpu_limit_percent: Option<u8>,
+    /// Slow down while any temperature sensor reads above this many °C
+    pub max_temp_c: Option<u8>,
+    /// Coinbase extra data (pool name, version string, ...); `None` uses `DEFAULT_EXTRA_DATA`
+    pub extra_data: Option<Vec<u8>>,
+    /// Accepted blocks are appended here for the Block History section
+    pub block_history: BlockHistory,
+    /// Hash and count solutions but never submit them, to check a setup
+    pub dry_run: bool,
+    /// Found blocks waiting to be submitted; further blocks are dropped (and
+    /// counted in `blocks_dropped_backpressure`) while the queue is full
+    pub submit_queue_depth: usize,
+    /// Pin thread `i` to logical CPU `cpu_affinity[i % len]`; `None` lets the OS
+    /// schedule the threads
+    pub cpu_affinity: Option<Vec<usize>>,
+    /// Configs are only created through `CpuMinerConfigBuilder`, which validates them
+    _validated: (),
 }
 
+impl CpuMinerConfig {
+    pub fn builder() -> CpuMinerConfigBuilder {
+        CpuMinerConfigBuilder::default()
+    }
+
+    /// One mining thread per physical core; HyperThreading siblings add little
+    pub fn default_threads() -> usize {
+        num_cpus::get_physical().max(1)
+    }
+}
+
+/// Builds a validated `CpuMinerConfig`
+pub struct CpuMinerConfigBuilder {
+    mining_addresses: Vec<String>,
+    threads: usize,
+    throttle: Option<Duration>,
+    poll_interval: Duration,
+    cpu_limit_percent: Option<u8>,
+    max_temp_c: Option<u8>,
+    extra_data: Option<Vec<u8>>,
+    block_history: BlockHistory,
+    dry_run: bool,
+    submit_queue_depth: usize,
+    cpu_affinity: Option<Vec<usize>>,
+}
+
+impl Default for CpuMinerConfigBuilder {
+    fn default() -> Self {
+        Self {
+            mining_addresses: Vec::new(),
+            threads: CpuMinerConfig::default_threads(),
+            throttle: None,
+            poll_interval: Duration::from_millis(50),
+            cpu_limit_percent: None,
+            max_temp_c: None,
+            extra_data: None,
+            block_history: BlockHistory::default(),
+            dry_run: false,
+            submit_queue_depth: SUBMIT_QUEUE_DEPTH,
+            cpu_affinity: None,
+        }
+    }
+}
+
+impl CpuMinerConfigBuilder {
+    pub fn mining_address(mut self, address: impl Into<String>) -> Self {
+        self.mining_addresses = vec![address.into()];
+        self
+    }
+
+    /// Rotate through several addresses, see `CpuMinerConfig::mining_addresses`
+    pub fn mining_addresses(mut self, addresses: Vec<String>) -> Self {
+        self.mining_addresses = addresses;
+        self
+    }
+
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    pub fn throttle(mut self, throttle: Duration) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn cpu_limit_percent(mut self, percent: u8) -> Self {
+        self.cpu_limit_percent = Some(percent);
+        self
+    }
+
+    /// See `CpuMinerConfig::max_temp_c`
+    pub fn max_temp_c(mut self, celsius: u8) -> Self {
+        self.max_temp_c = Some(celsius);
+        self
+    }
+
+    pub fn extra_data(mut self, extra_data: Vec<u8>) -> Self {
+        self.extra_data = Some(extra_data);
+        self
+    }
+
+    pub fn block_history(mut self, history: BlockHistory) -> Self {
+        self.block_history = history;
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn submit_queue_depth(mut self, depth: usize) -> Self {
+        self.submit_queue_depth = depth;
+        self
+    }
+
+    /// See `CpuMinerConfig::cpu_affinity`
+    pub fn cpu_affinity(mut self, cpus: Vec<usize>) -> Self {
+        self.cpu_affinity = Some(cpus);
+        self
+    }
+
+    pub fn build(self) -> anyhow::Result<CpuMinerConfig> {
+        let mining_addresses: Vec<String> = self
+            .mining_addresses
+            .iter()
+            .map(|address| address.trim())
+            .filter(|address| !address.is_empty())
+            .map(str::to_string)
+            .collect();
+        let Some(mining_address) = mining_addresses.first().cloned() else {
+            return Err(anyhow::anyhow!("Mining address is required"));
+        };
+        if self.threads == 0 {
+            return Err(anyhow::anyhow!("At least one mining thread is required"));
+        }
+        if self.poll_interval.is_zero() {
+            return Err(anyhow::anyhow!("Template poll interval must be positive"));
+        }
+        if self.submit_queue_depth == 0 {
+            return Err(anyhow::anyhow!("Submit queue depth must be positive"));
+        }
+        let cpu_affinity = self.cpu_affinity.filter(|cpus| !cpus.is_empty());
+        if let Some(cpus) = &cpu_affinity {
+            let available = core_affinity::get_core_ids().unwrap_or_default();
+            if let Some(cpu) = cpus
+                .iter()
+                .find(|&&cpu| !available.iter().any(|core| core.id == cpu))
+            {
+                return Err(anyhow::anyhow!(
+                    "CPU {} is not available to pin a thread to",
+                    cpu
+                ));
+            }
+        }
+        if self
+            .cpu_limit_percent
+            .is_some_and(|percent| percent == 0 || percent > 100)
+        {
+            return Err(anyhow::anyhow!("CPU limit must be between 1 and 100%"));
+        }
+        if self
+            .extra_data
+            .as_ref()
+            .is_some_and(|data| data.len() > MAX_EXTRA_DATA_LEN)
+        {
+            return Err(anyhow::anyhow!(
+                "Extra data is longer than {} bytes",
+                MAX_EXTRA_DATA_LEN
+            ));
+        }
+        Ok(CpuMinerConfig {
+            mining_address,
+            mining_addresses,
+            threads: self.threads,
+            throttle: self.throttle,
+            template_poll_interval: self.poll_interval,
+            cpu_limit_percent: self.cpu_limit_percent,
+            max_temp_c: self.max_temp_c,
+            extra_data: self.extra_data,
+            block_history: self.block_history,
+            dry_run: self.dry_run,
+            submit_queue_depth: self.submit_queue_depth,
+            cpu_affinity,
+            _validated: (),
+        })
+    }
+}
+
+/// Default `CpuMinerConfig::submit_queue_depth`
+pub const SUBMIT_QUEUE_DEPTH: usize = 32;
+
+/// Coinbase extra data sent with every template request unless configured
+pub const DEFAULT_EXTRA_DATA: &[u8] = b"rkstratum-cpu-miner";
+/// Longest custom extra data the UI accepts
+pub const MAX_EXTRA_DATA_LEN: usize = 64;
+
+/// A block the node accepted
+#[derive(Debug, Clone)]
+pub struct BlockFoundEntry {
+    pub timestamp: DateTime<Local>,
+    pub nonce: u64,
+    /// Id of the template the block was mined from, counted from 0 per session
+    pub template_id: u64,
+}
+
+/// Most recent accepted blocks, oldest first, shared between the miner and the GUI
+pub type BlockHistory = Arc<Mutex<Vec<BlockFoundEntry>>>;
+
+/// Entries kept in a `BlockHistory`
+pub const BLOCK_HISTORY_LEN: usize = 50;
+
 pub struct CpuMinerMetrics {
     pub hashes_tried: Arc<AtomicU64>,
+    /// Every submit attempt, whatever the outcome
     pub blocks_submitted: Arc<AtomicU64>,
     pub blocks_accepted: Arc<AtomicU64>,
+    /// Blocks the node answered with a rejection report
+    pub blocks_rejected: Arc<AtomicU64>,
+    /// `Debug` form of the most recent rejection report
+    pub last_rejection_reason: Arc<Mutex<Option<String>>>,
+    /// Failed `get_block_template` calls
+    pub template_fetch_errors: Arc<AtomicU64>,
+    /// Solutions found in dry-run mode, which are logged instead of submitted
+    pub blocks_found_not_submitted: Arc<AtomicU64>,
+    /// Blocks lost while stopping: found after the submit task exited, or
+    /// failed to submit once shutdown had started
+    pub blocks_dropped_on_shutdown: Arc<AtomicU64>,
+    /// Blocks lost because the submit queue was full when they were found
+    pub blocks_dropped_backpressure: Arc<AtomicU64>,
 }
 
 impl Default for CpuMinerMetrics {
@@ -35,6 +274,12 @@ impl Default for CpuMinerMetrics {
             hashes_tried: Arc::new(AtomicU64::new(0)),
             blocks_submitted: Arc::new(AtomicU64::new(0)),
             blocks_accepted: Arc::new(AtomicU64::new(0)),
+            blocks_rejected: Arc::new(AtomicU64::new(0)),
+            last_rejection_reason: Arc::new(Mutex::new(None)),
+            template_fetch_errors: Arc::new(AtomicU64::new(0)),
+            blocks_found_not_submitted: Arc::new(AtomicU64::new(0)),
+            blocks_dropped_on_shutdown: Arc::new(AtomicU64::new(0)),
+            blocks_dropped_backpressure: Arc::new(AtomicU64::new(0)),
         }
     }
 }
@@ -46,9 +291,28 @@ struct Work {
     pow_state: Arc<PowState>,
 }
 
+/// Monotonic counter identifying each published work item. Kept distinct from
+/// other u64s (nonces, work ids) so they cannot be compared by accident.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WorkVersion(u64);
+
+impl WorkVersion {
+    fn initial() -> Self {
+        Self(0)
+    }
+
+    fn wrapping_next(self) -> Self {
+        Self(self.0.wrapping_add(1))
+    }
+
+    fn changed_since(self, last: Self) -> bool {
+        self != last
+    }
+}
+
 struct WorkSlot {
     work: Option<Work>,
-    version: u64,
+    version: WorkVersion,
 }
 
 struct SharedWork {
@@ -61,7 +325,7 @@ impl SharedWork {
         Self {
             slot: Mutex::new(WorkSlot {
                 work: None,
-                version: 0,
+                version: WorkVersion::initial(),
             }),
             cv: Condvar::new(),
         }
@@ -69,17 +333,21 @@ impl SharedWork {
 
     fn publish(&self, work: Work) {
         let mut slot = self.slot.lock();
-        slot.version = slot.version.wrapping_add(1);
+        slot.version = slot.version.wrapping_next();
         slot.work = Some(work);
         self.cv.notify_all();
     }
 
-    fn wait_for_update(&self, last_seen: u64, shutdown_flag: &AtomicBool) -> (u64, Option<Work>) {
+    fn wait_for_update(
+        &self,
+        last_seen: WorkVersion,
+        shutdown_flag: &AtomicBool,
+    ) -> (WorkVersion, Option<Work>) {
         let mut slot = self.slot.lock();
-        while slot.version == last_seen && !shutdown_flag.load(Ordering::Acquire) {
+        while !slot.version.changed_since(last_seen) && !shutdown_flag.load(Ordering::Acquire) {
             self.cv.wait(&mut slot);
         }
-        if shutdown_flag.load(Ordering::Acquire) && slot.version == last_seen {
+        if shutdown_flag.load(Ordering::Acquire) && !slot.version.changed_since(last_seen) {
             return (last_seen, None);
         }
         (
@@ -98,13 +366,232 @@ impl SharedWork {
     }
 }
 
+/// How often the CPU limiter re-measures usage
+const CPU_LIMIT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Lowest combined hashrate the CPU limiter throttles down to, so the miner
+/// always keeps producing samples it can adjust against
+const MIN_LIMITED_HASHRATE: f64 = 1_000.0;
+
+/// Longest single sleep of a rate-limited thread; it re-checks shutdown and new
+/// work in between
+const RATE_LIMIT_SLEEP_SLICE: Duration = Duration::from_millis(50);
+
+/// How far below `CpuMinerConfig::max_temp_c` the CPU has to cool before the
+/// limiter lets the hashrate rise again
+const THERMAL_HYSTERESIS_C: f32 = 5.0;
+
+/// Hashrate multiplier applied every `CPU_LIMIT_INTERVAL` while the CPU is too hot
+const THERMAL_BACKOFF: f64 = 0.7;
+
+/// Adjust `rate_limit` (target nanoseconds per hash, all threads combined; 0 means
+/// unlimited) so that the miner's own CPU usage converges to `limit_percent`. Each
+/// step scales the current hashrate by `target / usage`, damped so the limit doesn't
+/// oscillate. Load from other programs is not counted. While the hottest sensor is
+/// above `max_temp_c` the hashrate is cut by `THERMAL_BACKOFF` every step instead,
+/// until the CPU has cooled by `THERMAL_HYSTERESIS_C`.
+async fn run_cpu_limiter(
+    limit_percent: Option<u8>,
+    max_temp_c: Option<u8>,
+    metrics: Arc<CpuMinerMetrics>,
+    rate_limit: Arc<AtomicU64>,
+    shutdown_flag: Arc<AtomicBool>,
+) {
+    let target = limit_percent.map(|limit| f64::from(limit.clamp(1, 100)));
+    let mut load = ProcessLoad::new();
+    let mut temperature = max_temp_c.map(|_| CpuTemperature::new());
+    let mut too_hot = false;
+    let mut last = (Instant::now(), metrics.hashes_tried.load(Ordering::Relaxed));
+    let mut ticker = tokio::time::interval(CPU_LIMIT_INTERVAL);
+    ticker.tick().await;
+    while !shutdown_flag.load(Ordering::Acquire) {
+        ticker.tick().await;
+        let now = (Instant::now(), metrics.hashes_tried.load(Ordering::Relaxed));
+        let secs = now.0.duration_since(last.0).as_secs_f64();
+        let hashes_per_sec = now.1.saturating_sub(last.1) as f64 / secs.max(f64::EPSILON);
+        last = now;
+        let usage = f64::from(load.cpu_usage());
+
+        if let (Some(max), Some(temp)) = (
+            max_temp_c.map(f32::from),
+            temperature.as_mut().and_then(CpuTemperature::max_celsius),
+        ) {
+            if temp > max && !too_hot {
+                tracing::warn!(
+                    "[Miner] CPU at {:.0}°C, above the {:.0}°C limit; slowing down",
+                    temp,
+                    max
+                );
+                too_hot = true;
+            } else if temp < max - THERMAL_HYSTERESIS_C && too_hot {
+                tracing::info!("[Miner] CPU cooled to {:.0}°C; speeding up again", temp);
+                too_hot = false;
+            }
+        }
+
+        let ns_per_hash = rate_limit.load(Ordering::Relaxed);
+        let scale = match target {
+            _ if too_hot => THERMAL_BACKOFF,
+            Some(target) => {
+                if ns_per_hash == 0 && usage <= target {
+                    // Already under the limit at full speed
+                    continue;
+                }
+                if usage > 0.0 {
+                    (target / usage).clamp(0.5, 1.5)
+                } else {
+                    1.5
+                }
+            }
+            // Only the thermal limit slowed the miner down; ease back to full speed
+            None if ns_per_hash == 0 => continue,
+            None => 1.5,
+        };
+        // Below the `BATCH_SIZE` flush rate a sample can read 0; fall back to the
+        // rate currently allowed so the limit can still be raised again
+        let current_rate = if hashes_per_sec > 0.0 {
+            hashes_per_sec
+        } else if ns_per_hash > 0 {
+            1e9 / ns_per_hash as f64
+        } else {
+            continue;
+        };
+        let target_rate = (current_rate * scale).max(MIN_LIMITED_HASHRATE);
+        if target.is_none()
+            && !too_hot
+            && hashes_per_sec > 0.0
+            && target_rate > 2.0 * hashes_per_sec
+        {
+            // The threads no longer reach the allowed rate, so it no longer limits them
+            rate_limit.store(0, Ordering::Relaxed);
+            continue;
+        }
+        rate_limit.store((1e9 / target_rate) as u64, Ordering::Relaxed);
+    }
+}
+
+/// A running mining session
+pub struct MinerHandle {
+    metrics: Arc<CpuMinerMetrics>,
+    shutdown_tx: watch::Sender<bool>,
+    shutdown_flag: Arc<AtomicBool>,
+    work: Arc<SharedWork>,
+    threads: Vec<std::thread::JoinHandle<()>>,
+    /// Template and submit tasks
+    tasks: Vec<tokio::task::JoinHandle<()>>,
+    mining_addresses: Arc<[String]>,
+    /// Index into `mining_addresses` of the address templates are requested for
+    current_address_index: Arc<AtomicUsize>,
+}
+
+impl MinerHandle {
+    pub fn metrics(&self) -> &Arc<CpuMinerMetrics> {
+        &self.metrics
+    }
+
+    /// False once stopped or if every mining thread has exited
+    pub fn is_running(&self) -> bool {
+        !self.shutdown_flag.load(Ordering::Acquire)
+            && self.threads.iter().any(|thread| !thread.is_finished())
+    }
+
+    pub fn thread_count(&self) -> usize {
+        self.threads.len()
+    }
+
+    /// Address the current block templates pay to
+    pub fn current_mining_address(&self) -> &str {
+        let index = self.current_address_index.load(Ordering::Relaxed);
+        &self.mining_addresses[index % self.mining_addresses.len()]
+    }
+
+    /// Signal shutdown and wait for the mining threads and the template and
+    /// submit tasks to exit. Blocks still queued for submission are sent first.
+    pub fn stop(self) -> impl std::future::Future<Output = ()> {
+        let _ = self.shutdown_tx.send(true);
+        // Don't rely on the shutdown watcher task still being scheduled
+        self.shutdown_flag.store(true, Ordering::Release);
+        self.work.notify_all();
+        let threads = self.threads;
+        let tasks = self.tasks;
+        async move {
+            let _ = tokio::task::spawn_blocking(move || {
+                for thread in threads {
+                    let _ = thread.join();
+                }
+            })
+            .await;
+            for task in tasks {
+                let _ = task.await;
+            }
+        }
+    }
+}
+
+/// Worker threads of the miner runtime unless `KASPA_MINER_ASYNC_THREADS` says otherwise
+pub const DEFAULT_MINER_ASYNC_THREADS: usize = 2;
+
+/// Start the runtime the miner's template and submit tasks run on, kept apart from
+/// the GUI's so they don't wait behind UI work. `KASPA_MINER_RUNTIME_FLAVOR` picks
+/// `multi_thread` (the default, with `KASPA_MINER_ASYNC_THREADS` workers) or
+/// `current_thread`. The runtime is driven by its own thread until the process exits.
+pub fn start_miner_runtime() -> std::io::Result<Handle> {
+    let runtime = miner_runtime_builder()
+        .thread_name("kaspa-miner-async")
+        .enable_all()
+        .build()?;
+    let handle = runtime.handle().clone();
+    std::thread::Builder::new()
+        .name("kaspa-miner-runtime".to_string())
+        .spawn(move || runtime.block_on(std::future::pending::<()>()))?;
+    Ok(handle)
+}
+
+fn miner_runtime_builder() -> tokio::runtime::Builder {
+    let flavor = std::env::var("KASPA_MINER_RUNTIME_FLAVOR").unwrap_or_default();
+    match flavor.trim() {
+        "current_thread" => return tokio::runtime::Builder::new_current_thread(),
+        "" | "multi_thread" => {}
+        other => tracing::warn!(
+            "Ignoring KASPA_MINER_RUNTIME_FLAVOR={}: expected multi_thread or current_thread",
+            other
+        ),
+    }
+    let worker_threads = match std::env::var("KASPA_MINER_ASYNC_THREADS") {
+        Ok(value) => match value.trim().parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                tracing::warn!(
+                    "Ignoring KASPA_MINER_ASYNC_THREADS={}: expected a positive number",
+                    value
+                );
+                DEFAULT_MINER_ASYNC_THREADS
+            }
+        },
+        Err(_) => DEFAULT_MINER_ASYNC_THREADS,
+    };
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.worker_threads(worker_threads);
+    builder
+}
+
+/// Start mining, spawning the session's async tasks on `runtime`
 pub fn start_cpu_miner(
     kaspa_api: Arc<KaspaApi>,
     config: CpuMinerConfig,
-) -> Result<(Arc<CpuMinerMetrics>, watch::Sender<bool>), anyhow::Error> {
-    if config.mining_address.trim().is_empty() {
+    runtime: &Handle,
+) -> Result<MinerHandle, anyhow::Error> {
+    if config.mining_addresses.is_empty() {
         return Err(anyhow::anyhow!("mining address is required"));
     }
+    let address_count = config.mining_addresses.len();
+    for (i, address) in config.mining_addresses.iter().enumerate() {
+        kaspa_api
+            .check_address_network(address)
+            .with_context(|| format!("Mining address {} of {} rejected", i + 1, address_count))?;
+    }
+    let mining_addresses: Arc<[String]> = config.mining_addresses.clone().into();
+    let current_address_index = Arc::new(AtomicUsize::new(0));
 
     let work = Arc::new(SharedWork::new());
     let shutdown_flag = Arc::new(AtomicBool::new(false));
@@ -113,7 +600,7 @@ pub fn start_cpu_miner(
     let shutdown_flag_clone = Arc::clone(&shutdown_flag);
     let work_clone = Arc::clone(&work);
     let mut shutdown_rx_clone = shutdown_rx.clone();
-    tokio::spawn(async move {
+    runtime.spawn(async move {
         let _ = shutdown_rx_clone.wait_for(|v| *v).await;
         shutdown_flag_clone.store(true, Ordering::Release);
         work_clone.notify_all();
@@ -122,79 +609,105 @@ pub fn start_cpu_miner(
     let metrics = Arc::new(CpuMinerMetrics::default());
     let metrics_submit = Arc::clone(&metrics);
 
-    let (submit_tx, mut submit_rx) = mpsc::unbounded_channel::<RpcRawBlock>();
+    // Tasks are named through their tracing span: `tokio::task::Builder` needs the
+    // `tokio_unstable` cfg, which this crate doesn't build with
+    //
+    // Blocks are sent with the id of the template they were mined from
+    // Bounded so a slow node can't make found blocks pile up without limit
+    let (submit_tx, mut submit_rx) =
+        mpsc::channel::<(u64, RpcRawBlock)>(config.submit_queue_depth.max(1));
     let kaspa_api_submit = Arc::clone(&kaspa_api);
     let shutdown_flag_submit = Arc::clone(&shutdown_flag);
-    tokio::spawn(async move {
-        while let Some(rpc_block) = submit_rx.recv().await {
-            if shutdown_flag_submit.load(Ordering::Acquire) {
-                break;
-            }
-            let nonce = rpc_block.header.nonce;
-            let res = kaspa_api_submit.submit_rpc_block(rpc_block).await;
-            match res {
-                Ok(response) => {
-                    if response.report.is_success() {
-                        metrics_submit
-                            .blocks_submitted
-                            .fetch_add(1, Ordering::Relaxed);
-                        metrics_submit
-                            .blocks_accepted
-                            .fetch_add(1, Ordering::Relaxed);
-                        tracing::info!("[Miner] Block accepted by node (nonce: {})", nonce);
-                    } else {
-                        tracing::warn!("[Miner] Block rejected by node: {:?}", response.report);
-                    }
+    let block_history = Arc::clone(&config.block_history);
+    let address_index_submit = Arc::clone(&current_address_index);
+    let submit_task = runtime.spawn(
+        async move {
+            // Runs until every mining thread has dropped its sender, so blocks still
+            // queued when shutdown starts are submitted rather than lost
+            let mut drained = 0u64;
+            while let Some((template_id, rpc_block)) = submit_rx.recv().await {
+                let shutting_down = shutdown_flag_submit.load(Ordering::Acquire);
+                if shutting_down {
+                    drained += 1;
                 }
-                Err(e) => {
-                    tracing::warn!("[Miner] Submit block failed: {e}");
+                let nonce = rpc_block.header.nonce;
+                let res = kaspa_api_submit.submit_rpc_block(rpc_block).await;
+                metrics_submit
+                    .blocks_submitted
+                    .fetch_add(1, Ordering::Relaxed);
+                match res {
+                    Ok(response) => {
+                        if response.report.is_success() {
+                            metrics_submit
+                                .blocks_accepted
+                                .fetch_add(1, Ordering::Relaxed);
+                            tracing::info!("[Miner] Block accepted by node (nonce: {})", nonce);
+                            // The next template pays to the next address
+                            address_index_submit.fetch_add(1, Ordering::Relaxed);
+                            let mut history = block_history.lock();
+                            if history.len() >= BLOCK_HISTORY_LEN {
+                                history.remove(0);
+                            }
+                            history.push(BlockFoundEntry {
+                                timestamp: Local::now(),
+                                nonce,
+                                template_id,
+                            });
+                        } else {
+                            metrics_submit
+                                .blocks_rejected
+                                .fetch_add(1, Ordering::Relaxed);
+                            *metrics_submit.last_rejection_reason.lock() =
+                                Some(format!("{:?}", response.report));
+                            tracing::warn!("[Miner] Block rejected by node: {:?}", response.report);
+                        }
+                    }
+                    Err(e) => {
+                        if shutting_down {
+                            metrics_submit
+                                .blocks_dropped_on_shutdown
+                                .fetch_add(1, Ordering::Relaxed);
+                        }
+                        tracing::warn!("[Miner] Submit block failed: {e}");
+                    }
                 }
             }
+            let dropped = metrics_submit
+                .blocks_dropped_on_shutdown
+                .load(Ordering::Relaxed);
+            if drained > 0 || dropped > 0 {
+                tracing::info!(
+                    "[Miner] Submitted {} queued blocks during shutdown, {} dropped",
+                    drained,
+                    dropped
+                );
+            }
         }
-    });
+        .instrument(tracing::info_span!("task", name = "kaspa-miner-submit")),
+    );
 
     let work_publisher = Arc::clone(&work);
     let kaspa_api_templates = Arc::clone(&kaspa_api);
-    let mining_address = config.mining_address.clone();
+    let mining_addresses_templates = Arc::clone(&mining_addresses);
+    let address_index_templates = Arc::clone(&current_address_index);
+    let current_address = move || {
+        let index = address_index_templates.load(Ordering::Relaxed);
+        mining_addresses_templates[index % mining_addresses_templates.len()].clone()
+    };
+    let extra_data = config
+        .extra_data
+        .clone()
+        .unwrap_or_else(|| DEFAULT_EXTRA_DATA.to_vec());
     let poll = config.template_poll_interval;
     let shutdown_flag_templates = Arc::clone(&shutdown_flag);
     let next_id = Arc::new(AtomicU64::new(0));
     let next_id_templates = Arc::clone(&next_id);
-    tokio::spawn(async move {
-        // Fetch template immediately on startup
-        match kaspa_api_templates
-            .get_block_template_rpc(&mining_address)
-            .await
-        {
-            Ok((block, rpc_block)) => {
-                let id = next_id_templates.fetch_add(1, Ordering::Relaxed);
-                let header = block.header.clone();
-                let pow_state = Arc::new(PowState::new(&header));
-                work_publisher.publish(Work {
-                    id,
-                    block,
-                    rpc_block,
-                    pow_state,
-                });
-            }
-            Err(e) => {
-                tracing::warn!("[Miner] Initial get_block_template failed: {e}");
-            }
-        }
-
-        let mut interval = tokio::time::interval(poll);
-        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-        loop {
-            if shutdown_flag_templates.load(Ordering::Acquire) {
-                break;
-            }
-            interval.tick().await;
-            if shutdown_flag_templates.load(Ordering::Acquire) {
-                break;
-            }
-
+    let metrics_templates = Arc::clone(&metrics);
+    let template_task = runtime.spawn(
+        async move {
+            // Fetch template immediately on startup
             match kaspa_api_templates
-                .get_block_template_rpc(&mining_address)
+                .get_block_template_rpc(&current_address(), &extra_data)
                 .await
             {
                 Ok((block, rpc_block)) => {
@@ -209,126 +722,265 @@ pub fn start_cpu_miner(
                     });
                 }
                 Err(e) => {
-                    tracing::warn!("[Miner] Get_block_template failed: {e}");
+                    metrics_templates
+                        .template_fetch_errors
+                        .fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!("[Miner] Initial get_block_template failed: {e}");
+                }
+            }
+
+            let mut interval = tokio::time::interval(poll);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                if shutdown_flag_templates.load(Ordering::Acquire) {
+                    break;
+                }
+                interval.tick().await;
+                if shutdown_flag_templates.load(Ordering::Acquire) {
+                    break;
+                }
+
+                match kaspa_api_templates
+                    .get_block_template_rpc(&current_address(), &extra_data)
+                    .await
+                {
+                    Ok((block, rpc_block)) => {
+                        let id = next_id_templates.fetch_add(1, Ordering::Relaxed);
+                        let header = block.header.clone();
+                        let pow_state = Arc::new(PowState::new(&header));
+                        work_publisher.publish(Work {
+                            id,
+                            block,
+                            rpc_block,
+                            pow_state,
+                        });
+                    }
+                    Err(e) => {
+                        metrics_templates
+                            .template_fetch_errors
+                            .fetch_add(1, Ordering::Relaxed);
+                        tracing::warn!("[Miner] Get_block_template failed: {e}");
+                    }
                 }
             }
         }
-    });
+        .instrument(tracing::info_span!("task", name = "kaspa-miner-templates")),
+    );
 
     let threads = config.threads.max(1);
     let throttle = config.throttle;
+    let global_rate_limit = Arc::new(AtomicU64::new(0));
+    if config.cpu_limit_percent.is_some() || config.max_temp_c.is_some() {
+        runtime.spawn(run_cpu_limiter(
+            config.cpu_limit_percent,
+            config.max_temp_c,
+            Arc::clone(&metrics),
+            Arc::clone(&global_rate_limit),
+            Arc::clone(&shutdown_flag),
+        ));
+    }
 
     const BATCH_SIZE: u64 = 1000;
     const CHECK_WORK_INTERVAL: u64 = 200;
 
+    let mut thread_handles = Vec::with_capacity(threads);
     for thread_idx in 0..threads {
         let work = Arc::clone(&work);
         let submit_tx = submit_tx.clone();
+        let dry_run = config.dry_run;
         let shutdown_flag = Arc::clone(&shutdown_flag);
         let metrics_threads = Arc::clone(&metrics);
+        let global_rate_limit = Arc::clone(&global_rate_limit);
+        let pinned_cpu = config
+            .cpu_affinity
+            .as_ref()
+            .map(|cpus| cpus[thread_idx % cpus.len()]);
+
+        let name = format!("kaspa-miner-{}", thread_idx);
+        let spawned = std::thread::Builder::new()
+            .name(name.clone())
+            .spawn(move || {
+                let _span = tracing::info_span!("thread", name = %name).entered();
+                // Keeps the thread's cache warm instead of migrating between cores
+                if let Some(cpu) = pinned_cpu {
+                    if !core_affinity::set_for_current(core_affinity::CoreId { id: cpu }) {
+                        tracing::warn!(
+                            "[Miner] Failed to pin thread {} to CPU {}",
+                            thread_idx,
+                            cpu
+                        );
+                    }
+                }
+                let mut last_version = WorkVersion::initial();
+                let nonce_step = threads as u64;
+                let mut nonce = thread_idx as u64;
+                let mut local_hash_count = 0u64;
 
-        std::thread::spawn(move || {
-            let mut last_version = 0u64;
-            let nonce_step = threads as u64;
-            let mut nonce = thread_idx as u64;
-            let mut local_hash_count = 0u64;
+                loop {
+                    if shutdown_flag.load(Ordering::Acquire) {
+                        break;
+                    }
 
-            loop {
-                if shutdown_flag.load(Ordering::Acquire) {
-                    break;
-                }
+                    let (ver, maybe_work) = work.wait_for_update(last_version, &shutdown_flag);
+                    last_version = ver;
 
-                let (ver, maybe_work) = work.wait_for_update(last_version, &shutdown_flag);
-                last_version = ver;
+                    let Some(w) = maybe_work else {
+                        continue;
+                    };
 
-                let Some(w) = maybe_work else {
-                    continue;
-                };
+                    let mut hashes_since_work_check = 0u64;
+                    let mut batch_started = Instant::now();
 
-                let mut hashes_since_work_check = 0u64;
+                    loop {
+                        local_hash_count += 1;
+                        hashes_since_work_check += 1;
 
-                loop {
-                    local_hash_count += 1;
-                    hashes_since_work_check += 1;
+                        let current_nonce = nonce;
+                        nonce = nonce.wrapping_add(nonce_step);
 
-                    let current_nonce = nonce;
-                    nonce = nonce.wrapping_add(nonce_step);
+                        let (passed, _) = w.pow_state.check_pow(current_nonce);
+                        if passed {
+                            if local_hash_count > 0 {
+                                metrics_threads
+                                    .hashes_tried
+                                    .fetch_add(local_hash_count, Ordering::Relaxed);
+                                local_hash_count = 0;
+                            }
 
-                    let (passed, _) = w.pow_state.check_pow(current_nonce);
-                    if passed {
-                        if local_hash_count > 0 {
-                            metrics_threads
-                                .hashes_tried
-                                .fetch_add(local_hash_count, Ordering::Relaxed);
-                            local_hash_count = 0;
-                        }
+                            if dry_run {
+                                metrics_threads
+                                    .blocks_found_not_submitted
+                                    .fetch_add(1, Ordering::Relaxed);
+                                tracing::info!(
+                                    "[DryRun] Would submit block with nonce {}",
+                                    current_nonce
+                                );
+                            } else {
+                                let mined_rpc_block = RpcRawBlock {
+                                    header: {
+                                        let mut h = w.rpc_block.header.clone();
+                                        h.nonce = current_nonce;
+                                        h
+                                    },
+                                    transactions: w.rpc_block.transactions.clone(),
+                                };
+                                // Never block the mining thread on a full queue
+                                match submit_tx.try_send((w.id, mined_rpc_block)) {
+                                    Ok(()) => {}
+                                    Err(mpsc::error::TrySendError::Full(_)) => {
+                                        metrics_threads
+                                            .blocks_dropped_backpressure
+                                            .fetch_add(1, Ordering::Relaxed);
+                                        tracing::warn!(
+                                            "[Miner] Submit queue full, block dropped (nonce: {})",
+                                            current_nonce
+                                        );
+                                    }
+                                    // The submit task has already exited, so we are
+                                    // shutting down
+                                    Err(mpsc::error::TrySendError::Closed(_)) => {
+                                        metrics_threads
+                                            .blocks_dropped_on_shutdown
+                                            .fetch_add(1, Ordering::Relaxed);
+                                    }
+                                }
+                            }
 
-                        let mined_rpc_block = RpcRawBlock {
-                            header: {
-                                let mut h = w.rpc_block.header.clone();
-                                h.nonce = current_nonce;
-                                h
-                            },
-                            transactions: w.rpc_block.transactions.clone(),
-                        };
-                        let _ = submit_tx.send(mined_rpc_block);
-
-                        if let Some(slot) = work.slot.try_lock() {
-                            if slot.version != last_version {
-                                drop(slot);
-                                break;
+                            if let Some(slot) = work.slot.try_lock() {
+                                if slot.version.changed_since(last_version) {
+                                    drop(slot);
+                                    break;
+                                }
                             }
+                            hashes_since_work_check = 0;
                         }
-                        hashes_since_work_check = 0;
-                    }
 
-                    if local_hash_count >= BATCH_SIZE {
-                        metrics_threads
-                            .hashes_tried
-                            .fetch_add(BATCH_SIZE, Ordering::Relaxed);
-                        local_hash_count -= BATCH_SIZE;
-                    }
-
-                    if let Some(d) = throttle {
-                        if (hashes_since_work_check & 127) == 0 {
-                            std::thread::sleep(d);
+                        if local_hash_count >= BATCH_SIZE {
+                            metrics_threads
+                                .hashes_tried
+                                .fetch_add(BATCH_SIZE, Ordering::Relaxed);
+                            local_hash_count -= BATCH_SIZE;
                         }
-                    }
 
-                    if hashes_since_work_check >= CHECK_WORK_INTERVAL {
-                        if shutdown_flag.load(Ordering::Acquire) {
-                            if local_hash_count > 0 {
-                                metrics_threads
-                                    .hashes_tried
-                                    .fetch_add(local_hash_count, Ordering::Relaxed);
+                        if let Some(d) = throttle {
+                            if (hashes_since_work_check & 127) == 0 {
+                                std::thread::sleep(d);
                             }
-                            return;
                         }
 
-                        let slot = work.slot.lock();
-                        if slot.version != last_version {
+                        if hashes_since_work_check >= CHECK_WORK_INTERVAL {
+                            if shutdown_flag.load(Ordering::Acquire) {
+                                if local_hash_count > 0 {
+                                    metrics_threads
+                                        .hashes_tried
+                                        .fetch_add(local_hash_count, Ordering::Relaxed);
+                                }
+                                return;
+                            }
+
+                            let slot = work.slot.lock();
+                            if slot.version.changed_since(last_version) {
+                                drop(slot);
+                                if local_hash_count > 0 {
+                                    metrics_threads
+                                        .hashes_tried
+                                        .fetch_add(local_hash_count, Ordering::Relaxed);
+                                    local_hash_count = 0;
+                                }
+                                break;
+                            }
                             drop(slot);
-                            if local_hash_count > 0 {
-                                metrics_threads
-                                    .hashes_tried
-                                    .fetch_add(local_hash_count, Ordering::Relaxed);
-                                local_hash_count = 0;
+
+                            // Each thread gets an equal share of the combined hash budget
+                            let ns_per_hash = global_rate_limit.load(Ordering::Relaxed);
+                            if ns_per_hash > 0 {
+                                let budget = Duration::from_nanos(
+                                    ns_per_hash
+                                        .saturating_mul(threads as u64)
+                                        .saturating_mul(hashes_since_work_check),
+                                );
+                                // Sleep in slices so a long wait at a low rate still
+                                // reacts to shutdown and new work
+                                while let Some(remaining) =
+                                    budget.checked_sub(batch_started.elapsed())
+                                {
+                                    if remaining.is_zero()
+                                        || shutdown_flag.load(Ordering::Acquire)
+                                        || work.slot.lock().version.changed_since(last_version)
+                                    {
+                                        break;
+                                    }
+                                    std::thread::sleep(remaining.min(RATE_LIMIT_SLEEP_SLICE));
+                                }
                             }
-                            break;
+                            batch_started = Instant::now();
+                            hashes_since_work_check = 0;
                         }
-                        drop(slot);
-                        hashes_since_work_check = 0;
                     }
                 }
-            }
 
-            if local_hash_count > 0 {
-                metrics_threads
-                    .hashes_tried
-                    .fetch_add(local_hash_count, Ordering::Relaxed);
-            }
-        });
+                if local_hash_count > 0 {
+                    metrics_threads
+                        .hashes_tried
+                        .fetch_add(local_hash_count, Ordering::Relaxed);
+                }
+            });
+        match spawned {
+            Ok(handle) => thread_handles.push(handle),
+            Err(e) => tracing::error!("[Miner] Failed to start kaspa-miner-{}: {}", thread_idx, e),
+        }
     }
-
-    Ok((metrics, shutdown_tx))
+    // The threads hold the only senders now, so the submit channel closes once they exit
+    drop(submit_tx);
+
+    Ok(MinerHandle {
+        metrics,
+        shutdown_tx,
+        shutdown_flag,
+        work,
+        threads: thread_handles,
+        tasks: vec![submit_task, template_task],
+        mining_addresses,
+        current_address_index,
+    })
 }
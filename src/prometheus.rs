@@ -0,0 +1,122 @@
+use crate::CpuMinerMetrics;
+use anyhow::Result;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often the hashrate window is sampled
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Samples in the hashrate window, so the gauge averages the last 10 seconds
+const HASHRATE_WINDOW: usize = 10;
+
+/// Serve `/metrics` in the Prometheus text format on `0.0.0.0:port` from a
+/// background thread. The server runs until the process exits.
+pub fn serve(port: u16, metrics: Arc<CpuMinerMetrics>, started: Instant) -> Result<()> {
+    let server = tiny_http::Server::http(("0.0.0.0", port))
+        .map_err(|e| anyhow::anyhow!("Failed to listen on port {}: {}", port, e))?;
+    tracing::info!("Serving Prometheus metrics on :{}/metrics", port);
+
+    std::thread::Builder::new()
+        .name("kaspa-miner-metrics".to_string())
+        .spawn(move || {
+            let mut samples: VecDeque<(Instant, u64)> = VecDeque::with_capacity(HASHRATE_WINDOW);
+            loop {
+                if samples
+                    .back()
+                    .is_none_or(|(at, _)| at.elapsed() >= SAMPLE_INTERVAL)
+                {
+                    if samples.len() == HASHRATE_WINDOW {
+                        samples.pop_front();
+                    }
+                    samples
+                        .push_back((Instant::now(), metrics.hashes_tried.load(Ordering::Relaxed)));
+                }
+
+                let request = match server.recv_timeout(SAMPLE_INTERVAL) {
+                    Ok(Some(request)) => request,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        tracing::warn!("Metrics server stopped: {}", e);
+                        return;
+                    }
+                };
+                let response = if request.url() == "/metrics" {
+                    let body = render(&metrics, hashrate(&samples), started.elapsed());
+                    let content_type = tiny_http::Header::from_bytes(
+                        "Content-Type",
+                        "text/plain; version=0.0.4; charset=utf-8",
+                    )
+                    .expect("static header is valid");
+                    tiny_http::Response::from_string(body).with_header(content_type)
+                } else {
+                    tiny_http::Response::from_string("Not found").with_status_code(404)
+                };
+                if let Err(e) = request.respond(response) {
+                    tracing::debug!("Failed to answer metrics request: {}", e);
+                }
+            }
+        })?;
+    Ok(())
+}
+
+/// Hashes per second between the oldest and newest sample
+fn hashrate(samples: &VecDeque<(Instant, u64)>) -> f64 {
+    match (samples.front(), samples.back()) {
+        (Some((first_at, first)), Some((last_at, last))) if last_at > first_at => {
+            last.saturating_sub(*first) as f64 / (*last_at - *first_at).as_secs_f64()
+        }
+        _ => 0.0,
+    }
+}
+
+fn render(metrics: &CpuMinerMetrics, hashrate: f64, uptime: Duration) -> String {
+    let mut out = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        let _ = writeln!(out, "{} {}", name, value);
+    };
+    metric(
+        "kaspa_miner_hashes_total",
+        "counter",
+        "Hashes tried since the miner started",
+        metrics.hashes_tried.load(Ordering::Relaxed).to_string(),
+    );
+    metric(
+        "kaspa_miner_hashrate_hps",
+        "gauge",
+        "Hashrate over the last 10 seconds, in hashes per second",
+        format!("{:.2}", hashrate),
+    );
+    metric(
+        "kaspa_miner_blocks_submitted_total",
+        "counter",
+        "Blocks submitted to the node",
+        metrics.blocks_submitted.load(Ordering::Relaxed).to_string(),
+    );
+    metric(
+        "kaspa_miner_blocks_accepted_total",
+        "counter",
+        "Blocks the node accepted",
+        metrics.blocks_accepted.load(Ordering::Relaxed).to_string(),
+    );
+    metric(
+        "kaspa_miner_template_fetch_errors_total",
+        "counter",
+        "Failed block template requests",
+        metrics
+            .template_fetch_errors
+            .load(Ordering::Relaxed)
+            .to_string(),
+    );
+    metric(
+        "kaspa_miner_uptime_seconds",
+        "gauge",
+        "Seconds since mining started",
+        uptime.as_secs().to_string(),
+    );
+    out
+}
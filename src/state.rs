@@ -0,0 +1,280 @@
+use crate::miner::{start_cpu_miner, MinerHandle};
+use crate::ui::{Components, Theme};
+use crate::{CpuMinerConfig, CpuMinerMetrics, KaspaApi};
+use egui::{Color32, Ui};
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Handle;
+
+/// User intent produced by the state-specific controls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiAction {
+    Connect,
+    Disconnect,
+    Start,
+    Stop,
+    Pause,
+    Resume,
+}
+
+/// Connection and mining lifecycle. Each variant carries only the data that is
+/// valid in that state, so e.g. metrics cannot exist without a connection.
+pub enum MinerState {
+    Idle,
+    Connecting(ConnectingState),
+    Connected(ConnectedState),
+    Mining(MiningState),
+    Paused(PausedState),
+}
+
+/// A connect + sync running in the background
+pub struct ConnectingState {
+    pub address: String,
+    /// Filled in by the connect task; `None` while it is still running
+    pub result: Arc<Mutex<Option<Result<Arc<KaspaApi>, String>>>>,
+}
+
+pub struct ConnectedState {
+    pub api: Arc<KaspaApi>,
+}
+
+pub struct MiningState {
+    pub api: Arc<KaspaApi>,
+    pub handle: MinerHandle,
+    pub config: CpuMinerConfig,
+}
+
+/// Mining threads are stopped but the session's statistics stay on screen.
+/// Resuming starts a new miner with the same configuration.
+pub struct PausedState {
+    pub api: Arc<KaspaApi>,
+    pub metrics: Arc<CpuMinerMetrics>,
+    pub config: CpuMinerConfig,
+}
+
+/// Rendering that differs between states
+pub trait StateView {
+    /// Controls under the node address field
+    fn render_connection(&self, ui: &mut Ui) -> Option<UiAction>;
+    /// Controls at the bottom of the mining section
+    fn render_mining_controls(&self, ui: &mut Ui) -> Option<UiAction>;
+    /// Top bar indicators as `[node, mining]`
+    fn indicators(&self) -> [(Color32, &'static str); 2];
+}
+
+struct IdleView;
+
+impl StateView for IdleView {
+    fn render_connection(&self, ui: &mut Ui) -> Option<UiAction> {
+        ui.add(Components::teal_button("⚡ Connect"))
+            .clicked()
+            .then_some(UiAction::Connect)
+    }
+
+    fn render_mining_controls(&self, ui: &mut Ui) -> Option<UiAction> {
+        ui.add_enabled(false, Components::primary_button("▶ Start Mining"));
+        None
+    }
+
+    fn indicators(&self) -> [(Color32, &'static str); 2] {
+        [
+            (Theme::LIGHT_GRAY, "Node: Disconnected"),
+            (Theme::LIGHT_GRAY, "Mining: Stopped"),
+        ]
+    }
+}
+
+impl StateView for ConnectingState {
+    fn render_connection(&self, ui: &mut Ui) -> Option<UiAction> {
+        ui.horizontal(|ui| {
+            Components::spinner(ui, 20.0, Theme::PRIMARY_TEAL);
+            ui.add_space(8.0);
+            ui.label(
+                egui::RichText::new(format!("Connecting to {}...", self.address))
+                    .color(Theme::LIGHT_GRAY),
+            );
+        });
+        ui.add_space(8.0);
+        Components::progress_bar(ui, "Waiting for node sync...");
+        None
+    }
+
+    fn render_mining_controls(&self, ui: &mut Ui) -> Option<UiAction> {
+        ui.add_enabled(false, Components::primary_button("▶ Start Mining"));
+        None
+    }
+
+    fn indicators(&self) -> [(Color32, &'static str); 2] {
+        [
+            (Theme::ACCENT_TEAL, "Node: Connecting"),
+            (Theme::LIGHT_GRAY, "Mining: Stopped"),
+        ]
+    }
+}
+
+impl StateView for ConnectedState {
+    fn render_connection(&self, ui: &mut Ui) -> Option<UiAction> {
+        disconnect_button(ui)
+    }
+
+    fn render_mining_controls(&self, ui: &mut Ui) -> Option<UiAction> {
+        ui.add(Components::primary_button("▶ Start Mining"))
+            .clicked()
+            .then_some(UiAction::Start)
+    }
+
+    fn indicators(&self) -> [(Color32, &'static str); 2] {
+        [
+            (Theme::GREEN, "Node: Connected"),
+            (Theme::LIGHT_GRAY, "Mining: Stopped"),
+        ]
+    }
+}
+
+impl StateView for MiningState {
+    fn render_connection(&self, ui: &mut Ui) -> Option<UiAction> {
+        disconnect_button(ui)
+    }
+
+    fn render_mining_controls(&self, ui: &mut Ui) -> Option<UiAction> {
+        let mut action = None;
+        if ui.add(Components::teal_button("⏸ Pause")).clicked() {
+            action = Some(UiAction::Pause);
+        }
+        ui.add_space(10.0);
+        if ui.add(Components::danger_button("⏹ Stop Mining")).clicked() {
+            action = Some(UiAction::Stop);
+        }
+        action
+    }
+
+    fn indicators(&self) -> [(Color32, &'static str); 2] {
+        [
+            (Theme::GREEN, "Node: Connected"),
+            (Theme::GREEN, "Mining: Active"),
+        ]
+    }
+}
+
+impl StateView for PausedState {
+    fn render_connection(&self, ui: &mut Ui) -> Option<UiAction> {
+        disconnect_button(ui)
+    }
+
+    fn render_mining_controls(&self, ui: &mut Ui) -> Option<UiAction> {
+        let mut action = None;
+        if ui.add(Components::primary_button("▶ Resume")).clicked() {
+            action = Some(UiAction::Resume);
+        }
+        ui.add_space(10.0);
+        if ui.add(Components::danger_button("⏹ Stop Mining")).clicked() {
+            action = Some(UiAction::Stop);
+        }
+        action
+    }
+
+    fn indicators(&self) -> [(Color32, &'static str); 2] {
+        [
+            (Theme::GREEN, "Node: Connected"),
+            (Theme::ACCENT_TEAL, "Mining: Paused"),
+        ]
+    }
+}
+
+fn disconnect_button(ui: &mut Ui) -> Option<UiAction> {
+    ui.add(Components::danger_button("🔌 Disconnect"))
+        .clicked()
+        .then_some(UiAction::Disconnect)
+}
+
+impl MinerState {
+    /// State-specific rendering for the current variant
+    pub fn view(&self) -> &dyn StateView {
+        match self {
+            Self::Idle => &IdleView,
+            Self::Connecting(s) => s,
+            Self::Connected(s) => s,
+            Self::Mining(s) => s,
+            Self::Paused(s) => s,
+        }
+    }
+
+    /// Connected node client, if any
+    pub fn api(&self) -> Option<&Arc<KaspaApi>> {
+        match self {
+            Self::Connected(s) => Some(&s.api),
+            Self::Mining(s) => Some(&s.api),
+            Self::Paused(s) => Some(&s.api),
+            Self::Idle | Self::Connecting(_) => None,
+        }
+    }
+
+    /// Metrics of the current (or paused) mining session
+    pub fn metrics(&self) -> Option<&Arc<CpuMinerMetrics>> {
+        match self {
+            Self::Mining(s) => Some(s.handle.metrics()),
+            Self::Paused(s) => Some(&s.metrics),
+            _ => None,
+        }
+    }
+
+    pub fn is_mining(&self) -> bool {
+        matches!(self, Self::Mining(_))
+    }
+}
+
+impl ConnectedState {
+    /// Start mining on this connection, with the miner's template and submit tasks
+    /// on `runtime`. Fails if a mining address is for a different network than the node.
+    pub fn on_start(
+        self,
+        config: CpuMinerConfig,
+        runtime: &Handle,
+    ) -> Result<MiningState, (Self, anyhow::Error)> {
+        match start_cpu_miner(Arc::clone(&self.api), config.clone(), runtime) {
+            Ok(handle) => Ok(MiningState {
+                api: self.api,
+                handle,
+                config,
+            }),
+            Err(e) => Err((self, e)),
+        }
+    }
+}
+
+impl MiningState {
+    /// The caller must be inside a Tokio runtime context: the miner finishes
+    /// shutting down in a background task
+    pub fn on_stop(self) -> ConnectedState {
+        tokio::spawn(self.handle.stop());
+        ConnectedState { api: self.api }
+    }
+
+    /// Same runtime requirement as `on_stop`
+    pub fn on_pause(self) -> PausedState {
+        let metrics = Arc::clone(self.handle.metrics());
+        tokio::spawn(self.handle.stop());
+        PausedState {
+            api: self.api,
+            metrics,
+            config: self.config,
+        }
+    }
+}
+
+impl PausedState {
+    pub fn on_stop(self) -> ConnectedState {
+        ConnectedState { api: self.api }
+    }
+
+    /// Like `ConnectedState::on_start`, with the paused session's config
+    pub fn on_resume(self, runtime: &Handle) -> Result<MiningState, (Self, anyhow::Error)> {
+        match start_cpu_miner(Arc::clone(&self.api), self.config.clone(), runtime) {
+            Ok(handle) => Ok(MiningState {
+                api: self.api,
+                handle,
+                config: self.config,
+            }),
+            Err(e) => Err((self, e)),
+        }
+    }
+}
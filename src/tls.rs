@@ -0,0 +1,189 @@
+//! TLS for node connections. kaspa-grpc-client only opens plaintext channels, so
+//! a TLS connection goes through a tunnel: the client connects to a local port and
+//! every connection accepted there is forwarded to the node over TLS.
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::{self, CryptoProvider};
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{
+    ClientConfig, DigitallySignedStruct, Error as TlsError, RootCertStore, SignatureScheme,
+};
+use tokio_rustls::TlsConnector;
+use tracing::{debug, warn};
+
+/// Accepts the node's certificate only if the SHA-256 of its DER encoding matches.
+/// The chain and host name are not checked: pinning replaces the CA trust.
+#[derive(Debug)]
+struct FingerprintVerifier {
+    expected: [u8; 32],
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for FingerprintVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, TlsError> {
+        let actual: [u8; 32] = Sha256::digest(end_entity.as_ref()).into();
+        if actual == self.expected {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(TlsError::General(format!(
+                "certificate fingerprint {} does not match the pinned one",
+                to_hex(&actual)
+            )))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// TLS settings for gRPC: the pinned `fingerprint` if given, the Mozilla root
+/// certificates otherwise
+pub fn connector(fingerprint: Option<[u8; 32]>) -> Result<TlsConnector> {
+    let provider = Arc::new(crypto::ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .context("No usable TLS protocol version")?;
+    let mut config = match fingerprint {
+        Some(expected) => builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(FingerprintVerifier { expected, provider }))
+            .with_no_client_auth(),
+        None => builder
+            .with_root_certificates(RootCertStore {
+                roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+            })
+            .with_no_client_auth(),
+    };
+    // gRPC runs over HTTP/2
+    config.alpn_protocols = vec![b"h2".to_vec()];
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// A running tunnel; stops accepting connections when dropped
+pub struct TlsTunnel {
+    local_addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl TlsTunnel {
+    /// Listen on a free local port and forward each connection to `node`
+    /// (`host:port`) over TLS
+    pub async fn start(node: &str, connector: TlsConnector) -> Result<Self> {
+        let (host, _port) = node
+            .rsplit_once(':')
+            .with_context(|| format!("Node address {} has no port", node))?;
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let server_name = ServerName::try_from(host.to_string())
+            .with_context(|| format!("{} is not a valid TLS server name", host))?;
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .context("Failed to open the local TLS tunnel port")?;
+        let local_addr = listener.local_addr()?;
+        let node = node.to_string();
+        debug!("TLS tunnel to {} listening on {}", node, local_addr);
+
+        let task = tokio::spawn(async move {
+            loop {
+                let mut inbound = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        warn!(
+                            "TLS tunnel to {} stopped accepting connections: {}",
+                            node, e
+                        );
+                        return;
+                    }
+                };
+                let node = node.clone();
+                let server_name = server_name.clone();
+                let connector = connector.clone();
+                tokio::spawn(async move {
+                    let outbound = match TcpStream::connect(&node).await {
+                        Ok(stream) => stream,
+                        Err(e) => {
+                            warn!("Failed to reach {}: {}", node, e);
+                            return;
+                        }
+                    };
+                    let mut outbound = match connector.connect(server_name, outbound).await {
+                        Ok(stream) => stream,
+                        Err(e) => {
+                            warn!("TLS handshake with {} failed: {}", node, e);
+                            return;
+                        }
+                    };
+                    if let Err(e) = tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await
+                    {
+                        debug!("TLS tunnel connection to {} closed: {}", node, e);
+                    }
+                });
+            }
+        });
+        Ok(Self { local_addr, task })
+    }
+
+    /// Address for the gRPC client to connect to instead of the node
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for TlsTunnel {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
@@ -0,0 +1,217 @@
+use crate::AppState;
+use sysinfo::{Components, Pid, System};
+
+/// Samples overall CPU usage across all cores
+pub struct SystemLoad {
+    system: System,
+}
+
+impl SystemLoad {
+    pub fn new() -> Self {
+        let mut system = System::new();
+        // The first reading is always 0; prime it so the next sample is meaningful
+        system.refresh_cpu();
+        Self { system }
+    }
+
+    /// Average CPU usage across all cores since the previous call, in percent (0-100)
+    pub fn cpu_usage(&mut self) -> f32 {
+        self.system.refresh_cpu();
+        self.system.global_cpu_info().cpu_usage()
+    }
+}
+
+impl Default for SystemLoad {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Samples the CPU usage of this process alone, so load from other programs
+/// doesn't count against the miner
+pub struct ProcessLoad {
+    system: System,
+    pid: Option<Pid>,
+}
+
+impl ProcessLoad {
+    pub fn new() -> Self {
+        let mut system = System::new();
+        let pid = sysinfo::get_current_pid().ok();
+        // Like `SystemLoad`, the first reading is always 0
+        system.refresh_cpu();
+        if let Some(pid) = pid {
+            system.refresh_process(pid);
+        }
+        Self { system, pid }
+    }
+
+    /// This process's CPU usage since the previous call, in percent of all cores
+    /// (0-100). 0 if the process can't be inspected.
+    pub fn cpu_usage(&mut self) -> f32 {
+        let Some(pid) = self.pid else {
+            return 0.0;
+        };
+        self.system.refresh_cpu();
+        if !self.system.refresh_process(pid) {
+            return 0.0;
+        }
+        let cores = self.system.cpus().len().max(1) as f32;
+        self.system
+            .process(pid)
+            .map_or(0.0, |process| process.cpu_usage() / cores)
+    }
+}
+
+impl Default for ProcessLoad {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Samples the temperature sensors, e.g. for backing off when the CPU runs hot
+pub struct CpuTemperature {
+    components: Components,
+}
+
+impl CpuTemperature {
+    pub fn new() -> Self {
+        Self {
+            components: Components::new_with_refreshed_list(),
+        }
+    }
+
+    /// Highest temperature reported by any sensor, in °C. `None` if the system
+    /// exposes no sensors.
+    pub fn max_celsius(&mut self) -> Option<f32> {
+        self.components.refresh();
+        self.components
+            .iter()
+            .map(|c| c.temperature())
+            .filter(|t| t.is_finite() && *t > 0.0)
+            .reduce(f32::max)
+    }
+}
+
+impl Default for CpuTemperature {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Recommended number of mining threads given `current_load`, the CPU usage in
+/// percent that is *not* caused by the miner. One physical core is kept free for
+/// every core's worth of other load (rounded up), and at least one thread is
+/// always recommended.
+pub fn recommend_thread_count(physical_cores: usize, current_load: f32) -> usize {
+    let physical_cores = physical_cores.max(1);
+    let busy_cores = (current_load.clamp(0.0, 100.0) / 100.0 * physical_cores as f32).ceil();
+    physical_cores.saturating_sub(busy_cores as usize).max(1)
+}
+
+/// Human-readable recommendation for the thread slider. `total_load` is the
+/// overall CPU usage including the miner; each mining thread is assumed to
+/// saturate one logical CPU.
+pub fn thread_recommendation(
+    physical_cores: usize,
+    logical_cpus: usize,
+    threads: usize,
+    mining: bool,
+    total_load: f32,
+) -> String {
+    let miner_load = if mining {
+        threads as f32 / logical_cpus.max(1) as f32 * 100.0
+    } else {
+        0.0
+    };
+    let recommended = recommend_thread_count(physical_cores, total_load - miner_load);
+
+    if recommended < threads {
+        format!(
+            "You're running at {:.0}% CPU usage with {} threads; consider reducing to {} for system stability",
+            total_load, threads, recommended
+        )
+    } else if recommended > threads {
+        format!(
+            "Your CPU is {:.0}% idle; you could add {} more threads",
+            (100.0 - total_load).max(0.0),
+            recommended - threads
+        )
+    } else {
+        format!(
+            "{} threads looks right for the current CPU usage ({:.0}%)",
+            threads, total_load
+        )
+    }
+}
+
+/// Core counts used to size the configuration presets
+#[derive(Debug, Clone, Copy)]
+pub struct CpuInfo {
+    pub physical_cores: usize,
+    pub logical_cpus: usize,
+}
+
+impl CpuInfo {
+    pub fn detect() -> Self {
+        Self {
+            physical_cores: num_cpus::get_physical().max(1),
+            logical_cpus: num_cpus::get().max(1),
+        }
+    }
+}
+
+/// One-click configurations for users who don't want to tune settings by hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    LowPower,
+    MaxPerformance,
+}
+
+impl Preset {
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::LowPower => "Low Power",
+            Self::MaxPerformance => "Max Performance",
+        }
+    }
+
+    /// Tradeoffs shown in the confirmation dialog
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::LowPower => {
+                "Uses half of your physical cores, pauses briefly between hash batches, \
+                 caps CPU usage at 50% and throttles above 75°C. Your computer stays cool \
+                 and responsive, but you will find blocks less often."
+            }
+            Self::MaxPerformance => {
+                "Uses every physical core with no throttle or CPU limit. This gives the \
+                 highest hashrate, but your computer may become hot, loud and slow to \
+                 respond while mining."
+            }
+        }
+    }
+
+    pub fn apply(self, state: &mut AppState, cpu_info: &CpuInfo) {
+        match self {
+            Self::LowPower => apply_low_power_preset(state, cpu_info),
+            Self::MaxPerformance => apply_max_performance_preset(state, cpu_info),
+        }
+    }
+}
+
+pub fn apply_low_power_preset(state: &mut AppState, cpu_info: &CpuInfo) {
+    state.threads = (cpu_info.physical_cores / 2).max(1);
+    state.threads_auto_set = false;
+    state.throttle_ms = Some(5);
+    state.cpu_limit_percent = Some(50);
+    state.thermal_throttle = true;
+    state.max_temp_c = 75;
+}
+
+pub fn apply_max_performance_preset(state: &mut AppState, cpu_info: &CpuInfo) {
+    state.threads = cpu_info.physical_cores.max(1);
+    state.threads_auto_set = false;
+    state.throttle_ms = None;
+    state.cpu_limit_percent = None;
+}
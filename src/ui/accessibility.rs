@@ -0,0 +1,68 @@
+use egui::Context;
+
+/// Default body text size in points (egui's built-in `TextStyle::Body` size)
+pub const DEFAULT_BASE_SIZE: f32 = 14.0;
+const MIN_BASE_SIZE: f32 = 10.0;
+const MAX_BASE_SIZE: f32 = 28.0;
+const SIZE_STEP: f32 = 1.0;
+
+/// Reference pixels-per-point the default sizes were designed for
+const REFERENCE_PIXELS_PER_POINT: f32 = 1.5;
+
+/// Font size settings for the GUI
+#[derive(Clone, Debug, PartialEq)]
+pub struct FontSizeConfig {
+    pub base_size: f32,
+    pub scale_with_dpi: bool,
+}
+
+impl Default for FontSizeConfig {
+    fn default() -> Self {
+        Self {
+            base_size: DEFAULT_BASE_SIZE,
+            scale_with_dpi: true,
+        }
+    }
+}
+
+impl FontSizeConfig {
+    /// Body text size after optional DPI scaling. DPI scaling only ever enlarges
+    /// text so standard-density displays keep the configured size.
+    pub fn effective_size(&self, pixels_per_point: f32) -> f32 {
+        if self.scale_with_dpi {
+            self.base_size * (pixels_per_point / REFERENCE_PIXELS_PER_POINT).max(1.0)
+        } else {
+            self.base_size
+        }
+    }
+
+    pub fn increase(&mut self) {
+        self.base_size = (self.base_size + SIZE_STEP).min(MAX_BASE_SIZE);
+    }
+
+    pub fn decrease(&mut self) {
+        self.base_size = (self.base_size - SIZE_STEP).max(MIN_BASE_SIZE);
+    }
+
+    /// Set the base size, e.g. from a saved config, keeping it within the +/- range
+    pub fn set_base_size(&mut self, size: f32) {
+        self.base_size = if size.is_finite() {
+            size.clamp(MIN_BASE_SIZE, MAX_BASE_SIZE)
+        } else {
+            DEFAULT_BASE_SIZE
+        };
+    }
+
+    /// Scale every egui text style proportionally to the effective base size
+    pub fn apply(&self, ctx: &Context) {
+        let scale = self.effective_size(ctx.pixels_per_point()) / DEFAULT_BASE_SIZE;
+        let defaults = egui::Style::default().text_styles;
+        let mut style = (*ctx.style()).clone();
+        for (text_style, font_id) in style.text_styles.iter_mut() {
+            if let Some(default) = defaults.get(text_style) {
+                font_id.size = default.size * scale;
+            }
+        }
+        ctx.set_style(style);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::ui::format;
 use crate::ui::theme::Theme;
 use egui::{Color32, Frame, RichText, Ui};
 
@@ -14,6 +15,17 @@ impl Components {
             .inner_margin(egui::Margin::same(16.0))
     }
 
+    /// Small outlined label, styled like `section_frame` with a colored border
+    pub fn badge(ui: &mut Ui, text: &str, color: Color32) {
+        Self::section_frame()
+            .stroke(egui::Stroke::new(1.0, color))
+            .rounding(4.0)
+            .inner_margin(egui::Margin::symmetric(4.0, 0.0))
+            .show(ui, |ui| {
+                ui.label(RichText::new(text).monospace().size(11.0).color(color));
+            });
+    }
+
     /// Create a styled frame for content areas
     pub fn content_frame() -> Frame {
         Frame::default()
@@ -64,6 +76,74 @@ impl Components {
         });
     }
 
+    /// Render key-value pairs as a two-column grid so values line up.
+    /// `key_width` is the minimum width of the key column.
+    pub fn kv_grid(ui: &mut Ui, id: &str, pairs: &[(&str, &str)], key_width: f32) {
+        egui::Grid::new(id)
+            .num_columns(2)
+            .min_col_width(key_width)
+            .spacing([12.0, 6.0])
+            .show(ui, |ui| {
+                for (key, value) in pairs {
+                    ui.label(RichText::new(*key).color(Theme::LIGHT_GRAY));
+                    ui.label(RichText::new(*value).color(Theme::WHITE));
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Render a full-width colored alert bar with a "×" dismiss button
+    pub fn alert_banner(ui: &mut Ui, text: &str, color: Color32, on_dismiss: impl FnOnce()) {
+        Frame::default()
+            .fill(color)
+            .rounding(6.0)
+            .inner_margin(egui::Margin::symmetric(12.0, 8.0))
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(text).color(Theme::WHITE));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("×").clicked() {
+                            on_dismiss();
+                        }
+                    });
+                });
+            });
+    }
+
+    /// Render an animated indeterminate progress bar for in-flight operations
+    pub fn progress_bar(ui: &mut Ui, text: &str) {
+        ui.add(
+            egui::ProgressBar::new(0.0)
+                .animate(true)
+                .text(RichText::new(text).color(Theme::LIGHT_GRAY).size(13.0)),
+        );
+    }
+
+    /// Render a spinning arc, one revolution per second. Repaints are only
+    /// requested while the spinner is being drawn.
+    pub fn spinner(ui: &mut Ui, size: f32, color: Color32) {
+        const SEGMENTS: usize = 24;
+        const ARC_LENGTH: f32 = std::f32::consts::PI * 1.5;
+
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+        if !ui.is_rect_visible(rect) {
+            return;
+        }
+        let time = ui.input(|i| i.time);
+        let start = (time.fract() as f32) * std::f32::consts::TAU;
+        let radius = size / 2.0 - 2.0;
+        let points: Vec<egui::Pos2> = (0..=SEGMENTS)
+            .map(|i| {
+                let angle = start + ARC_LENGTH * i as f32 / SEGMENTS as f32;
+                rect.center() + radius * egui::vec2(angle.cos(), angle.sin())
+            })
+            .collect();
+        ui.painter()
+            .add(egui::Shape::line(points, egui::Stroke::new(2.5, color)));
+        ui.ctx().request_repaint();
+    }
+
     /// Create a styled button with teal background
     pub fn teal_button(text: &str) -> egui::Button {
         egui::Button::new(RichText::new(text).color(Theme::WHITE))
@@ -88,3 +168,87 @@ impl Components {
             .min_size(egui::vec2(150.0, 35.0))
     }
 }
+
+/// Small line chart of recent hashrate samples (H/s), oldest first. Labels the
+/// min and max on the left and draws the mean as a faint horizontal line.
+pub struct SparklineWidget<'a> {
+    samples: &'a [f32],
+    height: f32,
+}
+
+impl<'a> SparklineWidget<'a> {
+    const LABEL_WIDTH: f32 = 72.0;
+
+    pub fn new(samples: &'a [f32]) -> Self {
+        Self {
+            samples,
+            height: 48.0,
+        }
+    }
+
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+}
+
+impl egui::Widget for SparklineWidget<'_> {
+    fn ui(self, ui: &mut Ui) -> egui::Response {
+        let size = egui::vec2(ui.available_width(), self.height);
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+        if self.samples.len() < 2 || !ui.is_rect_visible(rect) {
+            return response;
+        }
+
+        let min = self.samples.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = self
+            .samples
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max);
+        let mean = self.samples.iter().sum::<f32>() / self.samples.len() as f32;
+        // Avoid dividing by zero when every sample is the same
+        let range = (max - min).max(f32::EPSILON);
+
+        let painter = ui.painter_at(rect);
+        let plot = rect.with_min_x(rect.left() + Self::LABEL_WIDTH).shrink(2.0);
+        let to_y = |value: f32| plot.bottom() - (value - min) / range * plot.height();
+        let last = (self.samples.len() - 1) as f32;
+        let points: Vec<egui::Pos2> = self
+            .samples
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                egui::pos2(plot.left() + plot.width() * i as f32 / last, to_y(*value))
+            })
+            .collect();
+
+        painter.hline(
+            plot.x_range(),
+            to_y(mean),
+            egui::Stroke::new(1.0, Theme::LIGHT_GRAY.gamma_multiply(0.4)),
+        );
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.5, Theme::PRIMARY_TEAL),
+        ));
+
+        let font = egui::FontId::proportional(11.0);
+        painter.text(
+            rect.left_top(),
+            egui::Align2::LEFT_TOP,
+            format::hashrate(max as f64),
+            font.clone(),
+            Theme::LIGHT_GRAY,
+        );
+        painter.text(
+            rect.left_bottom(),
+            egui::Align2::LEFT_BOTTOM,
+            format::hashrate(min as f64),
+            font,
+            Theme::LIGHT_GRAY,
+        );
+
+        response.on_hover_text(format!("Mean: {}", format::hashrate(mean as f64)))
+    }
+}
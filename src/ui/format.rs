@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+/// Format an integer with comma thousands separators, e.g. `12,345,678`
+pub fn thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Format a hashrate in H/s with an SI unit, e.g. `15.0 GH/s`
+pub fn hashrate(hashes_per_sec: f64) -> String {
+    const UNITS: [&str; 7] = ["H/s", "kH/s", "MH/s", "GH/s", "TH/s", "PH/s", "EH/s"];
+    let mut value = hashes_per_sec.max(0.0);
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Format an amount in sompi as KAS, e.g. `43.75 KAS`
+pub fn kas(sompi: u64) -> String {
+    const SOMPI_PER_KAS: f64 = 100_000_000.0;
+    format!("{:.2} KAS", sompi as f64 / SOMPI_PER_KAS)
+}
+
+/// Format a difficulty with an SI prefix, e.g. `4.7 G`
+pub fn difficulty(difficulty: f64) -> String {
+    const PREFIXES: [&str; 7] = ["", "k", "M", "G", "T", "P", "E"];
+    let mut value = difficulty.max(0.0);
+    let mut prefix = 0;
+    while value >= 1000.0 && prefix < PREFIXES.len() - 1 {
+        value /= 1000.0;
+        prefix += 1;
+    }
+    format!("{:.1} {}", value, PREFIXES[prefix])
+        .trim_end()
+        .to_string()
+}
+
+/// Format an `estimated_time_to_block` result, e.g. `4h 23m` or `< 1 min`
+pub fn time_to_block(estimate: Option<Duration>) -> String {
+    let Some(estimate) = estimate else {
+        return "\u{221e} (no hashrate)".to_string();
+    };
+    let secs = estimate.as_secs();
+    if secs < 60 {
+        return "< 1 min".to_string();
+    }
+    let (days, hours, minutes) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
@@ -1,7 +1,10 @@
+pub mod accessibility;
 pub mod components;
+pub mod format;
 pub mod sections;
 pub mod theme;
 
+pub use accessibility::FontSizeConfig;
 pub use components::*;
 pub use sections::*;
 pub use theme::Theme;
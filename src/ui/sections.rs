@@ -1,69 +1,265 @@
-use crate::ui::components::Components;
+use crate::config::address_book::{AddressBook, AddressBookEntry, AddressType};
+use crate::config::profiles::Profiles;
+use crate::config::MinerProfile;
+use crate::miner::{DEFAULT_EXTRA_DATA, MAX_EXTRA_DATA_LEN};
+use crate::stats::estimated_time_to_block;
+use crate::tuning::{CpuInfo, Preset};
+use crate::ui::components::{Components, SparklineWidget};
+use crate::ui::format;
 use crate::ui::theme::Theme;
-use crate::AppState;
+use crate::{
+    AppState, BlockFoundEntry, CpuMinerConfig, CpuMinerMetrics, LogBuffer, LogFilter, StatusType,
+    UiAction,
+};
 use egui::{RichText, TextEdit, Ui};
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 
 /// UI sections for the miner application
 pub struct Sections;
 
+fn set_status(state: &mut AppState, message: impl Into<String>, status_type: StatusType) {
+    state.status_message = message.into();
+    state.status_type = status_type;
+}
+
+/// Wait this long after the last keystroke before validating the mining address
+const ADDRESS_VALIDATION_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Key column width shared by the key-value grids so sections line up
+const KV_KEY_WIDTH: f32 = 140.0;
+
 impl Sections {
-    /// Render the node connection section
-    pub fn node_connection<F1, F2>(
+    /// Saved-address menu shown next to an address field. Picking an entry
+    /// copies it into `field`; the bottom row saves `field` under `label`.
+    /// Returns the outcome of a save or remove, for the status bar.
+    fn address_book_menu(
         ui: &mut Ui,
-        state: &mut AppState,
-        on_connect: F1,
-        on_disconnect: F2,
-    ) where
-        F1: FnOnce(),
-        F2: FnOnce(),
-    {
-        Components::section_frame().show(ui, |ui| {
+        book: &mut AddressBook,
+        label: &mut String,
+        address_type: AddressType,
+        field: &mut String,
+    ) -> Option<anyhow::Result<String>> {
+        let mut outcome = None;
+        ui.menu_button("📖", |ui| {
+            let mut remove = None;
+            for entry in book.of_type(address_type) {
+                ui.horizontal(|ui| {
+                    let pick = ui.button(&entry.label).on_hover_text(&entry.address);
+                    if pick.clicked() {
+                        *field = entry.address.clone();
+                        ui.close_menu();
+                    }
+                    if ui.small_button("×").clicked() {
+                        remove = Some(entry.label.clone());
+                    }
+                });
+            }
+            if book.of_type(address_type).next().is_none() {
+                ui.label(RichText::new("No saved addresses").color(Theme::LIGHT_GRAY));
+            }
+            if let Some(removed) = remove {
+                book.remove(&removed, address_type);
+                outcome = Some(
+                    AddressBook::default_path()
+                        .and_then(|path| book.save(&path))
+                        .map(|_| format!("Removed \"{}\" from the address book", removed)),
+                );
+            }
+
+            ui.separator();
             ui.horizontal(|ui| {
-                ui.label(RichText::new("Address:").color(Theme::LIGHT_GRAY));
-                ui.add_space(10.0);
                 ui.add(
-                    TextEdit::singleline(&mut state.node_address)
-                        .desired_width(400.0)
-                        .frame(true),
+                    TextEdit::singleline(label)
+                        .hint_text("Label")
+                        .desired_width(120.0),
                 );
+                if ui.button("Save current").clicked() {
+                    let saved = book
+                        .add(AddressBookEntry {
+                            label: label.clone(),
+                            address: field.clone(),
+                            address_type,
+                        })
+                        .and_then(|_| AddressBook::default_path())
+                        .and_then(|path| book.save(&path))
+                        .map(|_| format!("Saved \"{}\" to the address book", label.trim()));
+                    if saved.is_ok() {
+                        label.clear();
+                    }
+                    outcome = Some(saved);
+                }
             });
+        });
+        outcome
+    }
 
-            ui.add_space(15.0);
+    /// Validate the mining address once typing pauses and show the result inline.
+    /// Addresses set elsewhere (profiles, sessions) are picked up the same way.
+    fn address_validation(ui: &mut Ui, state: &mut AppState) {
+        let address = state.mining_address.trim();
+        if address.is_empty() {
+            state.address_validation = None;
+            state.address_edited_at = None;
+            return;
+        }
 
-            ui.horizontal(|ui| {
-                if state.is_connected {
-                    if ui.add(Components::danger_button("🔌 Disconnect")).clicked() {
-                        on_disconnect();
+        let stale =
+            !matches!(&state.address_validation, Some((validated, _)) if validated == address);
+        if stale {
+            let edited_at = *state.address_edited_at.get_or_insert_with(Instant::now);
+            let waited = edited_at.elapsed();
+            if waited >= ADDRESS_VALIDATION_DEBOUNCE {
+                let result = crate::api::validate_address(address);
+                state.address_validation = Some((address.to_string(), result));
+                state.address_edited_at = None;
+            } else {
+                ui.ctx()
+                    .request_repaint_after(ADDRESS_VALIDATION_DEBOUNCE - waited);
+                return;
+            }
+        }
+
+        if let Some((_, result)) = &state.address_validation {
+            if result.is_valid {
+                let text = format!("Valid {} address, {}", result.network, result.address_type);
+                Components::status_indicator(ui, Theme::GREEN, &text);
+            } else {
+                let text = result.error.as_deref().unwrap_or("Invalid address");
+                Components::status_indicator(ui, Theme::RED, text);
+            }
+        }
+    }
+
+    /// Render the node connection section
+    pub fn node_connection<F>(ui: &mut Ui, state: &mut AppState, on_action: F)
+    where
+        F: FnOnce(UiAction),
+    {
+        Components::section_frame().show(ui, |ui| {
+            // The first address is the primary node, the rest are fallbacks
+            let mut remove = None;
+            let mut book_outcome = None;
+            let can_remove = state.node_addresses.len() > 1;
+            for (i, address) in state.node_addresses.iter_mut().enumerate() {
+                if i > 0 {
+                    ui.add_space(6.0);
+                }
+                ui.horizontal(|ui| {
+                    let label = if i == 0 { "Address:" } else { "Fallback:" };
+                    ui.label(RichText::new(label).color(Theme::LIGHT_GRAY));
+                    ui.add_space(10.0);
+                    ui.add(
+                        TextEdit::singleline(address)
+                            .desired_width(400.0)
+                            .frame(true),
+                    );
+                    let outcome = Self::address_book_menu(
+                        ui,
+                        &mut state.address_book,
+                        &mut state.address_book_label,
+                        AddressType::Node,
+                        address,
+                    );
+                    if outcome.is_some() {
+                        book_outcome = outcome;
                     }
-                } else {
-                    if ui.add(Components::teal_button("⚡ Connect")).clicked() {
-                        on_connect();
+                    if can_remove && ui.small_button("×").clicked() {
+                        remove = Some(i);
                     }
+                });
+            }
+            if let Some(i) = remove {
+                state.node_addresses.remove(i);
+            }
+            match book_outcome {
+                Some(Ok(message)) => set_status(state, message, StatusType::Success),
+                Some(Err(e)) => set_status(state, format!("{:#}", e), StatusType::Error),
+                None => {}
+            }
+            ui.add_space(6.0);
+            if ui.small_button("+ Add fallback node").clicked() {
+                state.node_addresses.push(String::new());
+            }
+
+            ui.add_space(10.0);
+            ui.checkbox(&mut state.connect_options.use_tls, "Use TLS");
+            if state.connect_options.use_tls {
+                ui.add_space(10.0);
+                let fingerprint = state
+                    .connect_options
+                    .cert_fingerprint
+                    .get_or_insert_with(String::new);
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Cert SHA-256:").color(Theme::LIGHT_GRAY));
+                    ui.add_space(10.0);
+                    ui.add(
+                        TextEdit::singleline(fingerprint)
+                            .hint_text("optional, e.g. AB:CD:...")
+                            .desired_width(400.0)
+                            .frame(true),
+                    );
+                });
+                if let Err(e) = state.connect_options.parsed_fingerprint() {
+                    ui.label(RichText::new(e.to_string()).color(Theme::RED));
                 }
-            });
+            }
+
+            if let Some(api) = state.miner.api() {
+                ui.add_space(10.0);
+                let on_off = |enabled: bool| if enabled { "on" } else { "off" };
+                let network = api.network_name();
+                Components::kv_grid(
+                    ui,
+                    "connection_details",
+                    &[
+                        ("Node", api.active_address()),
+                        ("Network", network.as_deref().unwrap_or("unknown")),
+                        ("Version", api.server_version().unwrap_or("unknown")),
+                        ("TLS", on_off(state.connect_options.use_tls)),
+                    ],
+                    KV_KEY_WIDTH,
+                );
+            }
+
+            ui.add_space(15.0);
+
+            if let Some(action) = state.miner.view().render_connection(ui) {
+                on_action(action);
+            }
         });
     }
 
     /// Render the mining configuration section
-    pub fn mining_config<F1, F2>(
-        ui: &mut Ui,
-        state: &mut AppState,
-        num_cpus: usize,
-        on_start: F1,
-        on_stop: F2,
-    ) where
-        F1: FnOnce(),
-        F2: FnOnce(),
+    pub fn mining_config<F>(ui: &mut Ui, state: &mut AppState, num_cpus: usize, on_action: F)
+    where
+        F: FnOnce(UiAction),
     {
         Components::section_frame().show(ui, |ui| {
             ui.horizontal(|ui| {
                 ui.label(RichText::new("Address:").color(Theme::LIGHT_GRAY));
                 ui.add_space(10.0);
-                ui.add(
+                let response = ui.add(
                     TextEdit::singleline(&mut state.mining_address)
                         .desired_width(400.0)
                         .frame(true),
                 );
+                if response.changed() {
+                    state.address_edited_at = Some(Instant::now());
+                }
+                let book_outcome = Self::address_book_menu(
+                    ui,
+                    &mut state.address_book,
+                    &mut state.address_book_label,
+                    AddressType::Mining,
+                    &mut state.mining_address,
+                );
+                match book_outcome {
+                    Some(Ok(message)) => set_status(state, message, StatusType::Success),
+                    Some(Err(e)) => set_status(state, format!("{:#}", e), StatusType::Error),
+                    None => {}
+                }
+                Self::address_validation(ui, state);
             });
 
             ui.add_space(15.0);
@@ -71,10 +267,41 @@ impl Sections {
             ui.horizontal(|ui| {
                 ui.label(RichText::new("threads:").color(Theme::LIGHT_GRAY));
                 ui.add_space(10.0);
-                ui.add(egui::Slider::new(&mut state.threads, 1..=num_cpus).show_value(false));
+                // A loaded profile may come from a machine with more CPUs
+                state.threads = state.threads.clamp(1, num_cpus);
+                let slider =
+                    ui.add(egui::Slider::new(&mut state.threads, 1..=num_cpus).show_value(false));
+                if slider.changed() {
+                    state.threads_auto_set = false;
+                }
                 ui.label(RichText::new(format!("{}", state.threads)).color(Theme::LIGHT_GRAY));
+                ui.label(
+                    RichText::new(format!(
+                        "(physical cores: {})",
+                        CpuMinerConfig::default_threads()
+                    ))
+                    .size(13.0)
+                    .color(Theme::LIGHT_GRAY),
+                );
             });
 
+            // Only advise once the user has picked a thread count themselves
+            if let (false, Some(load)) = (state.threads_auto_set, state.cpu_load) {
+                ui.add_space(8.0);
+                let recommendation = crate::tuning::thread_recommendation(
+                    num_cpus::get_physical(),
+                    num_cpus,
+                    state.threads,
+                    state.miner.is_mining(),
+                    load,
+                );
+                ui.label(
+                    RichText::new(recommendation)
+                        .size(13.0)
+                        .color(Theme::ACCENT_TEAL),
+                );
+            }
+
             ui.add_space(15.0);
 
             ui.horizontal(|ui| {
@@ -91,37 +318,248 @@ impl Sections {
                 }
             });
 
-            ui.add_space(20.0);
+            ui.add_space(15.0);
 
             ui.horizontal(|ui| {
-                if ui
-                    .add_enabled(
-                        state.is_connected && !state.is_mining,
-                        Components::primary_button("▶ Start Mining"),
-                    )
-                    .clicked()
-                {
-                    on_start();
+                ui.label(RichText::new("Extra data (optional):").color(Theme::LIGHT_GRAY));
+                ui.add_space(10.0);
+                let response = ui.add(
+                    TextEdit::singleline(&mut state.extra_data)
+                        .hint_text(String::from_utf8_lossy(DEFAULT_EXTRA_DATA))
+                        .desired_width(250.0)
+                        .frame(true),
+                );
+                if response.changed() {
+                    // Cut at the last whole character that fits
+                    while state.extra_data.len() > MAX_EXTRA_DATA_LEN {
+                        state.extra_data.pop();
+                    }
                 }
+                ui.label(
+                    RichText::new(format!(
+                        "{}/{} bytes",
+                        state.extra_data.len(),
+                        MAX_EXTRA_DATA_LEN
+                    ))
+                    .size(13.0)
+                    .color(Theme::LIGHT_GRAY),
+                );
+            });
+
+            ui.add_space(15.0);
 
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("CPU limit (%, optional):").color(Theme::LIGHT_GRAY));
                 ui.add_space(10.0);
+                let mut limit_str = state
+                    .cpu_limit_percent
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                let response = ui.add(
+                    TextEdit::singleline(&mut limit_str)
+                        .desired_width(150.0)
+                        .frame(true),
+                );
+                if response.changed() {
+                    state.cpu_limit_percent =
+                        limit_str.parse().ok().filter(|v| (1..=100).contains(v));
+                }
+            });
 
-                if ui
-                    .add_enabled(state.is_mining, Components::danger_button("⏹ Stop Mining"))
-                    .clicked()
-                {
-                    on_stop();
+            ui.add_space(15.0);
+
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Presets:").color(Theme::LIGHT_GRAY));
+                ui.add_space(10.0);
+                for preset in [Preset::LowPower, Preset::MaxPerformance] {
+                    if ui.button(preset.title()).clicked() {
+                        state.pending_preset = Some(preset);
+                    }
                 }
             });
+            if state.thermal_throttle {
+                ui.add_space(8.0);
+                ui.label(
+                    RichText::new(format!("Thermal throttle above {}°C", state.max_temp_c))
+                        .size(13.0)
+                        .color(Theme::LIGHT_GRAY),
+                );
+            }
+            Self::preset_confirmation(ui.ctx(), state);
+
+            ui.add_space(15.0);
+
+            ui.horizontal(|ui| {
+                if ui.button("Save Profile").clicked() {
+                    let saved = MinerProfile::default_path()
+                        .and_then(|path| MinerProfile::from_state(state).save(&path).map(|_| path));
+                    match saved {
+                        Ok(path) => set_status(
+                            state,
+                            format!("Profile saved to {}", path.display()),
+                            StatusType::Success,
+                        ),
+                        Err(e) => set_status(state, format!("{:#}", e), StatusType::Error),
+                    }
+                }
+                if ui.button("Load Profile").clicked() {
+                    let loaded =
+                        MinerProfile::default_path().and_then(|path| MinerProfile::load(&path));
+                    match loaded {
+                        Ok(profile) => {
+                            profile.apply_to(state);
+                            set_status(state, "Profile loaded", StatusType::Success);
+                        }
+                        Err(e) => set_status(state, format!("{:#}", e), StatusType::Error),
+                    }
+                }
+            });
+
+            ui.add_space(10.0);
+            Self::profile_selector(ui, state);
+
+            ui.add_space(20.0);
+
+            let action = ui
+                .horizontal(|ui| state.miner.view().render_mining_controls(ui))
+                .inner;
+            if let Some(action) = action {
+                on_action(action);
+            }
         });
     }
 
+    /// Named profile dropdown, plus saving the current settings under a name.
+    /// Picking a profile fills in all of its fields.
+    fn profile_selector(ui: &mut Ui, state: &mut AppState) {
+        let mut picked = None;
+        let mut save = false;
+        let mut delete = false;
+        ui.horizontal(|ui| {
+            let selected_text = state.selected_profile.as_deref().unwrap_or("None");
+            egui::ComboBox::from_label("Profile")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    for name in state.profiles.names() {
+                        let selected = state.selected_profile.as_deref() == Some(name.as_str());
+                        if ui.selectable_label(selected, &name).clicked() {
+                            picked = Some(name);
+                        }
+                    }
+                });
+            if state.selected_profile.is_some() && ui.small_button("×").clicked() {
+                delete = true;
+            }
+            ui.add_space(10.0);
+            ui.add(
+                TextEdit::singleline(&mut state.profile_name)
+                    .hint_text("Profile name")
+                    .desired_width(150.0),
+            );
+            save = ui.button("Save as Profile").clicked();
+        });
+
+        if let Some(name) = picked {
+            let config = state.profiles.get(&name).map(|p| p.config.clone());
+            if let Some(config) = config {
+                config.apply_to(state);
+                set_status(
+                    state,
+                    format!("Profile \"{}\" loaded", name),
+                    StatusType::Success,
+                );
+                state.selected_profile = Some(name);
+            }
+        }
+        if delete {
+            if let Some(name) = state.selected_profile.take() {
+                let deleted = state
+                    .profiles
+                    .delete(&name)
+                    .and_then(|_| Profiles::default_path())
+                    .and_then(|path| state.profiles.save(&path));
+                match deleted {
+                    Ok(()) => set_status(
+                        state,
+                        format!("Profile \"{}\" deleted", name),
+                        StatusType::Success,
+                    ),
+                    Err(e) => set_status(state, format!("{:#}", e), StatusType::Error),
+                }
+            }
+        }
+        if save {
+            let name = state.profile_name.trim().to_string();
+            let config = MinerProfile::from_state(state);
+            let result = if state.profiles.get(&name).is_some() {
+                state.profiles.update(&name, config)
+            } else {
+                state.profiles.create(&name, config)
+            };
+            let saved = result
+                .and_then(|_| Profiles::default_path())
+                .and_then(|path| state.profiles.save(&path));
+            match saved {
+                Ok(()) => {
+                    set_status(
+                        state,
+                        format!("Profile \"{}\" saved", name),
+                        StatusType::Success,
+                    );
+                    state.selected_profile = Some(name);
+                    state.profile_name.clear();
+                }
+                Err(e) => set_status(state, format!("{:#}", e), StatusType::Error),
+            }
+        }
+    }
+
+    /// Confirmation dialog for `state.pending_preset`, explaining its tradeoffs
+    fn preset_confirmation(ctx: &egui::Context, state: &mut AppState) {
+        let Some(preset) = state.pending_preset else {
+            return;
+        };
+        let mut decision = None;
+        egui::Window::new(format!("Apply {} preset?", preset.title()))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.set_max_width(360.0);
+                ui.label(RichText::new(preset.description()).color(Theme::LIGHT_GRAY));
+                if state.miner.is_mining() {
+                    ui.add_space(8.0);
+                    ui.label(
+                        RichText::new("Restart mining to apply the new settings.")
+                            .color(Theme::ACCENT_TEAL),
+                    );
+                }
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.add(Components::primary_button("Apply")).clicked() {
+                        decision = Some(true);
+                    }
+                    ui.add_space(10.0);
+                    if ui.add(Components::teal_button("Cancel")).clicked() {
+                        decision = Some(false);
+                    }
+                });
+            });
+        if let Some(apply) = decision {
+            if apply {
+                preset.apply(state, &CpuInfo::detect());
+            }
+            state.pending_preset = None;
+        }
+    }
+
     /// Render the status section
     pub fn status(ui: &mut Ui, status_message: &str, status_type: &crate::StatusType) {
         Components::content_frame().show(ui, |ui| {
             if !status_message.is_empty() {
                 let color = match status_type {
                     crate::StatusType::Success => Theme::PRIMARY_TEAL,
+                    crate::StatusType::Warning => Theme::AMBER,
                     crate::StatusType::Error => Theme::RED,
                     crate::StatusType::Info => Theme::ACCENT_TEAL,
                 };
@@ -133,28 +571,59 @@ impl Sections {
         });
     }
 
-    /// Render the mining statistics section
+    /// Render the mining statistics section. `difficulty` comes from the latest
+    /// `DagInfo` and `local_hashrate` is this miner's rate in H/s.
     pub fn mining_stats(
         ui: &mut Ui,
         is_mining: bool,
-        hashes: Option<u64>,
-        blocks_submitted: Option<u64>,
-        blocks_accepted: Option<u64>,
+        metrics: Option<&CpuMinerMetrics>,
+        difficulty: Option<f64>,
+        local_hashrate: Option<f64>,
+        hashrate_history: &[f32],
     ) {
         Components::content_frame().show(ui, |ui| {
             if is_mining {
-                if let (Some(h), Some(bs), Some(ba)) = (hashes, blocks_submitted, blocks_accepted) {
-                    ui.label(
-                        RichText::new(format!("Hashes Tried: {}", h)).color(Theme::LIGHT_GRAY),
-                    );
-                    ui.add_space(10.0);
-                    ui.label(
-                        RichText::new(format!("Blocks Submitted: {}", bs)).color(Theme::LIGHT_GRAY),
-                    );
-                    ui.add_space(10.0);
-                    ui.label(
-                        RichText::new(format!("Blocks Accepted: {}", ba)).color(Theme::LIGHT_GRAY),
-                    );
+                if let Some(metrics) = metrics {
+                    let values = [
+                        metrics.hashes_tried.load(Ordering::Relaxed).to_string(),
+                        local_hashrate
+                            .map(format::hashrate)
+                            .unwrap_or_else(|| "measuring...".to_string()),
+                        metrics.blocks_submitted.load(Ordering::Relaxed).to_string(),
+                        metrics.blocks_accepted.load(Ordering::Relaxed).to_string(),
+                        metrics.blocks_rejected.load(Ordering::Relaxed).to_string(),
+                        difficulty.map(format::difficulty).unwrap_or_default(),
+                    ];
+                    let mut pairs = vec![
+                        ("Hashes Tried", values[0].as_str()),
+                        ("Hashrate", values[1].as_str()),
+                        ("Blocks Submitted", values[2].as_str()),
+                        ("Blocks Accepted", values[3].as_str()),
+                        ("Blocks Rejected", values[4].as_str()),
+                    ];
+                    if difficulty.is_some() {
+                        pairs.push(("Network difficulty", values[5].as_str()));
+                    }
+                    Components::kv_grid(ui, "mining_stats", &pairs, KV_KEY_WIDTH);
+                    let dropped = metrics.blocks_dropped_backpressure.load(Ordering::Relaxed);
+                    if dropped > 0 {
+                        ui.label(
+                            RichText::new(format!("Dropped (submit queue full): {}", dropped))
+                                .color(Theme::RED),
+                        );
+                    }
+                    // Tells orphaned (stale) blocks apart from invalid ones
+                    if let Some(reason) = metrics.last_rejection_reason.lock().as_deref() {
+                        ui.label(
+                            RichText::new(format!("Last rejection: {}", reason))
+                                .small()
+                                .color(Theme::AMBER),
+                        );
+                    }
+                    if hashrate_history.len() >= 2 {
+                        ui.add_space(8.0);
+                        ui.add(SparklineWidget::new(hashrate_history));
+                    }
                 } else {
                     ui.label(
                         RichText::new("Waiting for mining to start...").color(Theme::LIGHT_GRAY),
@@ -166,6 +635,211 @@ impl Sections {
                         .color(Theme::LIGHT_GRAY),
                 );
             }
+
+            if let Some(difficulty) = difficulty {
+                let hashrate = local_hashrate.filter(|_| is_mining).unwrap_or(0.0);
+                let estimate = estimated_time_to_block(hashrate, difficulty);
+                ui.add_space(10.0);
+                ui.label(
+                    RichText::new(format!(
+                        "~Est. time to block: {}",
+                        format::time_to_block(estimate)
+                    ))
+                    .color(Theme::ACCENT_TEAL),
+                );
+                ui.label(
+                    RichText::new("Block discovery is random; this is an average, not a countdown")
+                        .small()
+                        .color(Theme::LIGHT_GRAY),
+                );
+            }
+        });
+    }
+
+    /// Render accepted blocks, newest first
+    pub fn block_history(ui: &mut Ui, entries: &[BlockFoundEntry]) {
+        Components::content_frame().show(ui, |ui| {
+            if entries.is_empty() {
+                ui.label(RichText::new("No blocks found yet").color(Theme::LIGHT_GRAY));
+                return;
+            }
+            egui::ScrollArea::vertical()
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    for entry in entries.iter().rev() {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new("✔").color(Theme::GREEN));
+                            ui.label(entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string());
+                            ui.label(
+                                RichText::new(format!("nonce {:#018x}", entry.nonce))
+                                    .monospace()
+                                    .color(Theme::LIGHT_GRAY),
+                            );
+                        });
+                    }
+                });
         });
     }
+
+    /// Render the network info section. `local_hashrate` is this miner's rate in
+    /// H/s, used to show its share of the network.
+    pub fn network_info(ui: &mut Ui, state: &AppState, local_hashrate: Option<f64>) {
+        Components::content_frame().show(ui, |ui| {
+            let Some(info) = state.dag_info.as_ref() else {
+                ui.label(
+                    RichText::new("Network info will appear here once connected")
+                        .color(Theme::LIGHT_GRAY),
+                );
+                return;
+            };
+
+            let bps = info
+                .bps
+                .map(|bps| format!("{:.1}", bps))
+                .unwrap_or_else(|| "measuring...".to_string());
+            let network_hashrate = info.network_hashrate();
+            let network_hashrate_text = network_hashrate
+                .map(|h| format!("~{}", format::hashrate(h)))
+                .unwrap_or_else(|| "measuring...".to_string());
+
+            let mut rows = vec![
+                (
+                    "Virtual DAA Score",
+                    format::thousands(info.virtual_daa_score),
+                ),
+                ("BPS", bps),
+                ("Network Hashrate", network_hashrate_text),
+                ("Blue Score", format::thousands(info.blue_score)),
+            ];
+            if let Some(peers) = &state.peers {
+                let outbound = peers.iter().filter(|p| p.is_outbound).count();
+                rows.push(("Peers", format!("{} ({} out)", peers.len(), outbound)));
+            }
+            if let Some(supply) = &state.coin_supply {
+                rows.push((
+                    "Mined",
+                    format!("{:.1}% of max supply", supply.mining_progress()),
+                ));
+            }
+            if let Some(reward) = state.block_reward_sompi {
+                rows.push(("Block Reward", format::kas(reward)));
+            }
+            let pairs: Vec<(&str, &str)> = rows.iter().map(|(k, v)| (*k, v.as_str())).collect();
+            Components::kv_grid(ui, "network_info", &pairs, KV_KEY_WIDTH);
+
+            if let (Some(local), Some(network)) = (local_hashrate, network_hashrate) {
+                if network > 0.0 {
+                    ui.add_space(10.0);
+                    ui.label(
+                        RichText::new(format!("Your share: {:.3}%", local / network * 100.0))
+                            .color(Theme::ACCENT_TEAL),
+                    );
+                }
+            }
+        });
+    }
+
+    /// Render the log panel with Copy All / Clear controls, level and text
+    /// filters, and line selection. Clicking a row selects it; Ctrl+C then
+    /// copies just that line. The view follows new entries unless scrolled up.
+    pub fn log_panel<F1, F2>(
+        ui: &mut Ui,
+        entries: &LogBuffer,
+        filter: &mut LogFilter,
+        selected_line: &mut Option<usize>,
+        on_clear: F1,
+        on_copy_all: F2,
+    ) where
+        F1: FnOnce(),
+        F2: FnOnce(),
+    {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Logs").size(18.0).color(Theme::WHITE));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("Clear").clicked() {
+                    on_clear();
+                }
+                if ui.button("Copy All").clicked() {
+                    on_copy_all();
+                }
+            });
+        });
+        ui.horizontal(|ui| {
+            for level in [
+                tracing::Level::TRACE,
+                tracing::Level::DEBUG,
+                tracing::Level::INFO,
+                tracing::Level::WARN,
+                tracing::Level::ERROR,
+            ] {
+                ui.radio_value(&mut filter.min_level, level, level.as_str());
+            }
+            ui.add_space(10.0);
+            ui.add(
+                TextEdit::singleline(&mut filter.search)
+                    .hint_text("Search")
+                    .desired_width(200.0),
+            );
+        });
+        ui.separator();
+
+        // Drop a selection that no longer points at a line (e.g. after old lines rotated out)
+        if selected_line.is_some_and(|idx| idx >= entries.len()) {
+            *selected_line = None;
+        }
+
+        let copy_requested = ui.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Copy)));
+        if copy_requested {
+            if let Some(entry) = selected_line.and_then(|idx| entries.get(idx)) {
+                let text = entry.to_string();
+                ui.output_mut(|o| o.copied_text = text);
+            }
+        }
+
+        egui::ScrollArea::vertical()
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for (idx, entry) in entries.iter().enumerate() {
+                    if !filter.matches(entry) {
+                        continue;
+                    }
+                    let is_selected = *selected_line == Some(idx);
+                    let line = format!(
+                        "{} {}",
+                        entry.timestamp.format("%H:%M:%S%.3f"),
+                        entry.message
+                    );
+                    let text = RichText::new(line).monospace().size(12.0);
+                    let text = if is_selected {
+                        text.color(Theme::WHITE)
+                    } else {
+                        text
+                    };
+                    let clicked = ui
+                        .horizontal(|ui| {
+                            Components::badge(
+                                ui,
+                                &format!("{:<5}", entry.level),
+                                level_color(entry.level),
+                            );
+                            ui.selectable_label(is_selected, text).clicked()
+                        })
+                        .inner;
+                    if clicked {
+                        *selected_line = if is_selected { None } else { Some(idx) };
+                    }
+                }
+            });
+    }
+}
+
+/// Badge color of a log level in the log panel
+fn level_color(level: tracing::Level) -> egui::Color32 {
+    match level {
+        tracing::Level::TRACE => Theme::LIGHT_GRAY,
+        tracing::Level::DEBUG => Theme::BLUE,
+        tracing::Level::INFO => Theme::PRIMARY_TEAL,
+        tracing::Level::WARN => Theme::AMBER,
+        _ => Theme::RED,
+    }
 }
@@ -14,6 +14,8 @@ impl Theme {
     pub const WHITE: Color32 = Color32::from_rgb(255, 255, 255);
     pub const RED: Color32 = Color32::from_rgb(220, 53, 69);
     pub const GREEN: Color32 = Color32::from_rgb(34, 197, 94);
+    pub const AMBER: Color32 = Color32::from_rgb(245, 158, 11);
+    pub const BLUE: Color32 = Color32::from_rgb(59, 130, 246);
 
     /// Apply the theme to egui visuals
     pub fn apply(visuals: &mut egui::style::Visuals) {